@@ -0,0 +1,295 @@
+# Messages of the dispatch of a job. Arguments are written as { $name }
+
+## Selection of the source
+select-source = Select the cronjob that you want to use as a base of the job
+select-sources = Select the cronjobs that you want to use as a base of the jobs
+listing-jobs = Getting list of jobs...
+listing-progress = Getting list of jobs... { $items } found ({ $pages } pages)
+fetching-object = Getting object details...
+target-name-from-source = Will use the name of the target job to create the job
+no-release-workload = No workload found for the release { $release }
+no-source-selected = No source selected
+non-interactive-source = The name of the source is needed in non-interactive mode (--job-name)
+selected-object-not-found = Unable to find the selected object { $name }
+suspended-source-warning = The cronjob { $name } is suspended, make sure that a manual run is expected
+upcoming-runs = Next scheduled runs of { $name } ({ $schedule }):
+invalid-schedule = Unable to parse the schedule "{ $schedule }": { $error }
+no-previous-run = No previous run found for the cronjob
+previous-runs = Last runs of the cronjob:
+run-not-started = not started
+last-run-failures = Failures of the last run { $name }:
+source-not-idle = The cronjob { $name } has active jobs: { $jobs } (--if-idle)
+manual-jobs-running = Manual jobs dispatched from { $name } are still running: { $jobs } (--if-idle)
+run-history-failed = Unable to get the last runs of the cronjob due to: { $error }
+
+## Confirmations
+confirm-delete-existing = An job with the same name already exist. Do you want to delete this job
+host-access-warning = The job will have host-level access:
+confirm-host-access = Do you want to create a job with host-level access
+host-access-refused = Job with host-level access refused
+confirm-secret-display = Secret values will be displayed in clear text, make sure that your screen isn't shared. Continue ?
+confirm-restricted = Do you want to set these fields in the job ?
+restricted-missing-fields = The namespace enforces the restricted Pod Security profile. The following fields are missing or not allowed:
+confirm-version-skew = Do you want to create the job anyway
+forced = Forced ({ $option }):
+forced-change-env = changing the protected env { $name }
+forced-remove-env = removing the protected env { $name }
+forced-add-env = adding the protected env { $name }
+no-diff = No change between { $original } and { $modified }
+version-skew = { $feature } requires Kubernetes { $since } (server is { $version }), the job may be rejected or behave differently
+version-skew-removed = { $feature } requires Kubernetes { $since } (server is { $version }), it has been removed from the job
+version-skew-refused = Job using features unsupported by Kubernetes { $version } refused
+pod-security-failed = Unable to get the Pod Security profile of the namespace: { $error }
+
+## Edition of the job
+confirm-remove-env = Do you want to remove env ?
+select-remove-envs = Which variables do you want to remove from { $container } ?
+confirm-additional-env = Do you want to add additional env ?
+confirm-more-env = Do you still want to add additional env ?
+confirm-overwrite-env = { $name } is already defined ({ $current }), do you want to overwrite it ?
+confirm-resources = Do you want to update the resources limits ?
+confirm-metadata = Do you want to edit the labels and annotations of the { $target ->
+        [job] job
+       *[pods] pods
+    } ?
+current-metadata = Current { $kind ->
+        [label] labels
+       *[annotation] annotations
+    } of the { $target ->
+        [job] job
+       *[pods] pods
+    }: { $current }
+input-metadata = { $kind ->
+        [label] label
+       *[annotation] annotation
+    } to set on the { $target ->
+        [job] job
+       *[pods] pods
+    } (KEY=VALUE, KEY- to remove, empty to continue)
+confirm-review-metadata = Do you want to review the labels and annotations copied from the source ?
+select-metadata = Select the labels and annotations to copy onto the job
+metadata-not-copied = Not copied onto the job: { $keys }
+select-sidecars = Select the sidecars to keep
+native-sidecars = Native sidecars (init containers with restartPolicy: Always): { $names }
+removed-sidecars = Removed sidecars: { $names }
+select-scheduler = Select the scheduler of the pods
+select-runtime-class = Select the RuntimeClass of the pods
+confirm-mount-pvc = Do you want to mount a PersistentVolumeClaim ?
+select-pvc = Select the PersistentVolumeClaim to mount
+select-pvc-container = Select the container mounting the PersistentVolumeClaim
+input-pvc-mount-path = Input the path where { $claim } is mounted in { $container }
+invalid-mount-path = The mount path should be absolute
+select-precedence = The template already defines { $field }, which value should be used ?
+precedence-template = Keep the template value ({ $value })
+precedence-cli = Use the CLI value ({ $value })
+schedule-interval = Interval of the schedule: { $seconds }s
+schedule-interval-failed = Unable to compute the interval of the schedule, the activeDeadlineSeconds is kept as is
+next-run-failed = Unable to compute the next scheduled run of { $name } (--align-next-run)
+
+## Environment variables
+select-envs = Which variables do you want to change ?
+select-envs-page = Which variables do you want to change ? (page { $page }/{ $total })
+confirm-next-envs = Do you want to review the next variables ? ({ $remaining } remaining)
+env-from-source = Env from { $source } ({ $container })
+env-downward = Env for { $name } ({ $source })
+env-config-map-value = Env for { $name } (configmap { $reference }): { $value }
+env-secret-value = Env for { $name } (secret { $reference }): { $value }
+env-value-not-found = <not found>
+env-protected = Env { $name } is protected and kept unchanged (use --force to change it)
+env-duplicated = Env { $name } is defined { $count } times in the container { $container }
+container-not-found = Unable to find the container { $name } (--container)
+read-env-file-failed = Unable to read the env file { $path }: { $error }
+invalid-env-file = Invalid env file { $path }: { $error }
+expand-env-failed = Unable to expand the env { $name }: { $error }
+protected-env-flag = { $name } is protected, use --force to set it
+list-config-maps-failed = Unable to list the ConfigMaps due to: { $error }
+list-secrets-failed = Unable to list the Secrets due to: { $error }
+env-title = Env for { $name }:
+env-title-sidecar = Env for { $name } (sidecar { $sidecar }):
+env-title-init = Env for { $name } (init { $init }):
+env-title-reference = Env for { $name } ({ $reference }, a new value replaces the reference):
+env-title-secret = Env for { $name } ({ $reference }, hidden input, empty keeps the reference):
+env-title-sensitive = Env for { $name } (sensitive value, hidden input, empty keeps the value):
+select-secret-envs = Which variables should reference a Secret key instead of a literal ?
+select-secret-ref = Select the Secret referenced by { $name }
+input-secret-ref = Input the name of the Secret referenced by { $name }
+input-secret-key = Input the key of { $secret } referenced by { $name }
+select-env-value = Select the value to keep for the env { $name }
+select-env-container = Select the container to add the additional environment variable
+input-additional-env = Input the additional env separate with a =
+input-env-from-file = Input the env and the path of the file separate with a =
+select-additional-env-kind = Which kind of env do you want to add ?
+additional-env-literal = A value (KEY=VALUE)
+additional-env-file = The contents of a local file (KEY=PATH)
+additional-env-field = A field of the pod (downward API)
+additional-env-resource = A resource of the container (downward API)
+select-env-field = Select the field of the pod exposed as env
+select-env-resource = Select the resource of the container exposed as env
+input-downward-env = Input the name of the env exposing { $source }
+confirm-env-from = Do you want to edit the envFrom sources ?
+current-env-from = envFrom of { $container }: { $current }
+select-env-from-action = What do you want to do with the envFrom sources ?
+env-from-add-config-map = Add a ConfigMap
+env-from-add-secret = Add a Secret
+env-from-remove = Remove a source
+env-from-prefix = Set the prefix of a source
+env-from-done = Done
+select-env-from = Select the envFrom source
+select-env-from-name = Select the { $kind ->
+        [configmap] ConfigMap
+       *[secret] Secret
+    } whose keys are loaded as env
+input-env-from-name = Input the name of the { $kind ->
+        [configmap] ConfigMap
+       *[secret] Secret
+    } whose keys are loaded as env
+input-env-from-prefix = Input the prefix of the keys (empty to remove it)
+invalid-env-format = Environment variable should respect the format: ENV_NAME=VALUE
+invalid-env-file-format = Env read from a file should respect the format: ENV_NAME=PATH
+invalid-env-name = "{ $name }" isn't a valid env name, it should contain only letters, digits or '_' and not start with a digit
+invalid-env-line = Invalid env at the line { $line }: { $error }
+read-env-value-failed = Unable to read the file { $path } of the env { $name }: { $error }
+expansion-unclosed = Missing {"}"} after ${"{"}
+expansion-empty = Empty ${"{}"} in the value
+expansion-undefined = { $name } isn't defined in the local environment (use --no-expand to keep it as is)
+config-map-values-failed = Unable to resolve the ConfigMap values due to: { $error }
+secret-values-failed = Unable to resolve the Secret values due to: { $error }
+
+## Resources
+input-memory = Set the memory limits
+invalid-memory = Memory should contains only numbers
+select-memory-format = Select a memory format
+input-cpu = Set the cpu limits
+invalid-cpu = CPU should contains numbers
+invalid-cpu-min = CPU should be greater >= to 0.001
+select-cpu-format = Select a cpu format
+missing-resources-flags = Both --memory and --cpu are needed to set the resources
+
+## Creation of the job
+applying-dry-run = Running a dry-run job...
+applying = Applying job...
+bulk-progress = Job { $index }/{ $total } from { $source }
+bulk-creating = [{ $index }/{ $total }] Creating the job of { $source }
+bulk-job-failed = [{ $index }/{ $total }] The job of { $source } failed: { $error }
+bulk-failed = { $failed } of the { $total } jobs failed
+created-suspended = Job { $name } created suspended, it will start at { $run }
+job-failed = Unable to create job due to error: { $error }
+edited-job-without-spec = The edited job has no spec
+write-junit-failed = Unable to write the JUnit report { $path }: { $error }
+open-editor-failed = Unable to open the editor { $editor }: { $error }
+editor-exited = The editor { $editor } exited with { $status }
+edition-cancelled = Edition cancelled as the manifest is empty
+invalid-manifest = Invalid manifest: { $error }
+read-plugins-failed = Unable to read the plugins directory { $path }: { $error }
+run-plugin-failed = Unable to run { $path }: { $error }
+plugin-exited = exited with { $status }: { $stderr }
+plugin-invalid-spec = invalid JobSpec returned: { $error }
+wait-with-dry-run = Cannot use --wait with --dry-run
+server-version-failed = Unable to get the version of the API server: { $error }
+chain-stopped = The chain stopped at the job of { $source }: { $reason }
+chain-step-error = Error: { $error }
+chain-step-skipped = Skipped
+
+## Recovery of an interrupted run
+recover-deleted-job = The job { $name } was deleted but the new job wasn't created. Do you want to recreate the deleted job
+recover-suspended-job = The job { $name } is still suspended. What do you want to do
+recover-resume = Resume the job now
+recover-delete = Delete the job
+recover-keep = Leave it suspended
+recover-failed = Unable to recover from the interruption due to: { $error }
+debug-bundle-written = Debug bundle written to { $path }
+debug-bundle-failed = Unable to write the debug bundle due to: { $error }
+job-recreated = Job { $name } recreated
+job-resumed = Job { $name } resumed
+job-left-suspended = Job { $name } left suspended
+cronjob-suspended = Cronjob { $name } suspended
+cronjob-resumed = Cronjob { $name } resumed
+suspend-source-not-cronjob = --suspend-source requires a cronjob as the source
+
+## Management of the dispatched jobs
+doctor-failed = { $failed } of { $total } checks failed
+no-manual-job = No job dispatched with bakkutteh in the namespace { $namespace }
+not-manual-job = The job { $name } wasn't dispatched with bakkutteh
+job-run-failed = The job { $name } failed: { $reason }
+unknown-reason = unknown reason
+confirm-cancel = Do you want to cancel the job { $name } and delete its pods
+deleting-job = Deleting the job { $name }...
+job-cancelled = Job { $name } and its pods deleted
+job-cancelling = Job { $name } cancelled, its pods are being deleted
+job-rerun = Job { $name } dispatched again as { $created }
+invalid-age = Invalid age { $age }: { $error }
+no-prunable-job = No finished job older than { $age } to prune
+prunable-jobs = Finished jobs older than { $age }:
+confirm-prune = Do you want to delete these { $count } jobs
+job-deleted = Job { $name } deleted
+no-previous-dispatch = No previous dispatch of { $source } found in the namespace
+no-drift = No env or resources drift since the job { $name }
+drift = Drift since the job { $name }:
+invalid-duration = Invalid duration { $duration }: { $error }
+no-data-directory = Unable to find the data directory
+empty-history = No dispatch recorded in the history { $path }
+history-record-failed = Unable to record the dispatch in the history due to: { $error }
+read-manifest-failed = Unable to read the manifest { $path }: { $error }
+open-history-failed = Unable to open the history { $path }: { $error }
+read-history-failed = Unable to read the history { $path }: { $error }
+invalid-history-entry = Invalid entry at the line { $line } of the history { $path }: { $error }
+
+## Prompts
+interrupted = Operation interrupted
+end-of-input = Operation canceled: end of input
+non-interactive-unanswered = Unable to answer "{ $question }" in non-interactive mode, use the options or --replay to answer it
+invalid-input = Invalid input
+value-empty = The value can't be empty
+prompt-canceled = Operation canceled: { $error }
+prompt-validation-failed = Validation did not pass due to: { $error }
+prompt-select-failed = Unable to select the element due to: { $error }
+prompt-multi-select-failed = Unable to select the elements due to: { $error }
+prompt-confirm-failed = Unable to get the confirmation from the user: { $error }
+select-no-options = Unable to select the element due to: no options available
+plain-confirm-invalid = Please answer with y or n
+plain-select = Enter a number between 1 and { $count }:
+plain-multi-select = Enter the numbers separated by commas (empty keeps the selection):
+plain-invalid-choice = Invalid choice: { $answer }
+read-session-failed = Unable to read the session file { $path }: { $error }
+write-session-failed = Unable to write the session file { $path }: { $error }
+
+## Maintenance of the tool
+already-latest = bakkutteh { $version } is already the latest version
+updated = bakkutteh updated from { $current } to { $latest }
+unsupported-platform = No release is built for { $os } ({ $arch })
+invalid-release-version = Invalid version for release { $tag }: { $error }
+missing-release-asset = Release { $tag } doesn't contain { $name }
+empty-checksum = The checksum file is empty
+checksum-mismatch = Checksum mismatch, expected { $expected } but the artifact has { $digest }
+replace-binary-failed = Unable to replace { $path }: { $error }
+man-page-written = Man page written to { $path }
+
+## Kubernetes API
+read-only-refused = Unable to { $action }: the read-only mode only allows listing, previewing and dry runs
+action-create-job = create the job
+action-delete-job = delete the job { $name }
+action-recreate-job = recreate the job { $name }
+action-suspend-job = update the suspension of the job { $name }
+action-suspend-cronjob = update the suspension of the cronjob { $name }
+exec-credentials-rejected = The credentials provided by the exec plugin { $command } were rejected ({ $error }), refreshing them
+exec-credentials-invalid = The exec plugin { $command } is unable to provide valid credentials ({ $error }), { $hint }
+exec-login-hint = try to log in again with `{ $login }`
+exec-run-hint = make sure that `{ $command }` can be run and that you're logged in
+server-version-invalid = Unable to parse the version { $major }.{ $minor } of the API server
+template-spec-missing = Unable to get the template spec for { $name }
+template-spec-invalid = Unable to get the template spec for { $name }: { $error }
+job-spec-missing = Unable to create the job as building spec failed
+delete-job-failed = Unable to delete the job { $name } due to { $error }
+job-deleted-status = Job deleted with status { $status }
+wait-deletion-failed = Unable to wait for the deletion of the job { $name }: { $error }
+recreate-job-failed = Unable to recreate the job { $name }: { $error }
+suspend-job-failed = Unable to update the suspension of the job { $name }: { $error }
+suspend-cronjob-failed = Unable to update the suspension of the cronjob { $name }: { $error }
+watch-interrupted = Watch of the job { $name } interrupted, reconnecting: { $error }
+watch-ended = Watch of the job { $name } ended unexpectedly
+job-failed-reason = Job { $name } failed: { $reason }
+wait-timeout = Job with name { $name } may take more time than the maximum wait duration
+no-job-pod = No pod found for the job { $name }
+logs-unavailable = Unable to get the logs of { $container } due to: { $error }
+job-created = Job { $name } created
+dry-run-result = Dry run result for job { $name }
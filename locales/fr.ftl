@@ -0,0 +1,295 @@
+# Messages de la création d'un job. Les arguments s'écrivent { $name }
+
+## Sélection de la source
+select-source = Sélectionnez le cronjob à utiliser comme base du job
+select-sources = Sélectionnez les cronjobs à utiliser comme base des jobs
+listing-jobs = Récupération de la liste des jobs...
+listing-progress = Récupération de la liste des jobs... { $items } trouvés ({ $pages } pages)
+fetching-object = Récupération des détails de l'objet...
+target-name-from-source = Le nom du job source sera utilisé pour créer le job
+no-release-workload = Aucune ressource trouvée pour la release { $release }
+no-source-selected = Aucune source sélectionnée
+non-interactive-source = Le nom de la source est nécessaire en mode non interactif (--job-name)
+selected-object-not-found = Impossible de trouver l'objet sélectionné { $name }
+suspended-source-warning = Le cronjob { $name } est suspendu, assurez-vous qu'une exécution manuelle est attendue
+upcoming-runs = Prochaines exécutions planifiées de { $name } ({ $schedule }) :
+invalid-schedule = Impossible d'analyser la planification "{ $schedule }" : { $error }
+no-previous-run = Aucune exécution précédente trouvée pour le cronjob
+previous-runs = Dernières exécutions du cronjob :
+run-not-started = non démarré
+last-run-failures = Échecs de la dernière exécution { $name } :
+source-not-idle = Le cronjob { $name } a des jobs actifs : { $jobs } (--if-idle)
+manual-jobs-running = Des jobs manuels créés à partir de { $name } sont toujours en cours : { $jobs } (--if-idle)
+run-history-failed = Impossible de récupérer les dernières exécutions du cronjob : { $error }
+
+## Confirmations
+confirm-delete-existing = Un job avec le même nom existe déjà. Voulez-vous supprimer ce job
+host-access-warning = Le job aura un accès au niveau de l'hôte :
+confirm-host-access = Voulez-vous créer un job avec un accès au niveau de l'hôte
+host-access-refused = Job avec un accès au niveau de l'hôte refusé
+confirm-secret-display = Les valeurs des secrets seront affichées en clair, assurez-vous que votre écran n'est pas partagé. Continuer ?
+confirm-restricted = Voulez-vous définir ces champs dans le job ?
+restricted-missing-fields = Le namespace applique le profil Pod Security restricted. Les champs suivants sont manquants ou non autorisés :
+confirm-version-skew = Voulez-vous tout de même créer le job
+forced = Forcé ({ $option }) :
+forced-change-env = modification de la variable protégée { $name }
+forced-remove-env = suppression de la variable protégée { $name }
+forced-add-env = ajout de la variable protégée { $name }
+no-diff = Aucun changement entre { $original } et { $modified }
+version-skew = { $feature } nécessite Kubernetes { $since } (le serveur est en { $version }), le job peut être rejeté ou se comporter différemment
+version-skew-removed = { $feature } nécessite Kubernetes { $since } (le serveur est en { $version }), il a été retiré du job
+version-skew-refused = Job utilisant des fonctionnalités non supportées par Kubernetes { $version } refusé
+pod-security-failed = Impossible de récupérer le profil Pod Security du namespace : { $error }
+
+## Édition du job
+confirm-remove-env = Voulez-vous supprimer des variables d'environnement ?
+select-remove-envs = Quelles variables voulez-vous supprimer de { $container } ?
+confirm-additional-env = Voulez-vous ajouter des variables d'environnement ?
+confirm-more-env = Voulez-vous encore ajouter des variables d'environnement ?
+confirm-overwrite-env = { $name } est déjà défini ({ $current }), voulez-vous le remplacer ?
+confirm-resources = Voulez-vous modifier les limites de ressources ?
+confirm-metadata = Voulez-vous modifier les labels et annotations { $target ->
+        [job] du job
+       *[pods] des pods
+    } ?
+current-metadata = { $kind ->
+        [label] Labels actuels
+       *[annotation] Annotations actuelles
+    } { $target ->
+        [job] du job
+       *[pods] des pods
+    } : { $current }
+input-metadata = { $kind ->
+        [label] Label
+       *[annotation] Annotation
+    } à définir sur { $target ->
+        [job] le job
+       *[pods] les pods
+    } (CLÉ=VALEUR, CLÉ- pour supprimer, vide pour continuer)
+confirm-review-metadata = Voulez-vous revoir les labels et annotations copiés depuis la source ?
+select-metadata = Sélectionnez les labels et annotations à copier sur le job
+metadata-not-copied = Non copiés sur le job : { $keys }
+select-sidecars = Sélectionnez les sidecars à conserver
+native-sidecars = Sidecars natifs (init containers avec restartPolicy: Always) : { $names }
+removed-sidecars = Sidecars supprimés : { $names }
+select-scheduler = Sélectionnez le scheduler des pods
+select-runtime-class = Sélectionnez la RuntimeClass des pods
+confirm-mount-pvc = Voulez-vous monter un PersistentVolumeClaim ?
+select-pvc = Sélectionnez le PersistentVolumeClaim à monter
+select-pvc-container = Sélectionnez le conteneur qui monte le PersistentVolumeClaim
+input-pvc-mount-path = Saisissez le chemin où { $claim } est monté dans { $container }
+invalid-mount-path = Le chemin de montage doit être absolu
+select-precedence = Le template définit déjà { $field }, quelle valeur faut-il utiliser ?
+precedence-template = Conserver la valeur du template ({ $value })
+precedence-cli = Utiliser la valeur de la CLI ({ $value })
+schedule-interval = Intervalle de la planification : { $seconds }s
+schedule-interval-failed = Impossible de calculer l'intervalle de la planification, l'activeDeadlineSeconds est conservé
+next-run-failed = Impossible de calculer la prochaine exécution planifiée de { $name } (--align-next-run)
+
+## Variables d'environnement
+select-envs = Quelles variables voulez-vous modifier ?
+select-envs-page = Quelles variables voulez-vous modifier ? (page { $page }/{ $total })
+confirm-next-envs = Voulez-vous passer en revue les variables suivantes ? ({ $remaining } restantes)
+env-from-source = Variables d'environnement de { $source } ({ $container })
+env-downward = Variable { $name } ({ $source })
+env-config-map-value = Variable { $name } (configmap { $reference }) : { $value }
+env-secret-value = Variable { $name } (secret { $reference }) : { $value }
+env-value-not-found = <introuvable>
+env-protected = La variable { $name } est protégée et reste inchangée (utilisez --force pour la modifier)
+env-duplicated = La variable { $name } est définie { $count } fois dans le conteneur { $container }
+container-not-found = Impossible de trouver le conteneur { $name } (--container)
+read-env-file-failed = Impossible de lire le fichier d'env { $path } : { $error }
+invalid-env-file = Fichier d'env { $path } invalide : { $error }
+expand-env-failed = Impossible d'étendre la variable { $name } : { $error }
+protected-env-flag = { $name } est protégée, utilisez --force pour la définir
+list-config-maps-failed = Impossible de lister les ConfigMaps : { $error }
+list-secrets-failed = Impossible de lister les Secrets : { $error }
+env-title = Variable { $name } :
+env-title-sidecar = Variable { $name } (sidecar { $sidecar }) :
+env-title-init = Variable { $name } (init { $init }) :
+env-title-reference = Variable { $name } ({ $reference }, une nouvelle valeur remplace la référence) :
+env-title-secret = Variable { $name } ({ $reference }, saisie masquée, vide conserve la référence) :
+env-title-sensitive = Variable { $name } (valeur sensible, saisie masquée, vide conserve la valeur) :
+select-secret-envs = Quelles variables doivent référencer une clé de Secret au lieu d'une valeur ?
+select-secret-ref = Sélectionnez le Secret référencé par { $name }
+input-secret-ref = Saisissez le nom du Secret référencé par { $name }
+input-secret-key = Saisissez la clé de { $secret } référencée par { $name }
+select-env-value = Sélectionnez la valeur à conserver pour la variable { $name }
+select-env-container = Sélectionnez le conteneur auquel ajouter la variable d'environnement
+input-additional-env = Saisissez la variable d'environnement sous la forme CLÉ=VALEUR
+input-env-from-file = Saisissez la variable et le chemin du fichier sous la forme CLÉ=CHEMIN
+select-additional-env-kind = Quel type de variable voulez-vous ajouter ?
+additional-env-literal = Une valeur (CLÉ=VALEUR)
+additional-env-file = Le contenu d'un fichier local (CLÉ=CHEMIN)
+additional-env-field = Un champ du pod (downward API)
+additional-env-resource = Une ressource du conteneur (downward API)
+select-env-field = Sélectionnez le champ du pod exposé en variable
+select-env-resource = Sélectionnez la ressource du conteneur exposée en variable
+input-downward-env = Saisissez le nom de la variable exposant { $source }
+confirm-env-from = Voulez-vous modifier les sources envFrom ?
+current-env-from = envFrom de { $container } : { $current }
+select-env-from-action = Que voulez-vous faire des sources envFrom ?
+env-from-add-config-map = Ajouter une ConfigMap
+env-from-add-secret = Ajouter un Secret
+env-from-remove = Supprimer une source
+env-from-prefix = Définir le préfixe d'une source
+env-from-done = Terminer
+select-env-from = Sélectionnez la source envFrom
+select-env-from-name = Sélectionnez { $kind ->
+        [configmap] la ConfigMap
+       *[secret] le Secret
+    } dont les clés sont chargées comme variables d'environnement
+input-env-from-name = Saisissez le nom { $kind ->
+        [configmap] de la ConfigMap
+       *[secret] du Secret
+    } dont les clés sont chargées comme variables d'environnement
+input-env-from-prefix = Saisissez le préfixe des clés (vide pour le supprimer)
+invalid-env-format = La variable d'environnement doit respecter le format : ENV_NAME=VALUE
+invalid-env-file-format = La variable lue depuis un fichier doit respecter le format : ENV_NAME=PATH
+invalid-env-name = "{ $name }" n'est pas un nom de variable valide, il ne doit contenir que des lettres, des chiffres ou '_' et ne pas commencer par un chiffre
+invalid-env-line = Variable invalide à la ligne { $line } : { $error }
+read-env-value-failed = Impossible de lire le fichier { $path } de la variable { $name } : { $error }
+expansion-unclosed = {"}"} manquant après ${"{"}
+expansion-empty = ${"{}"} vide dans la valeur
+expansion-undefined = { $name } n'est pas définie dans l'environnement local (utilisez --no-expand pour la conserver telle quelle)
+config-map-values-failed = Impossible de résoudre les valeurs des ConfigMaps : { $error }
+secret-values-failed = Impossible de résoudre les valeurs des Secrets : { $error }
+
+## Ressources
+input-memory = Définissez la limite de mémoire
+invalid-memory = La mémoire ne doit contenir que des chiffres
+select-memory-format = Sélectionnez un format de mémoire
+input-cpu = Définissez la limite de CPU
+invalid-cpu = Le CPU doit contenir des chiffres
+invalid-cpu-min = Le CPU doit être supérieur ou égal à 0.001
+select-cpu-format = Sélectionnez un format de CPU
+missing-resources-flags = --memory et --cpu sont tous deux nécessaires pour définir les ressources
+
+## Création du job
+applying-dry-run = Exécution du job en dry-run...
+applying = Application du job...
+bulk-progress = Job { $index }/{ $total } à partir de { $source }
+bulk-creating = [{ $index }/{ $total }] Création du job de { $source }
+bulk-job-failed = [{ $index }/{ $total }] Le job de { $source } a échoué : { $error }
+bulk-failed = { $failed } des { $total } jobs ont échoué
+created-suspended = Job { $name } créé suspendu, il démarrera le { $run }
+job-failed = Impossible de créer le job : { $error }
+edited-job-without-spec = Le job édité n'a pas de spec
+write-junit-failed = Impossible d'écrire le rapport JUnit { $path } : { $error }
+open-editor-failed = Impossible d'ouvrir l'éditeur { $editor } : { $error }
+editor-exited = L'éditeur { $editor } s'est terminé avec { $status }
+edition-cancelled = Édition annulée car le manifeste est vide
+invalid-manifest = Manifeste invalide : { $error }
+read-plugins-failed = Impossible de lire le répertoire des plugins { $path } : { $error }
+run-plugin-failed = Impossible d'exécuter { $path } : { $error }
+plugin-exited = terminé avec { $status } : { $stderr }
+plugin-invalid-spec = JobSpec retournée invalide : { $error }
+wait-with-dry-run = Impossible d'utiliser --wait avec --dry-run
+server-version-failed = Impossible de récupérer la version du serveur d'API : { $error }
+chain-stopped = La chaîne s'est arrêtée au job de { $source } : { $reason }
+chain-step-error = Erreur : { $error }
+chain-step-skipped = Ignoré
+
+## Reprise après une interruption
+recover-deleted-job = Le job { $name } a été supprimé mais le nouveau job n'a pas été créé. Voulez-vous recréer le job supprimé
+recover-suspended-job = Le job { $name } est toujours suspendu. Que voulez-vous faire
+recover-resume = Reprendre le job maintenant
+recover-delete = Supprimer le job
+recover-keep = Le laisser suspendu
+recover-failed = Impossible de reprendre après l'interruption : { $error }
+debug-bundle-written = Bundle de débogage écrit dans { $path }
+debug-bundle-failed = Impossible d'écrire le bundle de débogage : { $error }
+job-recreated = Job { $name } recréé
+job-resumed = Job { $name } repris
+job-left-suspended = Job { $name } laissé suspendu
+cronjob-suspended = Cronjob { $name } suspendu
+cronjob-resumed = Cronjob { $name } repris
+suspend-source-not-cronjob = --suspend-source nécessite un cronjob comme source
+
+## Gestion des jobs créés
+doctor-failed = { $failed } vérifications sur { $total } ont échoué
+no-manual-job = Aucun job créé avec bakkutteh dans le namespace { $namespace }
+not-manual-job = Le job { $name } n'a pas été créé avec bakkutteh
+job-run-failed = Le job { $name } a échoué : { $reason }
+unknown-reason = raison inconnue
+confirm-cancel = Voulez-vous annuler le job { $name } et supprimer ses pods
+deleting-job = Suppression du job { $name }...
+job-cancelled = Job { $name } et ses pods supprimés
+job-cancelling = Job { $name } annulé, ses pods sont en cours de suppression
+job-rerun = Job { $name } recréé sous le nom { $created }
+invalid-age = Âge { $age } invalide : { $error }
+no-prunable-job = Aucun job terminé depuis plus de { $age } à supprimer
+prunable-jobs = Jobs terminés depuis plus de { $age } :
+confirm-prune = Voulez-vous supprimer ces { $count } jobs
+job-deleted = Job { $name } supprimé
+no-previous-dispatch = Aucune création précédente de { $source } trouvée dans le namespace
+no-drift = Aucune dérive des variables d'environnement ou des ressources depuis le job { $name }
+drift = Dérive depuis le job { $name } :
+invalid-duration = Durée { $duration } invalide : { $error }
+no-data-directory = Impossible de trouver le répertoire de données
+empty-history = Aucune création enregistrée dans l'historique { $path }
+history-record-failed = Impossible d'enregistrer la création dans l'historique : { $error }
+read-manifest-failed = Impossible de lire le manifeste { $path } : { $error }
+open-history-failed = Impossible d'ouvrir l'historique { $path } : { $error }
+read-history-failed = Impossible de lire l'historique { $path } : { $error }
+invalid-history-entry = Entrée invalide à la ligne { $line } de l'historique { $path } : { $error }
+
+## Questions
+interrupted = Opération interrompue
+end-of-input = Opération annulée : fin de l'entrée
+non-interactive-unanswered = Impossible de répondre à "{ $question }" en mode non interactif, utilisez les options ou --replay pour y répondre
+invalid-input = Saisie invalide
+value-empty = La valeur ne peut pas être vide
+prompt-canceled = Opération annulée : { $error }
+prompt-validation-failed = La validation a échoué : { $error }
+prompt-select-failed = Impossible de sélectionner l'élément : { $error }
+prompt-multi-select-failed = Impossible de sélectionner les éléments : { $error }
+prompt-confirm-failed = Impossible d'obtenir la confirmation de l'utilisateur : { $error }
+select-no-options = Impossible de sélectionner l'élément : aucune option disponible
+plain-confirm-invalid = Veuillez répondre par y ou n
+plain-select = Saisissez un nombre entre 1 et { $count } :
+plain-multi-select = Saisissez les nombres séparés par des virgules (vide conserve la sélection) :
+plain-invalid-choice = Choix invalide : { $answer }
+read-session-failed = Impossible de lire le fichier de session { $path } : { $error }
+write-session-failed = Impossible d'écrire le fichier de session { $path } : { $error }
+
+## Maintenance de l'outil
+already-latest = bakkutteh { $version } est déjà la dernière version
+updated = bakkutteh mis à jour de { $current } vers { $latest }
+unsupported-platform = Aucune release n'est construite pour { $os } ({ $arch })
+invalid-release-version = Version invalide pour la release { $tag } : { $error }
+missing-release-asset = La release { $tag } ne contient pas { $name }
+empty-checksum = Le fichier de checksum est vide
+checksum-mismatch = Checksum différent, { $expected } attendu mais l'artefact a { $digest }
+replace-binary-failed = Impossible de remplacer { $path } : { $error }
+man-page-written = Page de manuel écrite dans { $path }
+
+## API Kubernetes
+read-only-refused = Impossible de { $action } : le mode lecture seule n'autorise que le listage, les aperçus et les dry runs
+action-create-job = créer le job
+action-delete-job = supprimer le job { $name }
+action-recreate-job = recréer le job { $name }
+action-suspend-job = modifier la suspension du job { $name }
+action-suspend-cronjob = modifier la suspension du cronjob { $name }
+exec-credentials-rejected = Les identifiants fournis par le plugin exec { $command } ont été refusés ({ $error }), renouvellement en cours
+exec-credentials-invalid = Le plugin exec { $command } ne parvient pas à fournir des identifiants valides ({ $error }), { $hint }
+exec-login-hint = essayez de vous reconnecter avec `{ $login }`
+exec-run-hint = vérifiez que `{ $command }` peut être exécuté et que vous êtes connecté
+server-version-invalid = Impossible de lire la version { $major }.{ $minor } du serveur d'API
+template-spec-missing = Impossible de récupérer le template spec de { $name }
+template-spec-invalid = Impossible de récupérer le template spec de { $name } : { $error }
+job-spec-missing = Impossible de créer le job car la construction de la spec a échoué
+delete-job-failed = Impossible de supprimer le job { $name } : { $error }
+job-deleted-status = Job supprimé avec le statut { $status }
+wait-deletion-failed = Impossible d'attendre la suppression du job { $name } : { $error }
+recreate-job-failed = Impossible de recréer le job { $name } : { $error }
+suspend-job-failed = Impossible de modifier la suspension du job { $name } : { $error }
+suspend-cronjob-failed = Impossible de modifier la suspension du cronjob { $name } : { $error }
+watch-interrupted = Surveillance du job { $name } interrompue, reconnexion : { $error }
+watch-ended = La surveillance du job { $name } s'est arrêtée de manière inattendue
+job-failed-reason = Le job { $name } a échoué : { $reason }
+wait-timeout = Le job { $name } pourrait prendre plus de temps que la durée d'attente maximale
+no-job-pod = Aucun pod trouvé pour le job { $name }
+logs-unavailable = Impossible de récupérer les logs de { $container } : { $error }
+job-created = Job { $name } créé
+dry-run-result = Résultat du dry run pour le job { $name }
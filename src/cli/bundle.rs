@@ -0,0 +1,181 @@
+use crate::kube::SOURCE_TEMPLATE_ANNOTATION;
+use anyhow::{Result, anyhow};
+use flate2::{Compression, write::GzEncoder};
+use k8s_openapi::api::batch::v1::Job;
+use std::fs::File;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Constant
+const REDACTED: &str = "<redacted>";
+const FILE_MODE: u32 = 0o644;
+
+static BUNDLE: Mutex<Bundle> = Mutex::new(Bundle::new());
+
+/// Bundle holds the context of the run which is packaged in the debug bundle when the run fails
+#[derive(Debug, Default)]
+struct Bundle {
+    enabled: bool,
+    spec: Option<Job>,
+    api_errors: Vec<String>,
+}
+
+impl Bundle {
+    const fn new() -> Self {
+        Self {
+            enabled: false,
+            spec: None,
+            api_errors: Vec::new(),
+        }
+    }
+}
+
+fn bundle() -> MutexGuard<'static, Bundle> {
+    BUNDLE.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Start collecting the context of the run. The bundle is written with [`write`]
+pub fn enable() {
+    bundle().enabled = true;
+}
+
+/// Record the spec of the job which is built. The values of the literal env are redacted
+///
+/// # Arguments
+///
+/// * `job` - &Job
+pub fn record_spec(job: &Job) {
+    let mut bundle = bundle();
+    if bundle.enabled {
+        bundle.spec = Some(sanitize(job.clone()));
+    }
+}
+
+/// Record an error returned by the API server
+///
+/// # Arguments
+///
+/// * `err` - &kube::Error
+pub fn record_api_error(err: &kube::Error) {
+    let mut bundle = bundle();
+    if bundle.enabled {
+        bundle.api_errors.push(format!("{err:?}"));
+    }
+}
+
+/// Write the debug bundle (tar.gz) with the recorded context, the error of the run and the discovery info of the cluster
+///
+/// # Arguments
+///
+/// * `path` - &str
+/// * `error` - &anyhow::Error
+/// * `discovery` - Result<String> (discovery info, the error is written instead when the cluster can't be reached)
+pub fn write(path: &str, error: &anyhow::Error, discovery: Result<String>) -> Result<()> {
+    let bundle = bundle();
+
+    let spec = match &bundle.spec {
+        Some(job) => serde_yml::to_string(job)?,
+        None => "The spec of the job wasn't built\n".to_string(),
+    };
+    let discovery =
+        discovery.unwrap_or_else(|err| format!("Unable to get the discovery info: {err}\n"));
+    let files = [
+        (
+            "version.txt",
+            format!("bakkutteh {}\n", env!("CARGO_PKG_VERSION")),
+        ),
+        ("error.txt", format!("{error:?}\n")),
+        ("spec.yaml", spec),
+        ("api-errors.txt", bundle.api_errors.join("\n")),
+        ("discovery.yaml", discovery),
+    ];
+
+    let file = File::create(path)
+        .map_err(|err| anyhow!("Unable to create the debug bundle {path}: {err}"))?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let mtime = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    for (name, contents) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(FILE_MODE);
+        header.set_mtime(mtime);
+        header.set_cksum();
+
+        archive.append_data(&mut header, name, contents.as_bytes())?;
+    }
+
+    archive.into_inner()?.finish()?;
+
+    Ok(())
+}
+
+/// Redact the values of the literal env and remove the copy of the source template which contains the literal env
+/// of the source
+///
+/// # Arguments
+///
+/// * `job` - Job
+fn sanitize(mut job: Job) -> Job {
+    if let Some(annotations) = job.metadata.annotations.as_mut() {
+        annotations.remove(SOURCE_TEMPLATE_ANNOTATION);
+    }
+
+    let Some(pod_spec) = job
+        .spec
+        .as_mut()
+        .and_then(|spec| spec.template.spec.as_mut())
+    else {
+        return job;
+    };
+
+    pod_spec
+        .containers
+        .iter_mut()
+        .chain(pod_spec.init_containers.iter_mut().flatten())
+        .flat_map(|container| container.env.iter_mut().flatten())
+        .filter(|env| env.value.is_some())
+        .for_each(|env| env.value = Some(REDACTED.to_string()));
+
+    job
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sanitize;
+    use k8s_openapi::api::batch::v1::Job;
+
+    #[test]
+    fn expect_to_redact_literal_env() {
+        let job: Job = serde_json::from_value(serde_json::json!({
+            "metadata": {
+                "annotations": { "bakkutteh.io/source-template": "H4sI" }
+            },
+            "spec": {
+                "template": {
+                    "spec": {
+                        "containers": [{
+                            "name": "main",
+                            "env": [
+                                { "name": "PASSWORD", "value": "hunter2" },
+                                { "name": "TOKEN", "valueFrom": { "secretKeyRef": { "name": "s", "key": "k" } } }
+                            ]
+                        }]
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let job = sanitize(job);
+        assert!(job.metadata.annotations.unwrap().is_empty());
+
+        let pod_spec = job.spec.unwrap().template.spec.unwrap();
+        let env = pod_spec.containers[0].env.as_ref().unwrap();
+        assert_eq!(env[0].value.as_deref(), Some("<redacted>"));
+        assert!(env[1].value.is_none());
+    }
+}
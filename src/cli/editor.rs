@@ -0,0 +1,185 @@
+use super::i18n::{tr, tr_args};
+use anyhow::{Result, anyhow};
+use serde::{Serialize, de::DeserializeOwned};
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+// Constant
+const EDITOR_ENVS: [&str; 2] = ["VISUAL", "EDITOR"];
+const DEFAULT_EDITOR: &str = "vi";
+const COMMENT: char = '#';
+const HEADER: &str = "# Edit the manifest then save and close the editor to continue. An empty file cancels the dispatch\n";
+
+/// Open the value as YAML in the editor of the user ($VISUAL, $EDITOR or vi) and parse it once the editor is
+/// closed. The editor is opened again with the error on top of the file when the value is invalid
+///
+/// # Arguments
+///
+/// * `value` - &T
+/// * `validate` - F
+pub fn edit<T, F>(value: &T, validate: F) -> Result<T>
+where
+    T: Serialize + DeserializeOwned,
+    F: Fn(&T) -> Result<()>,
+{
+    edit_with(&command(), value, validate)
+}
+
+/// Open the text in the editor of the user and get the text once the editor is closed. The newline added at the end
+/// of the file by most editors is removed when the text didn't end with one
+///
+/// # Arguments
+///
+/// * `text` - &str
+pub fn edit_text(text: &str) -> Result<String> {
+    edit_text_with(&command(), text)
+}
+
+/// Get the editor of the user: $VISUAL, $EDITOR or vi
+fn command() -> String {
+    EDITOR_ENVS
+        .iter()
+        .find_map(|name| {
+            env::var(name)
+                .ok()
+                .filter(|editor| !editor.trim().is_empty())
+        })
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+/// Open the file with the editor and wait for the editor to be closed
+///
+/// # Arguments
+///
+/// * `editor` - &str
+/// * `path` - &Path
+fn open(editor: &str, path: &Path) -> Result<()> {
+    // The editor may be given with its arguments (e.g. code --wait)
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_EDITOR);
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .map_err(|err| {
+            anyhow!(tr_args(
+                "open-editor-failed",
+                &[("editor", editor.to_string()), ("error", err.to_string())]
+            ))
+        })?;
+    match status.success() {
+        true => Ok(()),
+        false => Err(anyhow!(tr_args(
+            "editor-exited",
+            &[
+                ("editor", editor.to_string()),
+                ("status", status.to_string())
+            ]
+        ))),
+    }
+}
+
+fn edit_text_with(editor: &str, text: &str) -> Result<String> {
+    let path = env::temp_dir().join(format!("bakkutteh-env-{}.txt", std::process::id()));
+    fs::write(&path, text)?;
+
+    let edited = open(editor, &path).and_then(|_| Ok(fs::read_to_string(&path)?));
+    let _ = fs::remove_file(&path);
+
+    edited.map(|edited| match text.ends_with('\n') {
+        true => edited,
+        false => edited
+            .strip_suffix('\n')
+            .map(|edited| edited.strip_suffix('\r').unwrap_or(edited))
+            .unwrap_or(&edited)
+            .to_string(),
+    })
+}
+
+fn edit_with<T, F>(editor: &str, value: &T, validate: F) -> Result<T>
+where
+    T: Serialize + DeserializeOwned,
+    F: Fn(&T) -> Result<()>,
+{
+    let path = env::temp_dir().join(format!("bakkutteh-edit-{}.yaml", std::process::id()));
+    let mut contents = format!("{HEADER}{}", serde_yml::to_string(value)?);
+
+    let result = loop {
+        fs::write(&path, &contents)?;
+        if let Err(err) = open(editor, &path) {
+            break Err(err);
+        }
+
+        let edited = fs::read_to_string(&path)?;
+        let manifest = strip_header(&edited);
+        if manifest.trim().is_empty() {
+            break Err(anyhow!(tr("edition-cancelled")));
+        }
+
+        let parsed = serde_yml::from_str::<T>(&manifest)
+            .map_err(|err| anyhow!(tr_args("invalid-manifest", &[("error", err.to_string())])))
+            .and_then(|value| validate(&value).map(|_| value));
+        match parsed {
+            Err(err) => {
+                let error = err
+                    .to_string()
+                    .lines()
+                    .map(|line| format!("{COMMENT} {line}\n"))
+                    .collect::<String>();
+                contents = format!("{HEADER}{error}{manifest}");
+            }
+            value => break value,
+        }
+    };
+
+    let _ = fs::remove_file(&path);
+    result
+}
+
+/// Remove the comments on top of the file, which contain the header and the errors of the previous edition. The
+/// other comments are kept as they may be part of a value (e.g. a script given as argument)
+///
+/// # Arguments
+///
+/// * `contents` - &str
+fn strip_header(contents: &str) -> String {
+    contents
+        .lines()
+        .skip_while(|line| line.starts_with(COMMENT))
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::{edit_text_with, edit_with};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn expect_to_edit_value() {
+        let value = BTreeMap::from([("image".to_string(), "busybox".to_string())]);
+
+        // sed edits the file in place like an editor would
+        let edited = edit_with("sed -i s/busybox/alpine/", &value, |_| Ok(())).unwrap();
+        assert_eq!(edited.get("image").map(String::as_str), Some("alpine"));
+
+        // Emptying the manifest cancels the edition
+        let cancelled = edit_with("sed -i /image/d", &value, |_| Ok(()));
+        assert!(cancelled.unwrap_err().to_string().contains("cancelled"));
+    }
+
+    #[test]
+    fn expect_to_edit_multi_line_text() {
+        let pem = "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----";
+
+        // sed adds the final newline like most editors, it's removed as the text didn't end with one
+        let edited = edit_text_with("sed -i s/MIIB/MIIC/", pem).unwrap();
+        assert_eq!(
+            edited,
+            "-----BEGIN CERTIFICATE-----\nMIIC\n-----END CERTIFICATE-----"
+        );
+    }
+}
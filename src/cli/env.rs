@@ -0,0 +1,301 @@
+use super::i18n::{tr, tr_args};
+use anyhow::{Result, anyhow};
+use std::fs;
+
+// Constant
+const SPLIT_ENV_OPERATOR: char = '=';
+const ESCAPE_CHAR: char = '\\';
+const QUOTES: [char; 2] = ['"', '\''];
+const COMMENT: char = '#';
+const EXPORT_PREFIX: &str = "export ";
+const EXPANSION_START: &str = "${";
+const EXPANSION_END: char = '}';
+const EXPANSION_ESCAPE: char = '$';
+
+/// Parse an environment variable written with the format KEY=VALUE.
+///
+/// The key ends at the first `=` which isn't escaped with `\`. Unless the raw mode is used:
+/// - `\=` and `\\` are unescaped
+/// - A single pair of matching quotes surrounding the whole value is removed. Other quotes are preserved (e.g. JSON)
+///
+/// In raw mode the value is kept as is after the first `=`
+///
+/// # Arguments
+///
+/// * `input` - &str
+/// * `raw` - bool
+pub fn parse_env(input: &str, raw: bool) -> Result<(String, String)> {
+    let format_err = || anyhow!(tr("invalid-env-format"));
+
+    if raw {
+        let (key, value) = input
+            .split_once(SPLIT_ENV_OPERATOR)
+            .ok_or_else(format_err)?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format_err());
+        }
+
+        return Ok((key.to_string(), value.to_string()));
+    }
+
+    let mut key = String::new();
+    let mut value = String::new();
+    let mut in_value = false;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        let target = match in_value {
+            true => &mut value,
+            false => &mut key,
+        };
+
+        match c {
+            ESCAPE_CHAR => match chars.next() {
+                Some(escaped @ (SPLIT_ENV_OPERATOR | ESCAPE_CHAR)) => target.push(escaped),
+                Some(other) => {
+                    target.push(ESCAPE_CHAR);
+                    target.push(other);
+                }
+                None => target.push(ESCAPE_CHAR),
+            },
+            SPLIT_ENV_OPERATOR if !in_value => in_value = true,
+            c => target.push(c),
+        }
+    }
+
+    let key = key.trim();
+    if !in_value || key.is_empty() {
+        return Err(format_err());
+    }
+
+    Ok((key.to_string(), unquote(value.trim()).to_string()))
+}
+
+/// Parse the KEY=VALUE pairs of a dotenv file. Empty lines and comments are skipped and the `export ` prefix
+/// of the lines is removed. The values are parsed like the env given with the prompts
+///
+/// # Arguments
+///
+/// * `contents` - &str
+/// * `raw` - bool
+pub fn parse_env_file(contents: &str, raw: bool) -> Result<Vec<(String, String)>> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with(COMMENT)
+        })
+        .map(|(idx, line)| {
+            let line = line.trim_start();
+            let line = line.strip_prefix(EXPORT_PREFIX).unwrap_or(line);
+
+            parse_env(line, raw).map_err(|err| {
+                anyhow!(tr_args(
+                    "invalid-env-line",
+                    &[("line", (idx + 1).to_string()), ("error", err.to_string())]
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Check that the name of the env is a C identifier ([A-Za-z_][A-Za-z0-9_]*) as required by Kubernetes
+///
+/// # Arguments
+///
+/// * `name` - &str
+pub fn validate_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    match valid {
+        true => Ok(()),
+        false => Err(anyhow!(tr_args(
+            "invalid-env-name",
+            &[("name", name.to_string())]
+        ))),
+    }
+}
+
+/// Parse an env written with the format KEY=PATH and read the contents of the file as its value. The contents are
+/// kept as is (e.g. a JSON config, newlines included)
+///
+/// # Arguments
+///
+/// * `input` - &str
+pub fn read_env_from_file(input: &str) -> Result<(String, String)> {
+    let (key, path) = parse_env(input, true).map_err(|_| anyhow!(tr("invalid-env-file-format")))?;
+    validate_name(&key)?;
+    let path = path.trim();
+    let contents = fs::read_to_string(path).map_err(|err| {
+        anyhow!(tr_args(
+            "read-env-value-failed",
+            &[
+                ("path", path.to_string()),
+                ("name", key.clone()),
+                ("error", err.to_string())
+            ]
+        ))
+    })?;
+
+    Ok((key, contents))
+}
+
+/// Expand the `${NAME}` of the value with the variables returned by the lookup (e.g. the local environment).
+/// `$${NAME}` is kept as `${NAME}`. The values of the variables are never part of the errors
+///
+/// # Arguments
+///
+/// * `value` - &str
+/// * `lookup` - F
+pub fn expand<F>(value: &str, lookup: F) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut expanded = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find(EXPANSION_START) {
+        let (before, after) = rest.split_at(start);
+        let after = &after[EXPANSION_START.len()..];
+
+        if let Some(before) = before.strip_suffix(EXPANSION_ESCAPE) {
+            expanded.push_str(before);
+            expanded.push_str(EXPANSION_START);
+            rest = after;
+            continue;
+        }
+
+        let (name, remaining) = after
+            .split_once(EXPANSION_END)
+            .ok_or_else(|| anyhow!(tr("expansion-unclosed")))?;
+        if name.is_empty() {
+            return Err(anyhow!(tr("expansion-empty")));
+        }
+
+        let resolved = lookup(name).ok_or_else(|| {
+            anyhow!(tr_args(
+                "expansion-undefined",
+                &[("name", name.to_string())]
+            ))
+        })?;
+        expanded.push_str(before);
+        expanded.push_str(&resolved);
+        rest = remaining;
+    }
+    expanded.push_str(rest);
+
+    Ok(expanded)
+}
+
+/// Remove the quotes surrounding the whole value if any
+///
+/// # Arguments
+///
+/// * `value` - &str
+fn unquote(value: &str) -> &str {
+    QUOTES
+        .iter()
+        .find_map(|quote| {
+            value
+                .strip_prefix(*quote)
+                .and_then(|v| v.strip_suffix(*quote))
+        })
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand, parse_env, parse_env_file, read_env_from_file, validate_name};
+
+    #[test]
+    fn expect_to_parse_env() {
+        let cases = [
+            ("KEY=value", ("KEY", "value")),
+            ("KEY=\"quoted value\"", ("KEY", "quoted value")),
+            ("KEY='single'", ("KEY", "single")),
+            ("KEY={\"a\":\"b\"}", ("KEY", "{\"a\":\"b\"}")),
+            ("KEY=a=b", ("KEY", "a=b")),
+            ("KEY=a\\=b", ("KEY", "a=b")),
+            ("KEY=it's", ("KEY", "it's")),
+            ("KEY=", ("KEY", "")),
+        ];
+
+        for (input, (key, value)) in cases {
+            let (k, v) = parse_env(input, false).unwrap();
+            assert_eq!((k.as_str(), v.as_str()), (key, value), "{input}");
+        }
+    }
+
+    #[test]
+    fn expect_raw_mode_to_keep_value() {
+        let (key, value) = parse_env("KEY=\"quoted\\=\"", true).unwrap();
+        assert_eq!(key, "KEY");
+        assert_eq!(value, "\"quoted\\=\"");
+    }
+
+    #[test]
+    fn expect_to_parse_env_file() {
+        let contents =
+            "# release overrides\n\nLOG_LEVEL=debug\nexport API_URL=\"https://momo.dev\"\n";
+        let envs = parse_env_file(contents, false).unwrap();
+        assert_eq!(
+            envs,
+            vec![
+                ("LOG_LEVEL".to_string(), "debug".to_string()),
+                ("API_URL".to_string(), "https://momo.dev".to_string()),
+            ]
+        );
+
+        let err = parse_env_file("LOG_LEVEL=debug\nAPI_URL", false).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn expect_to_expand_local_env() {
+        let lookup = |name: &str| (name == "OPERATOR").then(|| "momo".to_string());
+
+        let expanded = expand("run-by-${OPERATOR}/$${HOME}/$HOME", lookup).unwrap();
+        assert_eq!(expanded, "run-by-momo/${HOME}/$HOME");
+
+        let err = expand("${TOKEN}", lookup).unwrap_err();
+        assert!(err.to_string().contains("TOKEN isn't defined"));
+        assert!(expand("${OPERATOR", lookup).is_err());
+    }
+
+    #[test]
+    fn expect_to_read_env_from_file() {
+        let path = std::env::temp_dir().join(format!("bakkutteh-env-{}.json", std::process::id()));
+        std::fs::write(&path, "{\n  \"enabled\": true\n}\n").unwrap();
+
+        let (key, value) = read_env_from_file(&format!("CONFIG={}", path.display())).unwrap();
+        assert_eq!(key, "CONFIG");
+        assert_eq!(value, "{\n  \"enabled\": true\n}\n");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(read_env_from_file(&format!("CONFIG={}", path.display())).is_err());
+    }
+
+    #[test]
+    fn expect_invalid_env_to_fail() {
+        assert!(parse_env("KEY", false).is_err());
+        assert!(parse_env("=value", false).is_err());
+        assert!(parse_env("KEY\\=value", false).is_err());
+    }
+
+    #[test]
+    fn expect_to_validate_env_name() {
+        for name in ["LOG_LEVEL", "_private", "api2"] {
+            assert!(validate_name(name).is_ok(), "{name}");
+        }
+
+        for name in ["", "2FA", "LOG-LEVEL", "LOG LEVEL", "café"] {
+            assert!(validate_name(name).is_err(), "{name}");
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use clap::ValueEnum;
+use jiff::Timestamp;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Whether the events are written as JSON lines on the stderr
+static JSON_EVENTS: AtomicBool = AtomicBool::new(false);
+
+/// Format of the logs. The text format only displays the messages meant for humans while the JSON format
+/// additionally emits structured events which can be parsed by wrapper automation
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+/// Event of the activity of the tool
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    PromptAnswered {
+        question: &'a str,
+        answer: &'a str,
+    },
+    ObjectFetched {
+        kind: &'a str,
+        namespace: &'a str,
+        name: &'a str,
+    },
+    ListProgress {
+        kind: &'a str,
+        namespace: &'a str,
+        pages: usize,
+        items: usize,
+    },
+    JobCreated {
+        namespace: &'a str,
+        name: &'a str,
+        dry_run: bool,
+    },
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    timestamp: String,
+    #[serde(flatten)]
+    event: &'a Event<'a>,
+}
+
+/// Set the format of the logs
+///
+/// # Arguments
+///
+/// * `format` - LogFormat
+pub fn set_format(format: LogFormat) {
+    JSON_EVENTS.store(format == LogFormat::Json, Ordering::Relaxed);
+}
+
+/// Emit the event on the stderr when the JSON format is used
+///
+/// # Arguments
+///
+/// * `event` - Event
+pub fn emit(event: Event) {
+    if !JSON_EVENTS.load(Ordering::Relaxed) {
+        return;
+    }
+
+    match to_json(&event, Timestamp::now()) {
+        Ok(line) => eprintln!("{line}"),
+        Err(err) => eprintln!("Unable to serialize the event {event:?}: {err}"),
+    }
+}
+
+fn to_json(event: &Event, timestamp: Timestamp) -> serde_json::Result<String> {
+    serde_json::to_string(&Record {
+        timestamp: timestamp.to_string(),
+        event,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Event, to_json};
+
+    #[test]
+    fn expect_event_to_be_serialized() {
+        let event = Event::JobCreated {
+            namespace: "default",
+            name: "momo-manual",
+            dry_run: false,
+        };
+
+        let line = to_json(&event, "2025-01-01T02:00:00Z".parse().unwrap()).unwrap();
+        assert_eq!(
+            line,
+            r#"{"timestamp":"2025-01-01T02:00:00Z","event":"job_created","namespace":"default","name":"momo-manual","dry_run":false}"#
+        );
+    }
+}
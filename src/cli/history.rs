@@ -0,0 +1,285 @@
+use super::i18n::tr_args;
+use anyhow::{Result, anyhow};
+use jiff::Timestamp;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fmt::{self, Write as _};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// Constant
+const DATA_DIR: &str = "bakkutteh";
+const HISTORY_FILE: &str = "history.jsonl";
+const USER_ENVS: [&str; 2] = ["USER", "USERNAME"];
+const UNKNOWN_USER: &str = "unknown";
+// Length of the hashes in the table (e.g. sha256:0123abcd)
+const HASH_LEN: usize = 15;
+
+/// Outcome of a dispatch
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// The job was created without waiting for its completion
+    Created,
+    Succeeded,
+    Failed,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Created => write!(f, "Created"),
+            Outcome::Succeeded => write!(f, "Succeeded"),
+            Outcome::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+/// Dispatch recorded in the audit log, written as a JSON line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub timestamp: String,
+    pub user: String,
+    pub namespace: String,
+    pub source: String,
+    pub target: String,
+    pub spec_hash: String,
+    pub outcome: Outcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Entry {
+    /// Create the entry of a dispatch made now by the current user
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - &str
+    /// * `source` - &str
+    /// * `target` - &str
+    /// * `spec_hash` - String
+    pub fn new(namespace: &str, source: &str, target: &str, spec_hash: String) -> Self {
+        Self {
+            timestamp: Timestamp::now().to_string(),
+            user: USER_ENVS
+                .iter()
+                .find_map(|name| env::var(name).ok())
+                .unwrap_or_else(|| UNKNOWN_USER.to_string()),
+            namespace: namespace.to_string(),
+            source: source.to_string(),
+            target: target.to_string(),
+            spec_hash,
+            outcome: Outcome::Created,
+            error: None,
+        }
+    }
+}
+
+/// Filter of the history
+#[derive(Debug, Default, Clone)]
+pub struct Filter {
+    /// None to keep the entries of every namespace
+    pub namespace: Option<String>,
+    pub source: Option<String>,
+    pub since: Option<Timestamp>,
+    pub failed: bool,
+}
+
+impl Filter {
+    fn matches(&self, entry: &Entry) -> bool {
+        self.namespace
+            .as_ref()
+            .is_none_or(|namespace| *namespace == entry.namespace)
+            && self
+                .source
+                .as_ref()
+                .is_none_or(|source| *source == entry.source)
+            && self.since.is_none_or(|since| {
+                entry
+                    .timestamp
+                    .parse::<Timestamp>()
+                    .is_ok_and(|timestamp| timestamp >= since)
+            })
+            && (!self.failed || entry.outcome == Outcome::Failed)
+    }
+}
+
+/// Get the path of the audit log under the data directory (e.g. ~/.local/share/bakkutteh/history.jsonl)
+pub fn path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(DATA_DIR).join(HISTORY_FILE))
+}
+
+/// Append the entry to the audit log
+///
+/// # Arguments
+///
+/// * `path` - &Path
+/// * `entry` - &Entry
+pub fn record(path: &Path, entry: &Entry) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| {
+            anyhow!(tr_args(
+                "open-history-failed",
+                &[
+                    ("path", path.display().to_string()),
+                    ("error", err.to_string())
+                ]
+            ))
+        })?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+
+    Ok(())
+}
+
+/// Read the entries of the audit log matching the filter, the most recent last. No entry is returned when the
+/// audit log does not exist
+///
+/// # Arguments
+///
+/// * `path` - &Path
+/// * `filter` - &Filter
+pub fn read(path: &Path, filter: &Filter) -> Result<Vec<Entry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(path).map_err(|err| {
+        anyhow!(tr_args(
+            "read-history-failed",
+            &[
+                ("path", path.display().to_string()),
+                ("error", err.to_string())
+            ]
+        ))
+    })?;
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str::<Entry>(line).map_err(|err| {
+                anyhow!(tr_args(
+                    "invalid-history-entry",
+                    &[
+                        ("line", (idx + 1).to_string()),
+                        ("path", path.display().to_string()),
+                        ("error", err.to_string())
+                    ]
+                ))
+            })
+        })
+        .filter(|entry| entry.as_ref().map_or(true, |entry| filter.matches(entry)))
+        .collect()
+}
+
+/// Render the entries as a table
+///
+/// # Arguments
+///
+/// * `entries` - &[Entry]
+pub fn render(entries: &[Entry]) -> String {
+    let header = [
+        "TIME",
+        "USER",
+        "NAMESPACE",
+        "SOURCE",
+        "TARGET",
+        "SPEC",
+        "OUTCOME",
+    ]
+    .map(String::from);
+    let rows = entries
+        .iter()
+        .map(|entry| {
+            [
+                entry.timestamp.clone(),
+                entry.user.clone(),
+                entry.namespace.clone(),
+                entry.source.clone(),
+                entry.target.clone(),
+                entry.spec_hash.chars().take(HASH_LEN).collect(),
+                match &entry.error {
+                    Some(error) => format!("{}: {error}", entry.outcome),
+                    None => entry.outcome.to_string(),
+                },
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let widths = std::iter::once(&header)
+        .chain(&rows)
+        .fold([0; 7], |mut widths, row| {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+            widths
+        });
+
+    let mut output = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        let _ = writeln!(output, "{}", line.trim_end());
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Entry, Filter, Outcome, read, record};
+    use std::fs;
+
+    #[test]
+    fn expect_to_record_and_filter_history() {
+        let path = std::env::temp_dir()
+            .join(format!("bakkutteh-history-{}", std::process::id()))
+            .join("history.jsonl");
+
+        let mut entry = Entry::new("default", "momo", "momo-manual", "sha256:0123".to_string());
+        entry.timestamp = "2025-01-01T02:00:00Z".to_string();
+        record(&path, &entry).unwrap();
+
+        let mut failed = Entry::new(
+            "batch",
+            "reports",
+            "reports-manual",
+            "sha256:4567".to_string(),
+        );
+        failed.timestamp = "2025-01-02T02:00:00Z".to_string();
+        failed.outcome = Outcome::Failed;
+        failed.error = Some("BackoffLimitExceeded".to_string());
+        record(&path, &failed).unwrap();
+
+        let all = read(&path, &Filter::default()).unwrap();
+        assert_eq!(all, vec![entry.clone(), failed.clone()]);
+
+        let filter = Filter {
+            namespace: Some("default".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(read(&path, &filter).unwrap(), vec![entry]);
+
+        let filter = Filter {
+            since: Some("2025-01-01T12:00:00Z".parse().unwrap()),
+            failed: true,
+            ..Default::default()
+        };
+        assert_eq!(read(&path, &filter).unwrap(), vec![failed]);
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,256 @@
+use super::session;
+use anyhow::{Result, anyhow};
+use fluent_bundle::{FluentArgs, FluentResource, concurrent::FluentBundle};
+use std::fmt;
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+// Constant
+const DEFAULT_LANG: &str = "en";
+// Bundles embedded in the binary. English is used for the messages missing from a bundle
+const BUNDLES: [(&str, &str); 2] = [
+    ("en", include_str!("../../locales/en.ftl")),
+    ("fr", include_str!("../../locales/fr.ftl")),
+];
+pub const LANGS: [&str; 2] = ["en", "fr"];
+
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+struct Localizer {
+    bundle: FluentBundle<FluentResource>,
+    fallback: FluentBundle<FluentResource>,
+}
+
+impl Localizer {
+    fn new(lang: &str) -> Self {
+        Self {
+            bundle: bundle(lang),
+            fallback: bundle(DEFAULT_LANG),
+        }
+    }
+}
+
+fn bundle(lang: &str) -> FluentBundle<FluentResource> {
+    let source = BUNDLES
+        .iter()
+        .find_map(|(code, source)| (*code == lang).then_some(*source))
+        .unwrap_or(BUNDLES[0].1);
+    let langid = lang.parse::<LanguageIdentifier>().unwrap_or_default();
+
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // The isolation marks around the arguments are rendered as is by most terminals
+    bundle.set_use_isolating(false);
+    if let Ok(resource) = FluentResource::try_new(source.to_string()) {
+        let _ = bundle.add_resource(resource);
+    }
+
+    bundle
+}
+
+/// Select the language of the messages. The language given with `--lang` is used first, then the locale of the
+/// system. English is used when the locale isn't supported
+///
+/// # Arguments
+///
+/// * `lang` - Option<&str>
+pub fn init(lang: Option<&str>) {
+    let lang = lang
+        .map(String::from)
+        .or_else(sys_locale::get_locale)
+        .and_then(|locale| supported(&locale))
+        .unwrap_or(DEFAULT_LANG);
+
+    let _ = LOCALIZER.set(Localizer::new(lang));
+}
+
+/// Parse the language given with `--lang`. Regions are accepted (e.g. fr-FR) but only the language is used
+///
+/// # Arguments
+///
+/// * `input` - &str
+pub fn parse_lang(input: &str) -> Result<String> {
+    supported(input).map(String::from).ok_or_else(|| {
+        anyhow!(
+            "Unsupported language {input}, supported languages are: {}",
+            LANGS.join(", ")
+        )
+    })
+}
+
+/// Get the supported language of a locale (e.g. fr_FR.UTF-8 or fr-FR)
+///
+/// # Arguments
+///
+/// * `locale` - &str
+fn supported(locale: &str) -> Option<&'static str> {
+    let lang = locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    LANGS.into_iter().find(|supported| *supported == lang)
+}
+
+/// Get the message in the selected language
+///
+/// # Arguments
+///
+/// * `id` - &str
+pub fn tr(id: &str) -> String {
+    tr_args(id, &[])
+}
+
+/// Get the message in the selected language with its arguments. The id is returned when no bundle has the message
+///
+/// # Arguments
+///
+/// * `id` - &str
+/// * `args` - &[(&str, String)]
+pub fn tr_args(id: &str, args: &[(&str, String)]) -> String {
+    let localizer = LOCALIZER.get_or_init(|| Localizer::new(DEFAULT_LANG));
+
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+
+    [&localizer.bundle, &localizer.fallback]
+        .into_iter()
+        .find_map(|bundle| {
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+
+            Some(
+                bundle
+                    .format_pattern(pattern, Some(&fluent_args), &mut errors)
+                    .to_string(),
+            )
+        })
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Question is the title of a prompt rendered in the selected language. Its key, the id of the message and its
+/// arguments (e.g. env-title name=FOO), identifies the answers of the question in a session so that the session
+/// is replayed in any language and survives the changes of wording
+#[derive(Debug, Clone)]
+pub struct Question {
+    key: String,
+    text: String,
+}
+
+impl Question {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Append a suffix to the rendered text, the key is kept
+    ///
+    /// # Arguments
+    ///
+    /// * `suffix` - &str
+    pub fn suffixed(mut self, suffix: &str) -> Self {
+        self.text.push_str(suffix);
+        self
+    }
+}
+
+impl fmt::Display for Question {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+// The questions which aren't localized are keyed by their text
+impl From<String> for Question {
+    fn from(text: String) -> Self {
+        Self {
+            key: session::text_key(&text),
+            text,
+        }
+    }
+}
+
+impl From<&str> for Question {
+    fn from(text: &str) -> Self {
+        Self::from(text.to_string())
+    }
+}
+
+/// Get the question of a prompt in the selected language
+///
+/// # Arguments
+///
+/// * `id` - &str
+pub fn question(id: &str) -> Question {
+    question_args(id, &[])
+}
+
+/// Get the question of a prompt in the selected language with its arguments
+///
+/// # Arguments
+///
+/// * `id` - &str
+/// * `args` - &[(&str, String)]
+pub fn question_args(id: &str, args: &[(&str, String)]) -> Question {
+    let key = std::iter::once(id.to_string())
+        .chain(
+            args.iter()
+                .map(|(name, value)| format!("{name}={}", session::text_key(value))),
+        )
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Question {
+        key,
+        text: tr_args(id, args),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BUNDLES, parse_lang, supported, tr, tr_args};
+    use fluent_bundle::FluentResource;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn expect_to_select_supported_language() {
+        assert_eq!(supported("fr_FR.UTF-8"), Some("fr"));
+        assert_eq!(supported("EN-us"), Some("en"));
+        assert_eq!(supported("ja_JP"), None);
+        assert!(parse_lang("de").is_err());
+
+        let msg = tr_args("job-resumed", &[("name", "momo".to_string())]);
+        assert_eq!(msg, "Job momo resumed");
+    }
+
+    #[test]
+    fn expect_bundles_to_define_same_messages() {
+        // Messages start at the beginning of a line, comments and multiline values don't
+        let ids = |source: &str| {
+            source
+                .lines()
+                .filter(|line| !line.starts_with([' ', '#']))
+                .filter_map(|line| line.split_once(" =").map(|(id, _)| id.to_string()))
+                .collect::<BTreeSet<_>>()
+        };
+
+        // A syntax error would drop the whole bundle
+        for (lang, source) in BUNDLES {
+            assert!(
+                FluentResource::try_new(source.to_string()).is_ok(),
+                "{lang}"
+            );
+        }
+        assert_eq!(tr("expansion-empty"), "Empty ${} in the value");
+
+        let english = ids(BUNDLES[0].1);
+        for (lang, source) in &BUNDLES[1..] {
+            assert_eq!(ids(source), english, "{lang}");
+        }
+    }
+}
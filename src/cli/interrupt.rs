@@ -0,0 +1,59 @@
+use k8s_openapi::api::batch::v1::Job;
+use std::sync::{Mutex, MutexGuard};
+
+static PENDING: Mutex<Option<Pending>> = Mutex::new(None);
+// Source cronjob suspended while the manual job runs (--suspend-source)
+static SUSPENDED_SOURCE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Step of the flow which changed the cluster and isn't complete until the job is created (or resumed). It's
+/// offered to roll it back or to finish it when the run is interrupted or fails
+#[derive(Debug, Clone)]
+pub enum Pending {
+    /// The job with the same name as the target was deleted
+    DeletedJob(Box<Job>),
+    /// The job was created suspended and waits for the next scheduled run of the cronjob
+    SuspendedJob(String),
+}
+
+fn pending() -> MutexGuard<'static, Option<Pending>> {
+    // A poisoned lock only means that a prompt panicked, the pending step is still usable
+    PENDING.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Record a step which needs to be rolled back or finished if the run stops before its end
+///
+/// # Arguments
+///
+/// * `step` - Pending
+pub fn set(step: Pending) {
+    *pending() = Some(step);
+}
+
+/// Forget the pending step once the flow went past it
+pub fn clear() {
+    *pending() = None;
+}
+
+/// Take the pending step if any
+pub fn take() -> Option<Pending> {
+    pending().take()
+}
+
+/// Record the source cronjob which needs to be resumed if the run stops before its end
+///
+/// # Arguments
+///
+/// * `name` - &str
+pub fn set_suspended_source(name: &str) {
+    *SUSPENDED_SOURCE
+        .lock()
+        .unwrap_or_else(|err| err.into_inner()) = Some(name.to_string());
+}
+
+/// Take the source cronjob which is still suspended if any
+pub fn take_suspended_source() -> Option<String> {
+    SUSPENDED_SOURCE
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .take()
+}
@@ -0,0 +1,98 @@
+use jiff::SignedDuration;
+use std::fmt::Write;
+
+// Constant
+const SUITE_NAME: &str = "bakkutteh";
+
+/// Result of a dispatched job rendered as a test case
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    /// Namespace of the job, used as the class of the test case
+    pub namespace: String,
+    pub name: String,
+    pub duration: Option<SignedDuration>,
+    /// Reason of the failure followed by the failures of the pods. None when the job succeeded
+    pub failure: Option<Vec<String>>,
+}
+
+/// Render the test cases as a JUnit XML report
+///
+/// # Arguments
+///
+/// * `cases` - &[TestCase]
+pub fn report(cases: &[TestCase]) -> String {
+    let failures = cases.iter().filter(|case| case.failure.is_some()).count();
+    let total: f64 = cases.iter().map(seconds).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"{SUITE_NAME}\" tests=\"{}\" failures=\"{failures}\" time=\"{total:.3}\">",
+        cases.len()
+    );
+
+    for case in cases {
+        let _ = write!(
+            xml,
+            "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+            escape(&case.namespace),
+            escape(&case.name),
+            seconds(case)
+        );
+
+        match &case.failure {
+            Some(lines) => {
+                let message = lines.first().map(String::as_str).unwrap_or_default();
+                let _ = writeln!(
+                    xml,
+                    ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                    escape(message),
+                    escape(&lines.join("\n"))
+                );
+            }
+            None => xml.push_str("/>\n"),
+        }
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn seconds(case: &TestCase) -> f64 {
+    case.duration.map(|d| d.as_secs_f64()).unwrap_or_default()
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TestCase, report};
+    use jiff::SignedDuration;
+
+    #[test]
+    fn expect_to_render_junit_report() {
+        let xml = report(&[TestCase {
+            namespace: "default".to_string(),
+            name: "momo-manual".to_string(),
+            duration: Some(SignedDuration::from_millis(12_500)),
+            failure: Some(vec![
+                "Failed (BackoffLimitExceeded)".to_string(),
+                "container main exited with code 1: <oops>".to_string(),
+            ]),
+        }]);
+
+        assert!(xml.contains("tests=\"1\" failures=\"1\" time=\"12.500\""));
+        assert!(
+            xml.contains("<testcase classname=\"default\" name=\"momo-manual\" time=\"12.500\">")
+        );
+        assert!(xml.contains("<failure message=\"Failed (BackoffLimitExceeded)\">"));
+        assert!(xml.contains("code 1: &lt;oops&gt;</failure>"));
+    }
+}
@@ -0,0 +1,106 @@
+use super::i18n::tr_args;
+use anyhow::Result;
+use clap_mangen::Man;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+// Constant
+const SECTION_EXTENSION: &str = "1";
+const HELP_SUBCOMMAND: &str = "help";
+
+/// Render the man page of the command and of each of its subcommands. Subcommand pages are prefixed by
+/// their parent (e.g. bakkutteh-doctor)
+///
+/// # Arguments
+///
+/// * `cmd` - clap::Command
+pub fn pages(cmd: clap::Command) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut cmd = cmd;
+    cmd.build();
+
+    let mut pages = Vec::new();
+    collect(&cmd, &mut pages)?;
+
+    Ok(pages)
+}
+
+/// Write the man pages in the directory, or the page of the command on stdout when no directory is given
+///
+/// # Arguments
+///
+/// * `cmd` - clap::Command
+/// * `out_dir` - Option<&Path>
+pub fn write(cmd: clap::Command, out_dir: Option<&Path>) -> Result<()> {
+    let pages = pages(cmd)?;
+    let Some(out_dir) = out_dir else {
+        if let Some((_, page)) = pages.first() {
+            io::stdout().write_all(page)?;
+        }
+
+        return Ok(());
+    };
+
+    fs::create_dir_all(out_dir)?;
+    for (name, page) in pages {
+        let path = out_dir.join(format!("{name}.{SECTION_EXTENSION}"));
+        fs::write(&path, page)?;
+        println!(
+            "{}",
+            tr_args("man-page-written", &[("path", path.display().to_string())])
+        );
+    }
+
+    Ok(())
+}
+
+fn collect(cmd: &clap::Command, pages: &mut Vec<(String, Vec<u8>)>) -> Result<()> {
+    // Once built, the display name of a subcommand is prefixed by its parent
+    let name = cmd.get_display_name().unwrap_or_else(|| cmd.get_name());
+
+    let mut page = Vec::new();
+    Man::new(cmd.clone()).render(&mut page)?;
+    pages.push((name.to_string(), page));
+
+    // The help subcommand generated by clap is already described in the page of its parent
+    let subcommands = cmd
+        .get_subcommands()
+        .filter(|sub| !sub.is_hide_set() && sub.get_name() != HELP_SUBCOMMAND);
+    for sub in subcommands {
+        collect(sub, pages)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pages;
+    use clap::{CommandFactory, Parser, Subcommand};
+
+    #[derive(Parser)]
+    #[command(name = "tool")]
+    struct Cli {
+        #[command(subcommand)]
+        command: Option<Sub>,
+    }
+
+    #[derive(Subcommand)]
+    enum Sub {
+        /// First subcommand
+        First,
+    }
+
+    #[test]
+    fn expect_to_render_page_of_each_subcommand() {
+        let pages = pages(Cli::command()).unwrap();
+        let names = pages
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["tool", "tool-first"]);
+
+        let page = String::from_utf8(pages[1].1.clone()).unwrap();
+        assert!(page.contains("First subcommand"));
+    }
+}
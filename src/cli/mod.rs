@@ -1,28 +1,127 @@
+use crate::cli::events::{Event, LogFormat};
+use crate::cli::i18n::{Question, question, question_args, tr, tr_args};
+use crate::cli::interrupt::Pending;
+use crate::cli::pipeline::{Dispatch, Stage};
+use crate::cli::timings::{Phase, Timings};
 use crate::cli::ui::SpinnerWrapper;
-use crate::kube::KubeHandler;
-use crate::kube::spec::{ContainerEnv, EnvKind, SpecHandler, SpecResources};
+use crate::config::{Config, Propagation};
+use crate::kube::compat::{self, Version};
+use crate::kube::job::{self, JobStatus};
+use crate::kube::manifest::{self, Manifest};
+use crate::kube::schedule::{self, Schedule};
+use crate::kube::spec::{
+    self, ContainerEnv, EnvFrom, EnvFromKind, EnvKind, SpecHandler, SpecResources,
+};
+use crate::kube::template::TemplateSpecOps;
+use crate::kube::{
+    KubeHandler, SOURCE_SUSPENDED_ANNOTATION, SOURCE_TEMPLATE_ANNOTATION,
+    SOURCE_TEMPLATE_HASH_ANNOTATION, hash, is_forbidden, is_manual_job, manual_job_source,
+    rename_job, rendered_spec,
+};
+use crate::kube::{
+    access, argo, crd, describe, diff, doctor, drift, pod, redact, release, security, validate,
+};
+use ::kube::api::GroupVersionKind;
 use anyhow::{Result, anyhow};
-use clap::Parser;
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use colored::Colorize;
+use futures::{StreamExt, stream};
 use inquire::validator::Validation;
-use jiff::Span;
-use k8s_openapi::api::apps::v1::Deployment;
-use k8s_openapi::api::batch::v1::{CronJob, Job};
+use jiff::{SignedDuration, Span, Timestamp, Zoned, tz::TimeZone};
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment};
+use k8s_openapi::api::batch::v1::{CronJob, Job, JobSpec, JobTemplateSpec};
+use k8s_openapi::api::core::v1::{
+    ConfigMap, HostAlias, Pod, PodDNSConfig, PodDNSConfigOption, PodSpec, Secret,
+};
 use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{self, IsTerminal};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+pub mod bundle;
+pub mod editor;
+pub mod env;
+pub mod events;
+pub mod history;
+pub mod i18n;
+pub mod interrupt;
+pub mod junit;
+pub mod man;
+pub mod panic;
+pub mod pipeline;
+pub mod plugin;
+pub mod session;
+pub mod timings;
 pub mod ui;
+pub mod update;
 
 // Constant
-const SPLIT_ENV_OPERATOR: &str = "=";
 // See definition of the SI here
 // @link https://docs.rs/k8s-openapi/latest/k8s_openapi/apimachinery/pkg/api/resource/struct.Quantity.html
 const DECIMAL_SI: [&str; 6] = ["Ki", "Mi", "Gi", "Ti", "Pi", "Ei"];
 // CPU definition is either None (no format) or m (millis)
 const CPU: [&str; 2] = ["None", "m"];
-// Used to replace environment variable which already has a quote or single quote
-const REPLACE_STR: [char; 2] = ['\"', '\''];
+// Length of the values displayed in the list of the env to change
+const ENV_VALUE_PREVIEW: usize = 40;
+// Number of env listed per page when the env to change are selected
+const ENV_PAGE_SIZE: usize = 20;
+// Displayed in place of the values of the env referencing a Secret or looking sensitive
+const SECRET_MASK: &str = redact::MASK;
+// Number of upcoming runs displayed for a cronjob
+const UPCOMING_RUNS: usize = 3;
+// BackoffLimit used when neither the template nor the CLI define it
+const DEFAULT_BACKOFF_LIMIT: i32 = 3;
+// Number of previous runs displayed for a cronjob
+const HISTORY_SIZE: usize = 5;
+// Number of questions asked when updating the resources
+const RESOURCES_QUESTIONS: usize = 5;
+// Option of the picker used to run the pods without RuntimeClass
+const NO_RUNTIME_CLASS: &str = "<none>";
+// Options offered for a suspended job when the run stops before it's resumed (resume, delete, keep)
+const RECOVERY_OPTIONS: [&str; 3] = ["recover-resume", "recover-delete", "recover-keep"];
+// Actions offered on the envFrom of a container (add a ConfigMap, add a Secret, remove, prefix, done)
+const ENV_FROM_ACTIONS: [&str; 5] = [
+    "env-from-add-config-map",
+    "env-from-add-secret",
+    "env-from-remove",
+    "env-from-prefix",
+    "env-from-done",
+];
+const ADDITIONAL_ENV_KINDS: [&str; 4] = [
+    "additional-env-literal",
+    "additional-env-file",
+    "additional-env-field",
+    "additional-env-resource",
+];
+// Fields of the pod which can be exposed as env with the downward API
+const DOWNWARD_FIELDS: [&str; 8] = [
+    "metadata.name",
+    "metadata.namespace",
+    "metadata.uid",
+    "spec.nodeName",
+    "spec.serviceAccountName",
+    "status.hostIP",
+    "status.podIP",
+    "status.podIPs",
+];
+// Resources of the container which can be exposed as env with the downward API
+const DOWNWARD_RESOURCES: [&str; 6] = [
+    "limits.cpu",
+    "limits.memory",
+    "limits.ephemeral-storage",
+    "requests.cpu",
+    "requests.memory",
+    "requests.ephemeral-storage",
+];
+// Options skipping the confirmations, named in the log of the forced decisions
+const FORCE_OPTION: &str = "--force";
+const YES_OPTION: &str = "--yes";
 // Color code for the Clack purple theme on colorized side.
 pub(crate) const COLOR: (u8, u8, u8) = (180, 140, 247);
 
@@ -32,6 +131,17 @@ pub(crate) const COLOR: (u8, u8, u8) = (180, 140, 247);
     about = "A command to dispatch a kubernetes job from a cronjob spec"
 )]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[arg(
+        long,
+        global = true,
+        value_parser = i18n::parse_lang,
+        help = "Language of the prompts (en, fr). Defaults to the locale of the system"
+    )]
+    pub lang: Option<String>,
+
     #[arg(
         short,
         long,
@@ -45,11 +155,33 @@ pub struct Cli {
     #[arg(short, long, default_value = "false")]
     pub dry_run: bool,
 
-    #[arg(short, long, default_value = "default")]
+    #[arg(short, long, global = true, default_value = "default")]
     pub namespace: String,
 
-    #[arg(short, long, default_value = "3")]
-    pub backoff_limit: i32,
+    #[arg(
+        short,
+        long,
+        help = "The backoffLimit of the job. Defaults to 3 when the template doesn't define it"
+    )]
+    pub backoff_limit: Option<i32>,
+
+    #[arg(long, help = "The ttlSecondsAfterFinished of the job")]
+    pub ttl_seconds_after_finished: Option<i32>,
+
+    #[arg(
+        long,
+        conflicts_with = "deadline_from_schedule",
+        help = "The activeDeadlineSeconds of the job"
+    )]
+    pub active_deadline_seconds: Option<i64>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = Precedence::Ask,
+        help = "Which value is kept when a setting is defined by both the template and the CLI"
+    )]
+    pub precedence: Precedence,
 
     #[arg(
         long,
@@ -58,6 +190,81 @@ pub struct Cli {
     )]
     pub deployment: bool,
 
+    #[arg(
+        long,
+        default_value = "false",
+        conflicts_with = "deployment",
+        help = "Use the pod spec of a daemonset to create a manual job (e.g. to debug a node agent)"
+    )]
+    pub daemonset: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        conflicts_with_all = ["deployment", "daemonset", "crd"],
+        help = "Use the job template of a KEDA ScaledJob (spec.jobTargetRef) to create a manual job"
+    )]
+    pub scaledjob: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        conflicts_with_all = ["deployment", "daemonset", "scaledjob", "crd"],
+        help = "Use the first container template of an Argo CronWorkflow to create a manual job"
+    )]
+    pub cronworkflow: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["job_name", "deployment", "daemonset", "scaledjob", "cronworkflow"],
+        help = "Use the spec of a running pod to create a manual job"
+    )]
+    pub from_pod: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["job_name", "deployment", "daemonset", "scaledjob", "cronworkflow", "from_pod", "if_idle"],
+        help = "Use a CronJob / Deployment / DaemonSet / Pod manifest on disk as the source of the job"
+    )]
+    pub from_file: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value = "false",
+        conflicts_with_all = ["job_name", "deployment", "daemonset", "scaledjob", "cronworkflow", "from_pod", "from_file", "if_idle"],
+        help = "Read the manifest of the source from the stdin (e.g. kubectl get cronjob foo -o yaml | bakkutteh --from-stdin)"
+    )]
+    pub from_stdin: bool,
+
+    #[arg(
+        long,
+        value_parser = crd::parse_kind,
+        conflicts_with_all = ["deployment", "daemonset", "from_pod", "from_file", "from_stdin", "if_idle"],
+        help = "Use a custom resource embedding a job template as the source (group/version/kind e.g. keda.sh/v1alpha1/ScaledJob)"
+    )]
+    pub crd: Option<GroupVersionKind>,
+
+    #[arg(
+        long,
+        requires = "crd",
+        default_value = crd::DEFAULT_TEMPLATE_PATH,
+        help = "JSONPath of the job or pod template in the custom resource"
+    )]
+    pub template_path: String,
+
+    #[arg(
+        long,
+        help = "Only list the sources (cronjobs, deployments...) of the Helm release in the picker"
+    )]
+    pub release: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Run the executables of the plugins directory on the job. They run with the privileges of the user"
+    )]
+    pub plugins: bool,
+
     #[arg(
         long,
         help = "Output path of the spec when the user specified to use the --dry-run option"
@@ -66,147 +273,3517 @@ pub struct Cli {
 
     #[arg(long, help = "Wait for the job to complete before exiting")]
     pub wait: Option<Span>,
+
+    #[arg(
+        long,
+        requires = "wait",
+        help = "Write a JUnit XML report of the job once waited for (--wait)"
+    )]
+    pub junit: Option<PathBuf>,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Use numbered line-based prompts without cursor control (screen readers, dumb terminals)"
+    )]
+    pub plain_prompts: bool,
+
+    #[arg(long, help = "Record every prompt answer into the given session file")]
+    pub record: Option<String>,
+
+    #[arg(
+        long,
+        help = "Replay the answers of a recorded session file, prompting only for the missing ones"
+    )]
+    pub replay: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "false",
+        conflicts_with = "plain_prompts",
+        help = "Never prompt: the answers are given with the options and the other prompts keep their default. Enabled when the stdin isn't a terminal"
+    )]
+    pub non_interactive: bool,
+
+    #[arg(
+        long = "env",
+        value_name = "KEY=VALUE",
+        help = "Set the env of the job before the prompts. The env is set in the containers defining it, otherwise it's added to the first container"
+    )]
+    pub envs: Vec<String>,
+
+    #[arg(
+        long = "env-file",
+        help = "Set the env of the dotenv file (KEY=VALUE lines) like --env. The file can be given multiple times, the env given with --env take precedence"
+    )]
+    pub env_files: Vec<PathBuf>,
+
+    #[arg(
+        long = "env-from-file",
+        value_name = "KEY=PATH",
+        help = "Set the env to the contents of the file like --env (e.g. a JSON config). The contents are kept as is"
+    )]
+    pub env_from_files: Vec<String>,
+
+    #[arg(
+        long,
+        requires = "cpu",
+        help = "The memory limit of the container (e.g. 512Mi)"
+    )]
+    pub memory: Option<String>,
+
+    #[arg(
+        long,
+        requires = "memory",
+        help = "The cpu limit of the container (e.g. 500m)"
+    )]
+    pub cpu: Option<String>,
+
+    #[arg(
+        long,
+        help = "The container whose env and resources are set with --env, --env-file, --env-from-file, --memory and --cpu"
+    )]
+    pub container: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "false",
+        conflicts_with_all = ["envs", "env_files", "env_from_files"],
+        help = "Skip the review of the env, the job is dispatched with the env of the source as is"
+    )]
+    pub keep_env: bool,
+
+    #[arg(
+        short,
+        long,
+        default_value = "false",
+        help = "Answer yes to the confirmations of destructive operations"
+    )]
+    pub yes: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Skip the confirmations of destructive operations. The decisions are logged"
+    )]
+    pub force: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Keep the additional env values as typed (no quote removal or unescaping)"
+    )]
+    pub raw_env: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Keep the ${VAR} of the env values as typed instead of expanding them with the local environment"
+    )]
+    pub no_expand: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Refuse to dispatch the job when the source has active jobs or a manual job still running"
+    )]
+    pub if_idle: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Set the activeDeadlineSeconds of the job to the interval of the cronjob schedule"
+    )]
+    pub deadline_from_schedule: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Create the job suspended and start it at the next scheduled run of the cronjob"
+    )]
+    pub align_next_run: bool,
+
+    #[arg(
+        long,
+        help = "Path of the config file. Defaults to bakkutteh/config.yaml in the config directory of the user"
+    )]
+    pub config: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Display the values of the Secrets referenced by the env and of the env looking sensitive. Secrets are never read otherwise"
+    )]
+    pub show_secrets: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Refuse every mutating call. Only listing, previewing and dry runs are allowed"
+    )]
+    pub read_only: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Embed a compressed copy of the source template in an annotation of the job"
+    )]
+    pub embed_source_template: bool,
+
+    #[arg(
+        long,
+        help = "Display the diff between the spec of the source and the edited spec before creating the job"
+    )]
+    pub diff: bool,
+
+    #[arg(
+        long,
+        requires = "wait",
+        help = "Suspend the source cronjob while the manual job runs and resume it once the job is finished"
+    )]
+    pub suspend_source: bool,
+
+    #[arg(
+        long,
+        value_delimiter = ',',
+        requires = "wait",
+        conflicts_with_all = ["job_name", "target_name", "from_pod", "from_file", "from_stdin", "suspend_source"],
+        help = "Dispatch the jobs of the sources one after the other (e.g. extract,transform,load). The next job is only dispatched once the previous one succeeded"
+    )]
+    pub chain: Vec<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["job_name", "target_name", "from_pod", "from_file", "from_stdin", "wait", "suspend_source", "chain"],
+        help = "Select multiple sources in the picker and dispatch a job for each. The resources and the backoff limit are asked once for all the jobs"
+    )]
+    pub multi: bool,
+
+    #[arg(
+        long,
+        default_value = "4",
+        value_parser = clap::value_parser!(u16).range(1..),
+        help = "Maximum number of jobs created at the same time with --multi"
+    )]
+    pub max_concurrent: u16,
+
+    #[arg(
+        long,
+        help = "Open the manifest of the job in $EDITOR once edited through the questions, before creating it"
+    )]
+    pub edit: bool,
+
+    #[arg(
+        long,
+        action = ArgAction::Count,
+        help = "Log each API request (method, path, status, duration) on the stderr. Repeat it to log the bodies as well"
+    )]
+    pub verbose_api: u8,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Print the time spent listing, fetching, prompting and applying at the end of the run"
+    )]
+    pub timings: bool,
+
+    #[arg(
+        long,
+        help = "Write a debug bundle (tar.gz) to attach to a bug report when the run fails. The values of the env are redacted"
+    )]
+    pub debug_bundle: Option<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = LogFormat::Text,
+        help = "Format of the logs. The json format emits structured events (prompt answered, object fetched, job created) on the stderr"
+    )]
+    pub log_format: LogFormat,
+
+    #[arg(
+        long = "host-alias",
+        value_parser = pod::parse_host_alias,
+        help = "Append a hostAliases entry to the pod with the format IP=HOSTNAME[,HOSTNAME...]. Can be repeated"
+    )]
+    pub host_aliases: Vec<HostAlias>,
+
+    #[arg(
+        long,
+        value_parser = pod::DNS_POLICIES,
+        help = "Override the dnsPolicy of the pod"
+    )]
+    pub dns_policy: Option<String>,
+
+    #[arg(
+        long = "dns-nameserver",
+        help = "Append a nameserver to the dnsConfig of the pod. Can be repeated"
+    )]
+    pub dns_nameservers: Vec<IpAddr>,
+
+    #[arg(
+        long = "dns-search",
+        help = "Append a search domain to the dnsConfig of the pod. Can be repeated"
+    )]
+    pub dns_searches: Vec<String>,
+
+    #[arg(
+        long = "dns-option",
+        value_parser = pod::parse_dns_option,
+        help = "Append a resolver option (NAME[=VALUE], e.g. ndots=2) to the dnsConfig of the pod. Can be repeated"
+    )]
+    pub dns_options: Vec<PodDNSConfigOption>,
+
+    #[arg(
+        long,
+        help = "The schedulerName of the pod. Without it, a scheduler is asked with --pick-scheduler or when the template uses a custom scheduler"
+    )]
+    pub scheduler_name: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Ask the scheduler of the pod among the schedulers discovered in the kube-system namespace"
+    )]
+    pub pick_scheduler: bool,
+
+    #[arg(
+        long,
+        help = "The runtimeClassName of the pod (e.g. gvisor). Without it, a RuntimeClass is asked with --pick-runtime-class or when the template sets one"
+    )]
+    pub runtime_class: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Ask the RuntimeClass of the pod among the RuntimeClasses of the cluster"
+    )]
+    pub pick_runtime_class: bool,
+
+    #[arg(
+        long,
+        default_value = "false",
+        help = "Ask whether a PersistentVolumeClaim of the namespace is mounted in the job. Asked as well when the template mounts one"
+    )]
+    pub mount_pvc: bool,
+
+    #[arg(
+        long,
+        help = "Set the automountServiceAccountToken of the pod (true / false). Jobs which don't call the API can run without a token"
+    )]
+    pub automount_service_account_token: Option<bool>,
+
+    #[arg(
+        long,
+        help = "Set the enableServiceLinks of the pod (true / false). Disabling it removes the env injected for each service"
+    )]
+    pub enable_service_links: Option<bool>,
+
+    #[arg(
+        long = "projected-token",
+        value_parser = pod::parse_projected_token,
+        help = "Mount a service account token bound to an audience in the containers, with the format audience=AUDIENCE,path=MOUNT_PATH[,expiration=SECONDS]. Can be repeated"
+    )]
+    pub projected_tokens: Vec<pod::ProjectedToken>,
 }
 
 impl Cli {
-    pub async fn run<S: AsRef<str>>(&self, kube_handler: &mut KubeHandler<S>) -> Result<()> {
-        if self.dry_run && self.wait.is_some() {
-            return Err(anyhow!("Cannot use --wait with --dry-run"));
+    /// Get the kind of the object used as the source of the job
+    fn source_kind(&self) -> SourceKind<'_> {
+        if let Some(gvk) = &self.crd {
+            return SourceKind::Custom {
+                gvk: gvk.clone(),
+                path: &self.template_path,
+            };
         }
 
-        let name = match &self.job_name {
-            Some(name) => name.to_owned(),
+        if self.scaledjob {
+            return SourceKind::Custom {
+                gvk: crd::scaled_job(),
+                path: crd::SCALED_JOB_TEMPLATE_PATH,
+            };
+        }
+
+        if self.cronworkflow {
+            return SourceKind::CronWorkflow;
+        }
+
+        match (self.deployment, self.daemonset, self.from_pod.is_some()) {
+            (true, _, _) => SourceKind::Deployment,
+            (_, true, _) => SourceKind::DaemonSet,
+            (_, _, true) => SourceKind::Pod,
+            _ => SourceKind::CronJob,
+        }
+    }
+
+    /// Diagnose the environment and print a pass / fail report. An error is returned when a check failed
+    pub async fn doctor(&self) -> Result<()> {
+        let checks = doctor::diagnose(&self.namespace).await;
+
+        let mut failed = 0;
+        for check in &checks {
+            match &check.result {
+                Ok(detail) => println!("{} {}: {detail}", "PASS".green().bold(), check.name),
+                Err(err) => {
+                    failed += 1;
+                    println!("{} {}: {err}", "FAIL".bright_red().bold(), check.name);
+                }
+            }
+        }
+
+        match failed {
+            0 => Ok(()),
+            _ => Err(anyhow!(tr_args(
+                "doctor-failed",
+                &[
+                    ("failed", failed.to_string()),
+                    ("total", checks.len().to_string())
+                ]
+            ))),
+        }
+    }
+
+    /// Print the jobs dispatched with bakkutteh in the namespace, from the most recent to the oldest
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    pub async fn list_manual<S: AsRef<str>>(&self, kube_handler: &KubeHandler<S>) -> Result<()> {
+        let mut jobs = kube_handler.list_manual_jobs(None).await?;
+        if jobs.is_empty() {
+            println!(
+                "{}",
+                tr_args("no-manual-job", &[("namespace", self.namespace.clone())])
+            );
+            return Ok(());
+        }
+
+        jobs.sort_by(|a, b| {
+            b.metadata
+                .creation_timestamp
+                .cmp(&a.metadata.creation_timestamp)
+        });
+
+        let now = Timestamp::now();
+        let rows = jobs
+            .iter()
+            .map(|job| {
+                [
+                    job.metadata.name.clone().unwrap_or_default(),
+                    job::job_status(job).to_string(),
+                    job::age(job, now),
+                    manual_job_source(job).unwrap_or("-").to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let header = ["NAME", "STATUS", "AGE", "SOURCE"].map(String::from);
+        let widths = std::iter::once(&header)
+            .chain(&rows)
+            .fold([0; 3], |mut widths, row| {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.len());
+                }
+                widths
+            });
+
+        for [name, status, age, source] in std::iter::once(header).chain(rows) {
+            println!(
+                "{name:<w0$}   {status:<w1$}   {age:<w2$}   {source}",
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2]
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Print the status of the job, or its transitions and the ones of its pods until it's finished when watched. An
+    /// error is returned when the job failed so that the command composes with scripts
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `name` - &str
+    /// * `watch` - bool
+    pub async fn status<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        name: &str,
+        watch: bool,
+    ) -> Result<()> {
+        let status = match watch {
+            true => kube_handler.watch_job(name).await?,
+            false => {
+                let job: Job = kube_handler.get_object(name).await?;
+                let run = job::job_run(&job, Timestamp::now());
+                let duration = run
+                    .duration
+                    .map_or("-".to_string(), |duration| format!("{duration:#}"));
+                println!("{name} {} ({duration})", run.status);
+
+                run.status
+            }
+        };
+
+        match status {
+            JobStatus::Failed(reason) => Err(anyhow!(tr_args(
+                "job-run-failed",
+                &[
+                    ("name", name.to_string()),
+                    ("reason", reason.unwrap_or_else(|| tr("unknown-reason")))
+                ]
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Describe the job with its conditions, the phases of its pods and their last events
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `name` - &str
+    pub async fn describe<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        name: &str,
+    ) -> Result<()> {
+        let job: Job = kube_handler.get_object(name).await?;
+        let pods = kube_handler.get_job_pods(name).await?;
+        let names = std::iter::once(name)
+            .chain(pods.iter().filter_map(|pod| pod.metadata.name.as_deref()))
+            .collect::<Vec<_>>();
+        let events = kube_handler.get_events(&names).await?;
+
+        print!(
+            "{}",
+            describe::render(
+                &job,
+                manual_job_source(&job),
+                &pods,
+                &events,
+                Timestamp::now()
+            )
+        );
+
+        Ok(())
+    }
+
+    /// Cancel the job dispatched with bakkutteh once confirmed. The job is deleted after its pods
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `name` - &str
+    /// * `wait` - bool (wait until the job and its pods are deleted)
+    pub async fn cancel<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        name: &str,
+        wait: bool,
+    ) -> Result<()> {
+        let job: Job = kube_handler.get_object(name).await?;
+        if !is_manual_job(&job) {
+            return Err(anyhow!(tr_args(
+                "not-manual-job",
+                &[("name", name.to_string())]
+            )));
+        }
+
+        if !self.confirm_destructive(question_args(
+            "confirm-cancel",
+            &[("name", name.to_string())],
+        ))? {
+            return Ok(());
+        }
+
+        kube_handler.delete_job(name).await?;
+        match (wait, job.metadata.uid.as_deref()) {
+            (true, Some(uid)) => {
+                let mut spinner =
+                    SpinnerWrapper::new(tr_args("deleting-job", &[("name", name.to_string())]));
+                let res = kube_handler.wait_for_deletion(name, uid).await;
+                spinner.stop();
+                res?;
+
+                println!(
+                    "{}",
+                    tr_args("job-cancelled", &[("name", name.to_string())])
+                );
+            }
+            _ => println!(
+                "{}",
+                tr_args("job-cancelling", &[("name", name.to_string())])
+            ),
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch again a job dispatched with bakkutteh without any prompt. The rendered spec of the job is created
+    /// under a new name
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `name` - &str
+    /// * `new_name` - Option<&str> (generated from the source when omitted)
+    pub async fn rerun<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        name: &str,
+        new_name: Option<&str>,
+    ) -> Result<()> {
+        let mut job: Job = kube_handler.get_object(name).await?;
+        if !is_manual_job(&job) {
+            return Err(anyhow!(tr_args(
+                "not-manual-job",
+                &[("name", name.to_string())]
+            )));
+        }
+
+        rename_job(&mut job, new_name);
+        // A job waiting for the next run of its cronjob runs right away once dispatched again
+        if let Some(spec) = job.spec.as_mut() {
+            spec.suspend = None;
+        }
+
+        let job = kube_handler.recreate_job(job).await?;
+        let created = job.metadata.name.as_deref().unwrap_or_default();
+        events::emit(Event::JobCreated {
+            namespace: &self.namespace,
+            name: created,
+            dry_run: self.dry_run,
+        });
+        println!(
+            "{}",
+            tr_args(
+                "job-rerun",
+                &[("name", name.to_string()), ("created", created.to_string())]
+            )
+        );
+
+        Ok(())
+    }
+
+    /// Delete the finished jobs dispatched with bakkutteh which are older than the given age once confirmed
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `older_than` - Span
+    pub async fn prune<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        older_than: Span,
+    ) -> Result<()> {
+        let cutoff = Zoned::now()
+            .checked_sub(older_than)
+            .map_err(|err| {
+                anyhow!(tr_args(
+                    "invalid-age",
+                    &[("age", older_than.to_string()), ("error", err.to_string())]
+                ))
+            })?
+            .timestamp();
+
+        let jobs = kube_handler
+            .list_manual_jobs(None)
+            .await?
+            .into_iter()
+            .filter(|job| job::is_prunable(job, cutoff))
+            .filter_map(|job| job.metadata.name)
+            .collect::<Vec<_>>();
+
+        let age = [("age", format!("{older_than:#}"))];
+        if jobs.is_empty() {
+            println!("{}", tr_args("no-prunable-job", &age));
+            return Ok(());
+        }
+
+        println!("{}", tr_args("prunable-jobs", &age));
+        for name in &jobs {
+            println!("  - {name}");
+        }
+
+        if !self.confirm_destructive(question_args(
+            "confirm-prune",
+            &[("count", jobs.len().to_string())],
+        ))? {
+            return Ok(());
+        }
+
+        for name in &jobs {
+            kube_handler.delete_job(name).await?;
+            println!("{}", tr_args("job-deleted", &[("name", name.clone())]));
+        }
+
+        Ok(())
+    }
+
+    /// Dispatch a job from the source
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &mut KubeHandler<S>
+    /// * `config` - &Config
+    /// * `timings` - &mut Timings
+    pub async fn run<S: AsRef<str> + Clone>(
+        &self,
+        kube_handler: &mut KubeHandler<S>,
+        config: &Config,
+        timings: &mut Timings,
+    ) -> Result<()> {
+        self.run_with(kube_handler, config, timings, &[]).await
+    }
+
+    /// Dispatch a job through the stages of the pipeline: resolve the source → extract its spec → transform
+    /// (edition, then the custom stages) → validate → apply → observe
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &mut KubeHandler<S>
+    /// * `config` - &Config
+    /// * `timings` - &mut Timings
+    /// * `stages` - &[Box<dyn Stage>] (custom transforms)
+    pub async fn run_with<S: AsRef<str> + Clone>(
+        &self,
+        kube_handler: &mut KubeHandler<S>,
+        config: &Config,
+        timings: &mut Timings,
+        stages: &[Box<dyn Stage>],
+    ) -> Result<()> {
+        if self.dry_run && self.wait.is_some() {
+            return Err(anyhow!(tr("wait-with-dry-run")));
+        }
+
+        // The features of the job are checked against the version of the API server before the job is created
+        timings.enter(Phase::Fetching);
+        let server_version = kube_handler
+            .server_version()
+            .await
+            .inspect_err(|err| {
+                eprintln!(
+                    "{}",
+                    tr_args("server-version-failed", &[("error", err.to_string())])
+                )
+            })
+            .ok();
+        timings.enter(Phase::Prompting);
+
+        // The source is read from the manifest instead of the cluster when given
+        let manifest = match (&self.from_file, self.from_stdin) {
+            (Some(path), _) => Some(manifest::read(path)?),
+            (None, true) => Some(manifest::read_stdin()?),
+            (None, false) => None,
+        };
+        let sources = match &manifest {
+            Some(manifest) => vec![manifest.name.clone()],
+            None => self.resolve_sources(kube_handler, timings).await?,
+        };
+
+        let mut state = RunState {
+            server_version,
+            manifest,
+            ..Default::default()
+        };
+        if self.multi {
+            return self
+                .dispatch_all(kube_handler, config, timings, stages, &sources, &mut state)
+                .await;
+        }
+
+        let total = sources.len();
+        // Each step of the chain is the source, the job and its status or the error of the dispatch
+        let mut steps: Vec<(String, Option<Job>, Option<anyhow::Error>)> = Vec::new();
+        for (idx, source) in sources.iter().enumerate() {
+            if total > 1 {
+                let progress = tr_args(
+                    "bulk-progress",
+                    &[
+                        ("source", source.clone()),
+                        ("index", (idx + 1).to_string()),
+                        ("total", total.to_string()),
+                    ],
+                );
+                println!("{}", progress.bold());
+            }
+
+            let result = self
+                .dispatch_source(
+                    kube_handler,
+                    config,
+                    timings,
+                    stages,
+                    source.clone(),
+                    &mut state,
+                )
+                .await;
+            if self.chain.is_empty() {
+                result?;
+                continue;
+            }
+
+            // The next job of the chain is only dispatched once the previous one succeeded
+            let job = match result {
+                Ok(Some(name)) => kube_handler.get_object::<Job, _>(&name).await,
+                Ok(None) => continue,
+                Err(err) => Err(err),
+            };
+            let succeeded = job
+                .as_ref()
+                .is_ok_and(|job| job::job_status(job) == JobStatus::Succeeded);
+            match job {
+                Ok(job) => steps.push((source.clone(), Some(job), None)),
+                Err(err) => steps.push((source.clone(), None, Some(err))),
+            }
+            if !succeeded {
+                break;
+            }
+        }
+
+        if self.chain.is_empty() {
+            return Ok(());
+        }
+
+        self.display_summary(&sources, &steps);
+        match steps.last() {
+            Some((_, Some(job), None)) if job::job_status(job) == JobStatus::Succeeded => Ok(()),
+            Some((source, job, err)) => {
+                let reason = match (job, err) {
+                    (_, Some(err)) => err.to_string(),
+                    (Some(job), None) => job::job_status(job).to_string(),
+                    (None, None) => String::new(),
+                };
+
+                Err(anyhow!(tr_args(
+                    "chain-stopped",
+                    &[("source", source.clone()), ("reason", reason)]
+                )))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Ask the questions of the jobs of the sources one after the other, then create the jobs concurrently with at
+    /// most --max-concurrent jobs at a time. The outcome of each job is printed once known and a summary is
+    /// displayed at the end
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `config` - &Config
+    /// * `timings` - &mut Timings
+    /// * `stages` - &[Box<dyn Stage>]
+    /// * `sources` - &[String]
+    /// * `state` - &mut RunState
+    async fn dispatch_all<S: AsRef<str> + Clone>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        config: &Config,
+        timings: &mut Timings,
+        stages: &[Box<dyn Stage>],
+        sources: &[String],
+        state: &mut RunState,
+    ) -> Result<()> {
+        let total = sources.len();
+        let mut dispatches = Vec::new();
+        for (idx, source) in sources.iter().enumerate() {
+            let progress = tr_args(
+                "bulk-progress",
+                &[
+                    ("source", source.clone()),
+                    ("index", (idx + 1).to_string()),
+                    ("total", total.to_string()),
+                ],
+            );
+            println!("{}", progress.bold());
+
+            let dispatch = self
+                .prepare_dispatch(kube_handler, config, timings, stages, source.clone(), state)
+                .await?;
+            dispatches.extend(dispatch);
+        }
+
+        if dispatches.is_empty() {
+            return Ok(());
+        }
+
+        // Each job is created with its own handler as the handler keeps the job being created
+        timings.enter(Phase::Applying);
+        let total = dispatches.len();
+        let mut steps = stream::iter(dispatches.into_iter().enumerate())
+            .map(|(idx, dispatch)| async move {
+                let mut kube_handler = kube_handler.clone();
+                let source = dispatch.source.clone();
+                let args = [
+                    ("source", source.clone()),
+                    ("index", (idx + 1).to_string()),
+                    ("total", total.to_string()),
+                ];
+                println!("{}", tr_args("bulk-creating", &args));
+
+                let result = self
+                    .create_job(&mut kube_handler, dispatch, &mut SpinnerWrapper::hidden())
+                    .await;
+                if let Err(err) = &result {
+                    let args = [&args[..], &[("error", err.to_string())]].concat();
+                    eprintln!("{}", tr_args("bulk-job-failed", &args).bright_red());
+                }
+
+                (idx, source, result)
+            })
+            .buffer_unordered(usize::from(self.max_concurrent))
+            .collect::<Vec<_>>()
+            .await;
+        steps.sort_by_key(|(idx, _, _)| *idx);
+
+        let steps = steps
+            .into_iter()
+            .map(|(_, source, result)| match result {
+                Ok(job) => (source, Some(job), None),
+                Err(err) => (source, None, Some(err)),
+            })
+            .collect::<Vec<_>>();
+        let sources = steps
+            .iter()
+            .map(|(source, _, _)| source.clone())
+            .collect::<Vec<_>>();
+        self.display_summary(&sources, &steps);
+
+        match steps.iter().filter(|(_, _, err)| err.is_some()).count() {
+            0 => Ok(()),
+            failed => Err(anyhow!(tr_args(
+                "bulk-failed",
+                &[("failed", failed.to_string()), ("total", total.to_string())]
+            ))),
+        }
+    }
+
+    /// Display the status and the duration of the dispatched jobs. The sources after a failed job of a chain are
+    /// skipped
+    ///
+    /// # Arguments
+    ///
+    /// * `sources` - &[String]
+    /// * `steps` - &[(String, Option<Job>, Option<anyhow::Error>)]
+    fn display_summary(
+        &self,
+        sources: &[String],
+        steps: &[(String, Option<Job>, Option<anyhow::Error>)],
+    ) {
+        let now = Timestamp::now();
+        let rows = sources
+            .iter()
+            .enumerate()
+            .map(|(idx, source)| match steps.get(idx) {
+                Some((_, Some(job), _)) => {
+                    let run = job::job_run(job, now);
+                    [
+                        source.clone(),
+                        job.metadata.name.clone().unwrap_or_default(),
+                        run.status.to_string(),
+                        run.duration
+                            .map(|duration| {
+                                format!("{:#}", SignedDuration::from_secs(duration.as_secs()))
+                            })
+                            .unwrap_or_else(|| "-".to_string()),
+                    ]
+                }
+                Some((_, None, err)) => [
+                    source.clone(),
+                    "-".to_string(),
+                    err.as_ref()
+                        .map(|err| tr_args("chain-step-error", &[("error", err.to_string())]))
+                        .unwrap_or_default(),
+                    "-".to_string(),
+                ],
+                None => [
+                    source.clone(),
+                    "-".to_string(),
+                    tr("chain-step-skipped"),
+                    "-".to_string(),
+                ],
+            })
+            .collect::<Vec<_>>();
+
+        let header = ["SOURCE", "JOB", "STATUS", "DURATION"].map(String::from);
+        let widths = std::iter::once(&header)
+            .chain(&rows)
+            .fold([0; 3], |mut widths, row| {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+                widths
+            });
+
+        println!();
+        for [source, job, status, duration] in std::iter::once(header).chain(rows) {
+            println!(
+                "{source:<w0$}  {job:<w1$}  {status:<w2$}  {duration}",
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2]
+            );
+        }
+    }
+
+    /// Extract, edit and create the job of the source
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &mut KubeHandler<S>
+    /// * `config` - &Config
+    /// * `timings` - &mut Timings
+    /// * `stages` - &[Box<dyn Stage>]
+    /// * `source` - String
+    /// * `state` - &mut RunState
+    async fn dispatch_source<S: AsRef<str>>(
+        &self,
+        kube_handler: &mut KubeHandler<S>,
+        config: &Config,
+        timings: &mut Timings,
+        stages: &[Box<dyn Stage>],
+        source: String,
+        state: &mut RunState,
+    ) -> Result<Option<String>> {
+        let Some(dispatch) = self
+            .prepare_dispatch(kube_handler, config, timings, stages, source, state)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        // The scheduled runs of the cronjob would race with the manual job
+        let suspended_source = match self.suspend_source && !self.dry_run {
+            true => self.pause_source(kube_handler, &dispatch).await?,
+            false => None,
+        };
+
+        // The spinner is displayed until the job is observed
+        let mut apply_spinner = match self.dry_run {
+            true => SpinnerWrapper::new(tr("applying-dry-run")),
+            false => SpinnerWrapper::new(tr("applying")),
+        };
+        timings.enter(Phase::Applying);
+
+        let result = self
+            .create_job(kube_handler, dispatch, &mut apply_spinner)
+            .await
+            .map(|job| job.metadata.name);
+
+        if let Some(name) = suspended_source {
+            interrupt::take_suspended_source();
+            let resumed = self.set_suspend(kube_handler, &name, false).await;
+            return result.and_then(|name| resumed.map(|_| name));
+        }
+
+        result
+    }
+
+    /// Extract and edit the job of the source. None is returned when only the drift of the source is displayed
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `config` - &Config
+    /// * `timings` - &mut Timings
+    /// * `stages` - &[Box<dyn Stage>]
+    /// * `source` - String
+    /// * `state` - &mut RunState
+    async fn prepare_dispatch<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        config: &Config,
+        timings: &mut Timings,
+        stages: &[Box<dyn Stage>],
+        source: String,
+        state: &mut RunState,
+    ) -> Result<Option<Dispatch>> {
+        let manifest = state.manifest.take();
+        let mut dispatch = self
+            .extract_spec(kube_handler, config, source, manifest, timings)
+            .await?;
+        let original = self.diff.then(|| dispatch.job_spec.clone());
+
+        self.transform(kube_handler, config, &mut dispatch, state, timings)
+            .await?;
+        pipeline::run_stages(stages, &mut dispatch)?;
+        self.validate(kube_handler, &mut dispatch, state.server_version, timings)
+            .await?;
+        if self.edit {
+            self.edit_manifest(&mut dispatch)?;
+        }
+
+        if let Some(original) = original {
+            display_diff(
+                &original,
+                &dispatch.job_spec,
+                (&dispatch.source, &dispatch.target),
+            )?;
+        }
+
+        if self.is_diff() {
+            timings.enter(Phase::Fetching);
+            return self
+                .display_drift(kube_handler, &dispatch.source, &dispatch.job_spec)
+                .await
+                .map(|_| None);
+        }
+
+        Ok(Some(dispatch))
+    }
+
+    /// Create the job of the dispatch, observe it and record it in the audit log
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &mut KubeHandler<S>
+    /// * `dispatch` - Dispatch
+    /// * `spinner` - &mut SpinnerWrapper
+    async fn create_job<S: AsRef<str>>(
+        &self,
+        kube_handler: &mut KubeHandler<S>,
+        mut dispatch: Dispatch,
+        spinner: &mut SpinnerWrapper,
+    ) -> Result<Job> {
+        let start_at = dispatch.start_at.take();
+        // The hash is known once the job is built, it's the one annotated on the job
+        let mut entry = history::Entry::new(
+            &self.namespace,
+            &dispatch.source,
+            &dispatch.target,
+            String::new(),
+        );
+        let result = match self.apply(kube_handler, dispatch).await {
+            Ok(job) => self
+                .observe(kube_handler, job.clone(), start_at, spinner)
+                .await
+                .map(|_| job),
+            Err(err) => Err(err),
+        }
+        .inspect_err(|_| {
+            // stop the spinner before returning an error
+            spinner.stop();
+        });
+
+        if !self.dry_run {
+            entry.spec_hash = kube_handler.spec_hash().unwrap_or_default();
+            self.record_history(entry, &result);
+        }
+
+        result
+    }
+
+    /// Suspend the source cronjob of the dispatch. A cronjob which was already suspended is left as is and None is
+    /// returned as it shouldn't be resumed afterwards
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `dispatch` - &Dispatch
+    async fn pause_source<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        dispatch: &Dispatch,
+    ) -> Result<Option<String>> {
+        let Some(cron_job) = &dispatch.cron_job else {
+            return Err(anyhow!(tr("suspend-source-not-cronjob")));
+        };
+
+        if cron_job
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.suspend)
+            .unwrap_or_default()
+        {
+            return Ok(None);
+        }
+
+        self.set_suspend(kube_handler, &dispatch.source, true)
+            .await?;
+        interrupt::set_suspended_source(&dispatch.source);
+
+        Ok(Some(dispatch.source.clone()))
+    }
+
+    /// Suspend or resume the scheduling of the cronjob
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `name` - &str
+    /// * `suspend` - bool
+    pub async fn set_suspend<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        name: &str,
+        suspend: bool,
+    ) -> Result<()> {
+        kube_handler.set_cron_job_suspend(name, suspend).await?;
+
+        let key = match suspend {
+            true => "cronjob-suspended",
+            false => "cronjob-resumed",
+        };
+        let colored = name.truecolor(COLOR.0, COLOR.1, COLOR.2).to_string();
+        println!("{}", tr_args(key, &[("name", colored)]));
+
+        Ok(())
+    }
+
+    /// Record the outcome of the dispatch in the audit log. The dispatch isn't failed when it can't be recorded
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - history::Entry
+    /// * `result` - &Result<()>
+    fn record_history<T>(&self, mut entry: history::Entry, result: &Result<T>) {
+        let Some(path) = history::path() else {
+            return;
+        };
+
+        (entry.outcome, entry.error) = match (result, self.wait) {
+            (Err(err), _) => (history::Outcome::Failed, Some(err.to_string())),
+            (Ok(_), Some(_)) => (history::Outcome::Succeeded, None),
+            (Ok(_), None) => (history::Outcome::Created, None),
+        };
+
+        if let Err(err) = history::record(&path, &entry) {
+            eprintln!(
+                "{}",
+                tr_args("history-record-failed", &[("error", err.to_string())])
+            );
+        }
+    }
+
+    /// Print the dispatches recorded in the audit log, the most recent last. Only the dispatches of the namespace
+    /// are printed unless every namespace is asked
+    ///
+    /// # Arguments
+    ///
+    /// * `all_namespaces` - bool
+    /// * `source` - Option<&str>
+    /// * `since` - Option<Span>
+    /// * `failed` - bool
+    /// * `limit` - usize
+    pub fn history(
+        &self,
+        all_namespaces: bool,
+        source: Option<&str>,
+        since: Option<Span>,
+        failed: bool,
+        limit: usize,
+    ) -> Result<()> {
+        let since = since
+            .map(|span| {
+                Zoned::now()
+                    .checked_sub(span)
+                    .map(|zoned| zoned.timestamp())
+                    .map_err(|err| {
+                        anyhow!(tr_args(
+                            "invalid-duration",
+                            &[("duration", span.to_string()), ("error", err.to_string())]
+                        ))
+                    })
+            })
+            .transpose()?;
+        let filter = history::Filter {
+            namespace: (!all_namespaces).then(|| self.namespace.clone()),
+            source: source.map(String::from),
+            since,
+            failed,
+        };
+
+        let path = history::path().ok_or_else(|| anyhow!(tr("no-data-directory")))?;
+        let entries = history::read(&path, &filter)?;
+        if entries.is_empty() {
+            println!(
+                "{}",
+                tr_args("empty-history", &[("path", path.display().to_string())])
+            );
+            return Ok(());
+        }
+
+        let skipped = entries.len().saturating_sub(limit);
+        print!("{}", history::render(&entries[skipped..]));
+
+        Ok(())
+    }
+
+    /// Print the spec hash of the job manifest
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - &Path
+    pub fn hash_manifest(&self, file: &Path) -> Result<()> {
+        let manifest = fs::read_to_string(file).map_err(|err| {
+            anyhow!(tr_args(
+                "read-manifest-failed",
+                &[
+                    ("path", file.display().to_string()),
+                    ("error", err.to_string())
+                ]
+            ))
+        })?;
+        println!("{}", hash::manifest_hash(&manifest)?);
+
+        Ok(())
+    }
+
+    /// Get the names of the sources of the jobs, from the CLI or picked from the list of cronjobs / deployments.
+    /// Multiple sources can be picked with --multi
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `timings` - &mut Timings
+    async fn resolve_sources<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        timings: &mut Timings,
+    ) -> Result<Vec<String>> {
+        if let Some(name) = self.job_name.as_ref().or(self.from_pod.as_ref()) {
+            return Ok(vec![name.to_owned()]);
+        }
+
+        if !self.chain.is_empty() {
+            return Ok(self.chain.clone());
+        }
+
+        // Picking the first workload of the list would dispatch an arbitrary job
+        if ui::is_non_interactive() {
+            return Err(anyhow!(tr("non-interactive-source")));
+        }
+
+        // Show a spinner while getting the list of jobs
+        let mut spinner = SpinnerWrapper::new(tr("listing-jobs"));
+        timings.enter(Phase::Listing);
+        let progress = |pages: usize, items: usize| {
+            spinner.set_message(tr_args(
+                "listing-progress",
+                &[("items", items.to_string()), ("pages", pages.to_string())],
+            ))
+        };
+
+        // Each entry is the name of the object and its label in the picker
+        let in_release = |meta: &ObjectMeta| {
+            self.release
+                .as_ref()
+                .is_none_or(|name| release::belongs_to(meta, name))
+        };
+        let list: Vec<(String, String)> = match self.source_kind() {
+            SourceKind::Deployment => {
+                kube_handler
+                    .list_with(
+                        |deployment: Deployment| {
+                            if !in_release(&deployment.metadata) {
+                                return None;
+                            }
+
+                            deployment.metadata.name.map(|name| (name.clone(), name))
+                        },
+                        progress,
+                    )
+                    .await?
+            }
+            SourceKind::DaemonSet => {
+                kube_handler
+                    .list_with(
+                        |daemonset: DaemonSet| {
+                            if !in_release(&daemonset.metadata) {
+                                return None;
+                            }
+
+                            daemonset.metadata.name.map(|name| (name.clone(), name))
+                        },
+                        progress,
+                    )
+                    .await?
+            }
+            SourceKind::CronWorkflow => kube_handler
+                .list_custom_objects(&argo::cron_workflow())
+                .await?
+                .into_iter()
+                .filter(|object| in_release(&object.metadata))
+                .filter_map(|object| object.metadata.name.map(|name| (name.clone(), name)))
+                .collect(),
+            SourceKind::Custom { gvk, .. } => kube_handler
+                .list_custom_objects(&gvk)
+                .await?
+                .into_iter()
+                .filter(|object| in_release(&object.metadata))
+                .filter_map(|object| object.metadata.name.map(|name| (name.clone(), name)))
+                .collect(),
+            SourceKind::CronJob | SourceKind::Pod => {
+                kube_handler
+                    .list_with(
+                        |cron_job: CronJob| {
+                            if !in_release(&cron_job.metadata) {
+                                return None;
+                            }
+
+                            let label = cron_job_label(&cron_job);
+                            cron_job.metadata.name.map(|name| (name, label))
+                        },
+                        progress,
+                    )
+                    .await?
+            }
+        };
+
+        // Stop the spinner after getting the list
+        spinner.stop();
+        timings.enter(Phase::Prompting);
+
+        if let Some(name) = &self.release
+            && list.is_empty()
+        {
+            return Err(anyhow!(tr_args(
+                "no-release-workload",
+                &[("release", name.to_owned())]
+            )));
+        }
+
+        let labels = list.iter().map(|(_, label)| label.clone()).collect();
+        let answers = match self.multi {
+            true => ui::multi_select(question("select-sources"), labels, &[])?,
+            false => vec![ui::select(question("select-source"), labels)?],
+        };
+        if answers.is_empty() {
+            return Err(anyhow!(tr("no-source-selected")));
+        }
+
+        answers
+            .into_iter()
+            .map(|answer| {
+                list.iter()
+                    .find_map(|(name, label)| (*label == answer).then(|| name.clone()))
+                    .ok_or_else(|| {
+                        anyhow!(tr_args(
+                            "selected-object-not-found",
+                            &[("name", answer.clone())]
+                        ))
+                    })
+            })
+            .collect()
+    }
+
+    /// Make room for the target job and get the spec of the source along with the labels & annotations copied
+    /// onto the job
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `config` - &Config
+    /// * `name` - String (name of the source)
+    /// * `manifest` - Option<Manifest> (source read from a manifest)
+    /// * `timings` - &mut Timings
+    async fn extract_spec<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        config: &Config,
+        name: String,
+        manifest: Option<Manifest>,
+        timings: &mut Timings,
+    ) -> Result<Dispatch> {
+        timings.enter(Phase::Fetching);
+        if self.if_idle {
+            self.ensure_idle(kube_handler, &name).await?;
+        }
+
+        // Check if the targeted name already exist in the cluster
+        let target_job_name = match &self.target_name {
+            Some(name) => format!("{}-manual", name),
             None => {
-                // Show a spinner while getting the list of jobs
-                let mut spinner = SpinnerWrapper::new("Getting list of jobs...");
+                println!("{}", tr("target-name-from-source"));
+                format!("{}-manual", name)
+            }
+        };
+
+        // Nothing is created when comparing with the previous dispatch
+        if !self.is_diff()
+            && let Ok(existing) = kube_handler.get_object::<Job, _>(&target_job_name).await
+        {
+            timings.enter(Phase::Prompting);
+            match self.confirm_destructive(question("confirm-delete-existing"))? {
+                true => {
+                    timings.enter(Phase::Applying);
+                    kube_handler.delete_object(&target_job_name).await?;
+                    // The deleted job can be recreated if the flow stops before the new job is created
+                    interrupt::set(Pending::DeletedJob(Box::new(existing)));
+                }
+                false => {
+                    return Err(anyhow!(
+                        "Job with the same name already exist in the cluster"
+                    ));
+                }
+            }
+        }
+
+        // Get the job details and stop the spinner if it exists
+        let mut object_spinner = SpinnerWrapper::new(tr("fetching-object"));
+        timings.enter(Phase::Fetching);
+
+        // The cronjob is kept in order to display information about its schedule
+        let (job_tmpl_spec, cron_job) = self.fetch_source(kube_handler, &name, manifest).await?;
+
+        // Stop the spinner after getting the job details
+        object_spinner.stop();
+        timings.enter(Phase::Prompting);
+
+        // The unmodified template is recorded to be able to review what was changed for the manual run
+        let mut source_annotations = BTreeMap::from([(
+            SOURCE_TEMPLATE_HASH_ANNOTATION.to_string(),
+            hash::hash(&job_tmpl_spec)?,
+        )]);
+        if self.embed_source_template {
+            source_annotations.insert(
+                SOURCE_TEMPLATE_ANNOTATION.to_string(),
+                hash::compress(&job_tmpl_spec)?,
+            );
+        }
+
+        let Some(mut job_spec) = job_tmpl_spec.spec else {
+            return Err(anyhow!("Unable to get the job template spec"));
+        };
+
+        // Host-level accesses are common in the templates of DaemonSets and are rarely expected in a manual job
+        let accesses = job_spec
+            .template
+            .spec
+            .as_ref()
+            .map(access::host_access)
+            .unwrap_or_default();
+        if !accesses.is_empty() {
+            println!("{}", tr("host-access-warning").bright_red().bold());
+            for access in &accesses {
+                println!("  - {}", access.bright_red());
+            }
+
+            if !self.confirm_destructive(question("confirm-host-access"))? {
+                return Err(anyhow!(tr("host-access-refused")));
+            }
+        }
+
+        // Labels & annotations of the source template are copied onto the job unless they're filtered out
+        let source_meta = job_tmpl_spec.metadata.unwrap_or_default();
+        let mut labels = source_meta.labels.unwrap_or_default();
+        let mut annotations = source_meta.annotations.unwrap_or_default();
+        self.filter_propagated(
+            &config.propagation,
+            &mut labels,
+            &mut annotations,
+            &mut job_spec,
+        )?;
+        annotations.extend(source_annotations);
+
+        Ok(Dispatch {
+            source: name,
+            target: target_job_name,
+            cron_job,
+            job_spec,
+            backoff_limit: DEFAULT_BACKOFF_LIMIT,
+            labels,
+            annotations,
+            start_at: None,
+        })
+    }
+
+    /// Get the template of the job from the source, along with the source when it's a cronjob
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `name` - &str
+    /// * `manifest` - Option<Manifest> (source read from a manifest)
+    async fn fetch_source<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        name: &str,
+        manifest: Option<Manifest>,
+    ) -> Result<(JobTemplateSpec, Option<CronJob>)> {
+        let source = match (manifest, self.source_kind()) {
+            (Some(manifest), _) => (manifest.template, manifest.cron_job),
+            (None, SourceKind::Deployment) => (
+                kube_handler
+                    .get_spec_for_object::<_, Deployment>(name)
+                    .await?,
+                None,
+            ),
+            (None, SourceKind::DaemonSet) => (
+                kube_handler
+                    .get_spec_for_object::<_, DaemonSet>(name)
+                    .await?,
+                None,
+            ),
+            (None, SourceKind::Pod) => (
+                kube_handler.get_spec_for_object::<_, Pod>(name).await?,
+                None,
+            ),
+            (None, SourceKind::Custom { gvk, path }) => (
+                kube_handler
+                    .get_spec_for_custom_object(&gvk, name, |value| {
+                        crd::select(value, path).and_then(crd::template_spec)
+                    })
+                    .await?,
+                None,
+            ),
+            (None, SourceKind::CronWorkflow) => (
+                kube_handler
+                    .get_spec_for_custom_object(&argo::cron_workflow(), name, argo::template_spec)
+                    .await?,
+                None,
+            ),
+            (None, SourceKind::CronJob) => {
+                let cron_job: CronJob = kube_handler.get_object(name).await?;
+                let spec = cron_job
+                    .get_template_spec()
+                    .ok_or_else(|| anyhow!("Unable to get the template spec for {name}"))?;
+
+                (spec, Some(cron_job))
+            }
+        };
+
+        Ok(source)
+    }
+
+    /// Let the user edit the job then apply the settings given with the CLI
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `config` - &Config
+    /// * `dispatch` - &mut Dispatch
+    /// * `state` - &mut RunState (answers shared by the jobs of a bulk dispatch)
+    /// * `timings` - &mut Timings
+    async fn transform<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        config: &Config,
+        dispatch: &mut Dispatch,
+        state: &mut RunState,
+        timings: &mut Timings,
+    ) -> Result<()> {
+        let Dispatch {
+            source: name,
+            cron_job,
+            job_spec,
+            backoff_limit,
+            labels,
+            annotations,
+            start_at,
+            ..
+        } = dispatch;
+
+        if let Some(cron_job) = cron_job.as_ref() {
+            // A suspended cronjob usually means that someone intentionally paused the workload
+            if cron_job
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.suspend)
+                .unwrap_or_default()
+            {
+                let warning = tr_args("suspended-source-warning", &[("name", name.clone())]);
+                println!("{}", warning.bright_red().bold());
+                annotations.insert(SOURCE_SUSPENDED_ANNOTATION.to_string(), "true".to_string());
+            }
+
+            self.display_schedule(cron_job);
+
+            // Most manual runs are retries, show the outcome of the last scheduled runs
+            timings.enter(Phase::Fetching);
+            if let Err(err) = self.display_run_history(kube_handler, cron_job).await {
+                eprintln!(
+                    "{}",
+                    tr_args("run-history-failed", &[("error", err.to_string())])
+                );
+            }
+            timings.enter(Phase::Prompting);
+        }
+
+        // Native sidecars which aren't needed by the manual run can be removed before their env is edited
+        self.prompt_sidecars(job_spec)?;
+
+        // Get the environment variable from the job spec
+        let mut envs = job_spec.get_env()?;
+        let mut resources = None;
+
+        // Let the user choose the value to keep for the env names which are defined multiple times
+        self.prompt_duplicated_env(&mut envs)?;
+
+        // The env given with --env & --env-file are displayed as the current values by the prompts
+        self.set_flag_envs(&mut envs, &config.protected_env)?;
+
+        // The env of the source is kept as is, there is nothing to display
+        let (config_maps, secrets) = match self.keep_env {
+            true => (BTreeMap::new(), None),
+            false => {
+                self.resolve_env_values(kube_handler, &envs, timings)
+                    .await?
+            }
+        };
+
+        // Walk through the edition steps. Going back on the first prompt of a step returns to the previous step
+        let mut step = EditStep::Env;
+        while step != EditStep::Done {
+            let res = match step {
+                EditStep::Env
+                | EditStep::RemoveEnv
+                | EditStep::AdditionalEnv
+                | EditStep::EnvFrom
+                    if self.keep_env =>
+                {
+                    Ok(())
+                }
+                // Show the user the environment variable and let the user confirm the value to output
+                EditStep::Env => match self.prompt_user_env(
+                    &mut envs,
+                    &config_maps,
+                    secrets.as_ref(),
+                    &config.protected_env,
+                ) {
+                    // The Secrets of the namespace are listed only when a literal is replaced by a reference
+                    Ok(references) if !references.is_empty() => {
+                        timings.enter(Phase::Fetching);
+                        let secrets = kube_handler.list_names::<Secret>().await;
+                        timings.enter(Phase::Prompting);
+
+                        match prompt_secret_refs(&mut envs, &references, secrets) {
+                            Err(err) if ui::is_back(&err) => continue,
+                            res => res,
+                        }
+                    }
+                    res => res.map(|_| ()),
+                },
+                EditStep::RemoveEnv => match ui::confirm(question("confirm-remove-env"), false) {
+                    Ok(true) => match self.prompt_remove_env(&mut envs, &config.protected_env) {
+                        Err(err) if ui::is_back(&err) => continue,
+                        res => res,
+                    },
+                    res => res.map(|_| ()),
+                },
+                EditStep::AdditionalEnv => {
+                    match ui::confirm(question("confirm-additional-env"), false) {
+                        Ok(true) => match self
+                            .process_prompt_additional_env(&mut envs, &config.protected_env)
+                        {
+                            // Ask again whether the user wants to add additional env
+                            Err(err) if ui::is_back(&err) => continue,
+                            res => res,
+                        },
+                        res => res.map(|_| ()),
+                    }
+                }
+                EditStep::EnvFrom => match ui::confirm(question("confirm-env-from"), false) {
+                    Ok(true) => {
+                        // The ConfigMaps & Secrets of the namespace are listed to be picked
+                        timings.enter(Phase::Fetching);
+                        let config_maps = kube_handler.list_names::<ConfigMap>().await;
+                        let secrets = kube_handler.list_names::<Secret>().await;
+                        timings.enter(Phase::Prompting);
+
+                        match self.prompt_env_from(&mut envs, config_maps, secrets) {
+                            Err(err) if ui::is_back(&err) => continue,
+                            res => res,
+                        }
+                    }
+                    res => res.map(|_| ()),
+                },
+                EditStep::Resources if self.memory.is_some() => {
+                    resources = Some(self.flag_resources(&envs)?);
+                    Ok(())
+                }
+                // The resources answered for the first job of a bulk dispatch are used by the next ones
+                EditStep::Resources if state.resources.is_some() => {
+                    resources = state.resources.clone().flatten();
+                    Ok(())
+                }
+                // Upgrade the resources limits if needed
+                EditStep::Resources => match ui::confirm(question("confirm-resources"), false) {
+                    Ok(true) => match self.process_resources_prompt(&envs) {
+                        Ok(res) => {
+                            resources = Some(res);
+                            Ok(())
+                        }
+                        Err(err) if ui::is_back(&err) => continue,
+                        Err(err) => Err(err),
+                    },
+                    Ok(false) => {
+                        resources = None;
+                        Ok(())
+                    }
+                    Err(err) => Err(err),
+                },
+                // Labels & annotations of the job (e.g. ownership) are edited separately from the ones of the pods
+                // (e.g. mesh injection, scraping)
+                EditStep::JobMetadata => {
+                    match ui::confirm(
+                        question_args("confirm-metadata", &[("target", "job".to_string())]),
+                        false,
+                    ) {
+                        Ok(true) => match self.prompt_metadata("job", labels, annotations) {
+                            Err(err) if ui::is_back(&err) => continue,
+                            res => res,
+                        },
+                        res => res.map(|_| ()),
+                    }
+                }
+                EditStep::PodMetadata => {
+                    match ui::confirm(
+                        question_args("confirm-metadata", &[("target", "pods".to_string())]),
+                        false,
+                    ) {
+                        Ok(true) => {
+                            let pod_meta = job_spec.template.metadata.get_or_insert_default();
+                            let mut pod_labels = pod_meta.labels.take().unwrap_or_default();
+                            let mut pod_annotations =
+                                pod_meta.annotations.take().unwrap_or_default();
+
+                            let res =
+                                self.prompt_metadata("pods", &mut pod_labels, &mut pod_annotations);
+                            pod_meta.labels = (!pod_labels.is_empty()).then_some(pod_labels);
+                            pod_meta.annotations =
+                                (!pod_annotations.is_empty()).then_some(pod_annotations);
+
+                            match res {
+                                Err(err) if ui::is_back(&err) => continue,
+                                res => res,
+                            }
+                        }
+                        res => res.map(|_| ()),
+                    }
+                }
+                EditStep::Done => Ok(()),
+            };
+
+            step = match res {
+                Ok(()) => step.next(),
+                // The shared resources aren't asked, going back skips them
+                Err(err) if ui::is_back(&err) => match step.previous() {
+                    EditStep::Resources if state.resources.is_some() => EditStep::EnvFrom,
+                    previous => previous,
+                },
+                Err(err) => return Err(err),
+            };
+        }
+
+        // Rebuild the job spec with the updated environment variables
+        if !self.keep_env {
+            job_spec.rebuild_env(&mut envs)?;
+        }
+
+        state.resources.get_or_insert_with(|| resources.clone());
+        if let Some(mut resources) = resources {
+            // The containers of the sources of a bulk dispatch may be named differently
+            if !envs.iter().any(|env| env.name == resources.container_name)
+                && let Some(first) = envs.first()
+            {
+                resources.container_name = first.name.clone();
+            }
+            job_spec.update_resources(resources)?;
+        }
+
+        if let Some(pod_spec) = job_spec.template.spec.as_mut() {
+            pod::add_host_aliases(pod_spec, &self.host_aliases);
+            pod::add_projected_tokens(pod_spec, &self.projected_tokens);
+            pod::set_dns(pod_spec, self.dns_policy.as_deref(), self.dns_config())?;
+
+            if let Some(automount) = self.automount_service_account_token {
+                pod_spec.automount_service_account_token = Some(automount);
+            }
+            if let Some(enable) = self.enable_service_links {
+                pod_spec.enable_service_links = Some(enable);
+            }
+
+            // Manual batch jobs may have to be routed to a batch scheduler (e.g. volcano). The cluster is only queried
+            // when asked or when the template already relies on a custom scheduler
+            let custom_scheduler = pod_spec
+                .scheduler_name
+                .as_deref()
+                .is_some_and(|name| name != pod::DEFAULT_SCHEDULER);
+            pod_spec.scheduler_name = match &self.scheduler_name {
+                Some(name) => Some(name.to_owned()),
+                None if !self.pick_scheduler && !custom_scheduler => pod_spec.scheduler_name.take(),
+                None => {
+                    timings.enter(Phase::Fetching);
+                    let schedulers = kube_handler.list_schedulers().await;
+                    timings.enter(Phase::Prompting);
+
+                    self.prompt_scheduler(schedulers, pod_spec.scheduler_name.take())?
+                }
+            };
+
+            // Jobs processing untrusted input may have to run in a sandboxed runtime
+            pod_spec.runtime_class_name = match &self.runtime_class {
+                Some(name) => Some(name.to_owned()),
+                None if !self.pick_runtime_class && pod_spec.runtime_class_name.is_none() => None,
+                None => {
+                    timings.enter(Phase::Fetching);
+                    let classes = kube_handler.list_runtime_classes().await;
+                    timings.enter(Phase::Prompting);
+
+                    self.prompt_runtime_class(classes, pod_spec.runtime_class_name.take())?
+                }
+            };
+
+            // Data-repair jobs usually need a volume which isn't mounted by the template
+            let mounts_pvc = pod_spec
+                .volumes
+                .iter()
+                .flatten()
+                .any(|volume| volume.persistent_volume_claim.is_some());
+            if self.mount_pvc || mounts_pvc {
+                timings.enter(Phase::Fetching);
+                let claims = kube_handler.list_pvcs().await;
+                timings.enter(Phase::Prompting);
+                self.prompt_pvc(pod_spec, claims)?;
+            }
+        }
+
+        // Prevent the manual run from overlapping the next scheduled run
+        let mut active_deadline_seconds = self.active_deadline_seconds;
+        if self.deadline_from_schedule {
+            match cron_job.as_ref().and_then(schedule_interval) {
+                Some(seconds) => {
+                    println!(
+                        "{}",
+                        tr_args("schedule-interval", &[("seconds", seconds.to_string())])
+                    );
+                    active_deadline_seconds = Some(seconds);
+                }
+                None => eprintln!("{}", tr("schedule-interval-failed")),
+            }
+        }
+
+        // Settings of the template are only overridden according to the precedence
+        *backoff_limit = match state.backoff_limit {
+            Some(limit) => limit,
+            None => self
+                .resolve_setting("backoffLimit", job_spec.backoff_limit, self.backoff_limit)?
+                .unwrap_or(DEFAULT_BACKOFF_LIMIT),
+        };
+        state.backoff_limit = Some(*backoff_limit);
+        job_spec.ttl_seconds_after_finished = self.resolve_setting(
+            "ttlSecondsAfterFinished",
+            job_spec.ttl_seconds_after_finished,
+            self.ttl_seconds_after_finished,
+        )?;
+        job_spec.active_deadline_seconds = self.resolve_setting(
+            "activeDeadlineSeconds",
+            job_spec.active_deadline_seconds,
+            active_deadline_seconds,
+        )?;
+
+        // The job is created suspended and resumed at the next scheduled run
+        if self.align_next_run {
+            let run = cron_job
+                .as_ref()
+                .and_then(next_run)
+                .ok_or_else(|| anyhow!(tr_args("next-run-failed", &[("name", name.clone())])))?;
+            job_spec.suspend = Some(true);
+            *start_at = Some(run);
+        }
+
+        Ok(())
+    }
+
+    /// Resolve the values of the env referencing a ConfigMap in order to display them. The values of the env
+    /// referencing a Secret are only fetched when the user explicitly opted in
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `envs` - &[ContainerEnv]
+    /// * `timings` - &mut Timings
+    async fn resolve_env_values<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        envs: &[ContainerEnv],
+        timings: &mut Timings,
+    ) -> Result<(EnvValues, Option<EnvValues>)> {
+        timings.enter(Phase::Fetching);
+        let config_maps = kube_handler
+            .get_referenced_config_maps(envs)
+            .await
+            .unwrap_or_else(|err| {
+                eprintln!(
+                    "{}",
+                    tr_args("config-map-values-failed", &[("error", err.to_string())])
+                );
+                BTreeMap::new()
+            });
+
+        timings.enter(Phase::Prompting);
+        let show_secrets =
+            self.show_secrets && ui::confirm(question("confirm-secret-display"), false)?;
+        let secrets = match show_secrets {
+            true => {
+                timings.enter(Phase::Fetching);
+                Some(
+                    kube_handler
+                        .get_referenced_secrets(envs)
+                        .await
+                        .unwrap_or_else(|err| {
+                            eprintln!(
+                                "{}",
+                                tr_args("secret-values-failed", &[("error", err.to_string())])
+                            );
+                            BTreeMap::new()
+                        }),
+                )
+            }
+            false => None,
+        };
+        timings.enter(Phase::Prompting);
+
+        Ok((config_maps, secrets))
+    }
+
+    /// Check the job against the Pod Security profile of the namespace and the version of the API server
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `dispatch` - &mut Dispatch
+    /// * `server_version` - Option<Version>
+    /// * `timings` - &mut Timings
+    async fn validate<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        dispatch: &mut Dispatch,
+        server_version: Option<Version>,
+        timings: &mut Timings,
+    ) -> Result<()> {
+        let job_spec = &mut dispatch.job_spec;
+
+        // Jobs which don't comply with the restricted profile would be rejected at admission
+        timings.enter(Phase::Fetching);
+        let namespace_labels = kube_handler.get_namespace_labels().await;
+        timings.enter(Phase::Prompting);
+        match namespace_labels {
+            Ok(labels)
+                if labels.get(security::ENFORCE_LABEL).map(String::as_str)
+                    == Some(security::RESTRICTED) =>
+            {
+                self.prompt_restricted(job_spec)?
+            }
+            Ok(_) => {}
+            Err(err) => eprintln!(
+                "{}",
+                tr_args("pod-security-failed", &[("error", err.to_string())])
+            ),
+        }
+
+        if let Some(version) = server_version {
+            self.check_version_skew(job_spec, version)?;
+        }
+
+        Ok(())
+    }
+
+    /// Let the user edit the manifest of the job in the editor. The fields which aren't covered by the questions
+    /// (e.g. volumes, affinity) can be changed there
+    ///
+    /// # Arguments
+    ///
+    /// * `dispatch` - &mut Dispatch
+    fn edit_manifest(&self, dispatch: &mut Dispatch) -> Result<()> {
+        let job = Job {
+            metadata: ObjectMeta {
+                name: Some(dispatch.target.clone()),
+                labels: Some(dispatch.labels.clone()),
+                annotations: Some(dispatch.annotations.clone()),
+                ..Default::default()
+            },
+            spec: Some(JobSpec {
+                backoff_limit: Some(dispatch.backoff_limit),
+                ..dispatch.job_spec.clone()
+            }),
+            ..Default::default()
+        };
+
+        let edited = editor::edit(&job, validate::validate_job)?;
+        let Some(job_spec) = edited.spec else {
+            return Err(anyhow!(tr("edited-job-without-spec")));
+        };
+
+        dispatch.target = edited.metadata.name.unwrap_or_default();
+        dispatch.labels = edited.metadata.labels.unwrap_or_default();
+        dispatch.annotations = edited.metadata.annotations.unwrap_or_default();
+        dispatch.backoff_limit = job_spec.backoff_limit.unwrap_or(dispatch.backoff_limit);
+        dispatch.job_spec = job_spec;
+
+        Ok(())
+    }
+
+    /// Create the job of the dispatch
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &mut KubeHandler<S>
+    /// * `dispatch` - Dispatch
+    async fn apply<S: AsRef<str>>(
+        &self,
+        kube_handler: &mut KubeHandler<S>,
+        dispatch: Dispatch,
+    ) -> Result<Job> {
+        let job = kube_handler
+            .build_manual_job(
+                &dispatch.target,
+                &dispatch.source,
+                dispatch.job_spec,
+                dispatch.backoff_limit,
+                dispatch.labels,
+                dispatch.annotations,
+            )?
+            .apply_manual_job()
+            .await?;
+        interrupt::clear();
+
+        events::emit(Event::JobCreated {
+            namespace: &self.namespace,
+            name: job.metadata.name.as_deref().unwrap_or_default(),
+            dry_run: self.dry_run,
+        });
+
+        Ok(job)
+    }
+
+    /// Resume the suspended job at its run, wait for the job and display it
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `job` - Job
+    /// * `start_at` - Option<Zoned>
+    /// * `spinner` - &mut SpinnerWrapper
+    async fn observe<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        job: Job,
+        start_at: Option<Zoned>,
+        spinner: &mut SpinnerWrapper,
+    ) -> Result<()> {
+        let job = match start_at {
+            Some(run) if !self.dry_run => {
+                spinner.stop();
+                let name = job.metadata.name.clone().unwrap_or_default();
+                interrupt::set(Pending::SuspendedJob(name));
+
+                let job = self.resume_at(kube_handler, job, &run).await?;
+                interrupt::clear();
+
+                job
+            }
+            _ => job,
+        };
+
+        let name = job.metadata.name.clone().unwrap_or_default();
+        let waited = kube_handler.wait_for_job(job, self.wait).await;
+        // stop the spinner before displaying the output
+        spinner.stop();
+
+        // The report is written even if the job didn't complete in time so that CI shows the failure
+        if let Some(path) = &self.junit {
+            let wait_err = waited.as_ref().err().map(|err| err.to_string());
+            self.write_junit(kube_handler, &name, wait_err, path)
+                .await?;
+        }
+        let job = waited?;
+
+        let output = kube_handler.display_spec(job, !self.show_secrets)?;
+        if let (Some(output_path), Some(contents)) = (&self.dry_run_output_path, output) {
+            fs::write(PathBuf::from(output_path), contents)?;
+        }
+
+        Ok(())
+    }
+
+    /// Offer to roll back or to finish the step which was pending when the run was interrupted or failed so that
+    /// the cluster isn't left in a half-done state
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    pub async fn recover<S: AsRef<str>>(&self, kube_handler: &KubeHandler<S>) -> Result<()> {
+        // The source cronjob is resumed as it was only suspended for the run
+        if let Some(name) = interrupt::take_suspended_source() {
+            self.set_suspend(kube_handler, &name, false).await?;
+        }
+
+        let Some(pending) = interrupt::take() else {
+            return Ok(());
+        };
+
+        match pending {
+            Pending::DeletedJob(job) => {
+                let name = job.metadata.name.clone().unwrap_or_default();
+                let recreate = ui::confirm(
+                    question_args("recover-deleted-job", &[("name", name.clone())]),
+                    true,
+                )?;
+
+                if recreate {
+                    kube_handler.recreate_job(*job).await?;
+                    let name = name.truecolor(COLOR.0, COLOR.1, COLOR.2).to_string();
+                    println!("{}", tr_args("job-recreated", &[("name", name)]));
+                }
+            }
+            Pending::SuspendedJob(name) => {
+                let options = RECOVERY_OPTIONS.map(tr);
+                let answer = ui::select(
+                    question_args("recover-suspended-job", &[("name", name.clone())]),
+                    options.to_vec(),
+                )?;
+
+                let colored = name.truecolor(COLOR.0, COLOR.1, COLOR.2).to_string();
+                match options.iter().position(|option| *option == answer) {
+                    Some(0) => {
+                        kube_handler.set_job_suspend(&name, false).await?;
+                        println!("{}", tr_args("job-resumed", &[("name", colored)]));
+                    }
+                    Some(1) => kube_handler.delete_object(&name).await?,
+                    _ => println!("{}", tr_args("job-left-suspended", &[("name", colored)])),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Display the next scheduled runs of the cronjob
+    ///
+    /// # Arguments
+    ///
+    /// * `cron_job` - &CronJob
+    fn display_schedule(&self, cron_job: &CronJob) {
+        let Some(spec) = &cron_job.spec else {
+            return;
+        };
+
+        match Schedule::parse(&spec.schedule, spec.time_zone.as_deref()) {
+            Ok(schedule) => {
+                let name = cron_job
+                    .metadata
+                    .name
+                    .as_deref()
+                    .unwrap_or_default()
+                    .truecolor(COLOR.0, COLOR.1, COLOR.2)
+                    .to_string();
+                println!(
+                    "{}",
+                    tr_args(
+                        "upcoming-runs",
+                        &[("name", name), ("schedule", spec.schedule.clone())]
+                    )
+                );
+
+                let local = TimeZone::system();
+                for run in schedule.upcoming(Timestamp::now(), UPCOMING_RUNS) {
+                    println!("  - {}", schedule::format_run(&run, &local));
+                }
+            }
+            Err(err) => eprintln!(
+                "{}",
+                tr_args(
+                    "invalid-schedule",
+                    &[
+                        ("schedule", spec.schedule.clone()),
+                        ("error", err.to_string())
+                    ]
+                )
+            ),
+        }
+    }
+
+    /// Ask the scheduler of the pods when custom schedulers are discovered. The scheduler of the template is kept
+    /// when the user isn't allowed to discover the schedulers (e.g. the kube-system namespace can't be listed)
+    ///
+    /// # Arguments
+    ///
+    /// * `schedulers` - Result<BTreeSet<String>>
+    /// * `current` - Option<String>
+    fn prompt_scheduler(
+        &self,
+        schedulers: Result<BTreeSet<String>>,
+        current: Option<String>,
+    ) -> Result<Option<String>> {
+        let mut schedulers = match schedulers {
+            Ok(schedulers) => schedulers,
+            Err(err) if is_forbidden(&err) => return Ok(current),
+            Err(err) => return Err(err),
+        };
+
+        let selected = current.as_deref().unwrap_or(pod::DEFAULT_SCHEDULER);
+        schedulers.insert(selected.to_string());
+        if schedulers.len() == 1 {
+            return Ok(current);
+        }
+
+        // The scheduler of the template is displayed first
+        let list = std::iter::once(selected.to_string())
+            .chain(schedulers.into_iter().filter(|name| name != selected))
+            .collect::<Vec<_>>();
+        let answer = ui::select(question("select-scheduler"), list)?;
+
+        match current {
+            None if answer == pod::DEFAULT_SCHEDULER => Ok(None),
+            _ => Ok(Some(answer)),
+        }
+    }
+
+    /// Ask the RuntimeClass of the pods when the cluster defines some. The RuntimeClass of the template is kept
+    /// when the user isn't allowed to list the RuntimeClasses
+    ///
+    /// # Arguments
+    ///
+    /// * `classes` - Result<Vec<String>>
+    /// * `current` - Option<String>
+    fn prompt_runtime_class(
+        &self,
+        classes: Result<Vec<String>>,
+        current: Option<String>,
+    ) -> Result<Option<String>> {
+        let classes = match classes {
+            Ok(classes) if !classes.is_empty() => classes,
+            Ok(_) => return Ok(current),
+            Err(err) if is_forbidden(&err) => return Ok(current),
+            Err(err) => return Err(err),
+        };
+
+        // The RuntimeClass of the template is displayed first
+        let selected = current.as_deref().unwrap_or(NO_RUNTIME_CLASS).to_string();
+        let list = std::iter::once(selected.clone())
+            .chain(
+                classes
+                    .into_iter()
+                    .chain(std::iter::once(NO_RUNTIME_CLASS.to_string()))
+                    .filter(|name| name != &selected),
+            )
+            .collect::<Vec<_>>();
+        let answer = ui::select(question("select-runtime-class"), list)?;
+
+        match answer.as_str() {
+            NO_RUNTIME_CLASS => Ok(None),
+            _ => Ok(Some(answer)),
+        }
+    }
+
+    /// Ask whether a PersistentVolumeClaim of the namespace is mounted in a container of the pod and where. Nothing is
+    /// asked when the user isn't allowed to list the PersistentVolumeClaims or when the namespace has none
+    ///
+    /// # Arguments
+    ///
+    /// * `pod_spec` - &mut PodSpec
+    /// * `claims` - Result<Vec<String>>
+    fn prompt_pvc(&self, pod_spec: &mut PodSpec, claims: Result<Vec<String>>) -> Result<()> {
+        let claims = match claims {
+            Ok(claims) if !claims.is_empty() => claims,
+            Ok(_) => return Ok(()),
+            Err(err) if is_forbidden(&err) => return Ok(()),
+            Err(err) => return Err(err),
+        };
+
+        if !ui::confirm(question("confirm-mount-pvc"), false)? {
+            return Ok(());
+        }
+
+        let claim = ui::select(question("select-pvc"), claims)?;
+        let containers = pod_spec
+            .containers
+            .iter()
+            .map(|container| container.name.clone())
+            .collect::<Vec<_>>();
+        let container = match containers.len() {
+            1 => containers[0].clone(),
+            _ => ui::select(question("select-pvc-container"), containers)?,
+        };
+
+        let mount_path = ui::text_with_validator(
+            question_args(
+                "input-pvc-mount-path",
+                &[("claim", claim.clone()), ("container", container.clone())],
+            ),
+            |s: &str| match s.starts_with('/') {
+                true => Ok(Validation::Valid),
+                false => Ok(Validation::Invalid(tr("invalid-mount-path").into())),
+            },
+        )?;
+
+        pod::mount_pvc(pod_spec, &claim, &container, &mount_path)
+    }
+
+    /// Build the dnsConfig appended to the one of the pod from the options
+    fn dns_config(&self) -> PodDNSConfig {
+        let nameservers = self.dns_nameservers.iter().map(|ip| ip.to_string());
+
+        PodDNSConfig {
+            nameservers: non_empty(nameservers.collect()),
+            searches: non_empty(self.dns_searches.clone()),
+            options: non_empty(self.dns_options.clone()),
+        }
+    }
+
+    /// Whether no one can answer the prompts: asked with --non-interactive or the stdin isn't a terminal. The stdin
+    /// is expected to be piped in plain mode (answers) and with --from-stdin (manifest)
+    pub fn is_non_interactive(&self) -> bool {
+        self.non_interactive
+            || (!self.plain_prompts && !self.from_stdin && !io::stdin().is_terminal())
+    }
+
+    /// Whether the job is compared with the previous dispatch instead of being created
+    fn is_diff(&self) -> bool {
+        matches!(
+            self.command,
+            Some(Command::Diff {
+                against_last: true,
+                ..
+            })
+        )
+    }
+
+    /// Display the diff between the template of the source and the spec of a job dispatched with bakkutteh. The
+    /// template embedded in the job is used when present, otherwise it's fetched from the source
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `name` - &str
+    pub async fn diff_job<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        name: &str,
+    ) -> Result<()> {
+        let job: Job = kube_handler.get_object(name).await?;
+        let source = manual_job_source(&job)
+            .filter(|_| is_manual_job(&job))
+            .ok_or_else(|| anyhow!(tr_args("not-manual-job", &[("name", name.to_string())])))?;
+
+        let embedded = job
+            .metadata
+            .annotations
+            .as_ref()
+            .and_then(|annotations| annotations.get(SOURCE_TEMPLATE_ANNOTATION));
+        let template: JobTemplateSpec = match embedded {
+            Some(encoded) => hash::decompress(encoded)?,
+            None => self.fetch_source(kube_handler, source, None).await?.0,
+        };
+
+        let original = template
+            .spec
+            .ok_or_else(|| anyhow!("Unable to get the job template spec of {source}"))?;
+        let modified = rendered_spec(&job)
+            .ok_or_else(|| anyhow!("Unable to get the spec of the job {name}"))?;
+
+        display_diff(&original, &modified, (source, name))
+    }
+
+    /// Display the env & resources which changed since the previous dispatch of the same source
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `source` - &str
+    /// * `job_spec` - &JobSpec
+    async fn display_drift<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        source: &str,
+        job_spec: &JobSpec,
+    ) -> Result<()> {
+        let previous = kube_handler
+            .list_manual_jobs(Some(source))
+            .await?
+            .into_iter()
+            .max_by(|a, b| {
+                a.metadata
+                    .creation_timestamp
+                    .cmp(&b.metadata.creation_timestamp)
+            })
+            .ok_or_else(|| {
+                anyhow!(tr_args(
+                    "no-previous-dispatch",
+                    &[("source", source.to_string())]
+                ))
+            })?;
+
+        let previous_name = previous.metadata.name.unwrap_or_default();
+        let Some(previous_spec) = previous.spec else {
+            return Err(anyhow!("Unable to get the spec of the job {previous_name}"));
+        };
+
+        let changes = drift::drift(&previous_spec, job_spec)?;
+        let previous = [("name", previous_name)];
+        if changes.is_empty() {
+            println!("{}", tr_args("no-drift", &previous));
+            return Ok(());
+        }
+
+        println!("{}", tr_args("drift", &previous));
+        for change in changes {
+            println!("  - {}", change.bright_yellow());
+        }
+
+        Ok(())
+    }
+
+    /// Display the start, the duration and the result of the most recent jobs scheduled by the cronjob.
+    /// The failures of the pods of the last job are displayed if it failed
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `cron_job` - &CronJob
+    async fn display_run_history<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        cron_job: &CronJob,
+    ) -> Result<()> {
+        let Some(uid) = cron_job.metadata.uid.as_deref() else {
+            return Ok(());
+        };
+
+        let jobs = kube_handler.list_owned_jobs(uid).await?;
+        let Some(last) = jobs.first() else {
+            println!("{}", tr("no-previous-run"));
+            return Ok(());
+        };
+
+        println!("{}", tr("previous-runs"));
+        let now = Timestamp::now();
+        let local = TimeZone::system();
+        for job in jobs.iter().take(HISTORY_SIZE) {
+            let run = job::job_run(job, now);
+            let start = run.start.map_or(tr("run-not-started"), |start| {
+                start
+                    .to_zoned(local.clone())
+                    .strftime("%Y-%m-%d %H:%M %Z")
+                    .to_string()
+            });
+            let duration = run
+                .duration
+                .map_or("-".to_string(), |duration| format!("{duration:#}"));
+            let status = match &run.status {
+                JobStatus::Succeeded => run.status.to_string().bright_green(),
+                JobStatus::Failed(_) => run.status.to_string().bright_red(),
+                _ => run.status.to_string().bright_yellow(),
+            };
+
+            println!(
+                "  - {} {start} ({duration}) {status}",
+                job.metadata
+                    .name
+                    .as_deref()
+                    .unwrap_or_default()
+                    .truecolor(COLOR.0, COLOR.1, COLOR.2)
+            );
+        }
+
+        let name = last.metadata.name.as_deref().unwrap_or_default();
+        if let JobStatus::Failed(_) = job::job_status(last) {
+            println!(
+                "{}",
+                tr_args("last-run-failures", &[("name", name.to_string())])
+            );
+            for pod in kube_handler.get_job_pods(name).await? {
+                for failure in job::pod_failures(&pod) {
+                    println!(
+                        "  - pod {}: {failure}",
+                        pod.metadata.name.as_deref().unwrap_or_default()
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that the cronjob has no active jobs and that no manual job dispatched from the source is still running
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `name` - &str
+    async fn ensure_idle<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        name: &str,
+    ) -> Result<()> {
+        if self.source_kind() == SourceKind::CronJob {
+            let cron_job: CronJob = kube_handler.get_object(name).await?;
+            let active = cron_job
+                .status
+                .and_then(|status| status.active)
+                .unwrap_or_default();
+
+            if !active.is_empty() {
+                let jobs = active
+                    .into_iter()
+                    .filter_map(|job| job.name)
+                    .collect::<Vec<_>>();
+
+                return Err(anyhow!(tr_args(
+                    "source-not-idle",
+                    &[("name", name.to_string()), ("jobs", jobs.join(", "))]
+                )));
+            }
+        }
+
+        let running = kube_handler
+            .list_manual_jobs(Some(name))
+            .await?
+            .into_iter()
+            .filter(|job| {
+                !matches!(
+                    job::job_status(job),
+                    JobStatus::Succeeded | JobStatus::Failed(_)
+                )
+            })
+            .filter_map(|job| job.metadata.name)
+            .collect::<Vec<_>>();
+
+        match running.is_empty() {
+            true => Ok(()),
+            false => Err(anyhow!(tr_args(
+                "manual-jobs-running",
+                &[("name", name.to_string()), ("jobs", running.join(", "))]
+            ))),
+        }
+    }
+
+    /// Resolve the value of a setting defined by the template and/or the CLI. When both values differ,
+    /// the precedence decides which value is kept
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - &str
+    /// * `template` - Option<T>
+    /// * `cli` - Option<T>
+    fn resolve_setting<T>(
+        &self,
+        field: &str,
+        template: Option<T>,
+        cli: Option<T>,
+    ) -> Result<Option<T>>
+    where
+        T: Display + PartialEq + Copy,
+    {
+        let (template_value, cli_value) = match (template, cli) {
+            (Some(template_value), Some(cli_value)) if template_value != cli_value => {
+                (template_value, cli_value)
+            }
+            (template, cli) => return Ok(cli.or(template)),
+        };
+
+        let keep_template = match self.precedence {
+            Precedence::Template => true,
+            Precedence::Cli => false,
+            // The option was given on purpose, keeping the template value would silently ignore it (e.g. in CI)
+            Precedence::Ask if ui::is_non_interactive() => false,
+            Precedence::Ask => {
+                let template_option = tr_args(
+                    "precedence-template",
+                    &[("value", template_value.to_string())],
+                );
+                let answer = ui::select(
+                    question_args("select-precedence", &[("field", field.to_string())]),
+                    vec![
+                        template_option.clone(),
+                        tr_args("precedence-cli", &[("value", cli_value.to_string())]),
+                    ],
+                )?;
+
+                answer == template_option
+            }
+        };
+
+        match keep_template {
+            true => Ok(Some(template_value)),
+            false => Ok(Some(cli_value)),
+        }
+    }
+
+    /// Filter the labels & annotations of the source template which are copied onto the job and its pods.
+    /// The keys are filtered with the allow / deny lists of the config and can be reviewed by the user
+    ///
+    /// # Arguments
+    ///
+    /// * `propagation` - &Propagation
+    /// * `labels` - &mut BTreeMap<String, String>
+    /// * `annotations` - &mut BTreeMap<String, String>
+    /// * `job_spec` - &mut JobSpec
+    fn filter_propagated(
+        &self,
+        propagation: &Propagation,
+        labels: &mut BTreeMap<String, String>,
+        annotations: &mut BTreeMap<String, String>,
+        job_spec: &mut JobSpec,
+    ) -> Result<()> {
+        let pod_meta = job_spec.template.metadata.get_or_insert_default();
+        let mut pod_labels = pod_meta.labels.take().unwrap_or_default();
+        let mut pod_annotations = pod_meta.annotations.take().unwrap_or_default();
+
+        let mut maps = [
+            ("job label", labels),
+            ("job annotation", annotations),
+            ("pod label", &mut pod_labels),
+            ("pod annotation", &mut pod_annotations),
+        ];
+
+        let candidates = maps
+            .iter()
+            .flat_map(|(kind, map)| map.keys().map(move |key| (format!("{kind} {key}"), key)))
+            .collect::<Vec<_>>();
+
+        if !candidates.is_empty() {
+            let defaults = candidates
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, (_, key))| propagation.is_propagated(key).then_some(idx))
+                .collect::<Vec<_>>();
+            let options = candidates
+                .into_iter()
+                .map(|(option, _)| option)
+                .collect::<Vec<_>>();
+
+            let kept = match ui::confirm(question("confirm-review-metadata"), false)? {
+                true => ui::multi_select(question("select-metadata"), options.clone(), &defaults)?,
+                false => defaults.iter().map(|idx| options[*idx].clone()).collect(),
+            };
+
+            let skipped = options
+                .iter()
+                .filter(|option| !kept.contains(option))
+                .cloned()
+                .collect::<Vec<_>>();
+            if !skipped.is_empty() {
+                println!(
+                    "{}",
+                    tr_args("metadata-not-copied", &[("keys", skipped.join(", "))])
+                );
+            }
+
+            for (kind, map) in maps.iter_mut() {
+                map.retain(|key, _| kept.contains(&format!("{kind} {key}")));
+            }
+        }
+
+        pod_meta.labels = (!pod_labels.is_empty()).then_some(pod_labels);
+        pod_meta.annotations = (!pod_annotations.is_empty()).then_some(pod_annotations);
+
+        Ok(())
+    }
+
+    /// Display the native sidecars of the pod and let the user select the ones to keep
+    ///
+    /// # Arguments
+    ///
+    /// * `job_spec` - &mut JobSpec
+    fn prompt_sidecars(&self, job_spec: &mut JobSpec) -> Result<()> {
+        let Some(pod_spec) = job_spec.template.spec.as_mut() else {
+            return Ok(());
+        };
+
+        let sidecars = spec::sidecars(pod_spec)
+            .map(|container| container.name.clone())
+            .collect::<Vec<_>>();
+        if sidecars.is_empty() {
+            return Ok(());
+        }
+
+        let names = sidecars.join(", ").truecolor(COLOR.0, COLOR.1, COLOR.2);
+        println!(
+            "{}",
+            tr_args("native-sidecars", &[("names", names.to_string())])
+        );
+
+        let defaults = (0..sidecars.len()).collect::<Vec<_>>();
+        let kept = ui::multi_select(question("select-sidecars"), sidecars.clone(), &defaults)?;
+
+        if let Some(init_containers) = pod_spec.init_containers.as_mut() {
+            init_containers
+                .retain(|container| !spec::is_sidecar(container) || kept.contains(&container.name));
+        }
+
+        let removed = sidecars
+            .iter()
+            .filter(|name| !kept.contains(name))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !removed.is_empty() {
+            println!(
+                "{}",
+                tr_args("removed-sidecars", &[("names", removed.join(", "))])
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Offer to add the securityContext fields required by the restricted Pod Security profile
+    ///
+    /// # Arguments
+    ///
+    /// * `job_spec` - &mut JobSpec
+    fn prompt_restricted(&self, job_spec: &mut JobSpec) -> Result<()> {
+        let Some(pod_spec) = job_spec.template.spec.as_mut() else {
+            return Ok(());
+        };
+
+        // Changes are computed on a copy so that the user can refuse them
+        let mut restricted = pod_spec.clone();
+        let changes = security::restrict(&mut restricted);
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        println!("{}", tr("restricted-missing-fields"));
+        for change in &changes {
+            println!("  - {change}");
+        }
+
+        if ui::confirm(question("confirm-restricted"), true)? {
+            *pod_spec = restricted;
+        }
+
+        Ok(())
+    }
+
+    /// Warn about the features of the job which aren't supported by the API server. The features that the job can
+    /// run without are removed, a confirmation is asked for the other ones
+    ///
+    /// # Arguments
+    ///
+    /// * `job_spec` - &mut JobSpec
+    /// * `version` - Version
+    fn check_version_skew(&self, job_spec: &mut JobSpec, version: Version) -> Result<()> {
+        let skews = compat::downgrade(job_spec, version);
+        for skew in &skews {
+            let key = match skew.removed {
+                true => "version-skew-removed",
+                false => "version-skew",
+            };
+            let warning = tr_args(
+                key,
+                &[
+                    ("feature", skew.feature.to_string()),
+                    ("since", skew.since.to_string()),
+                    ("version", version.to_string()),
+                ],
+            );
+            println!("{}", warning.bright_red().bold());
+        }
+
+        if skews.iter().any(|skew| !skew.removed)
+            && !self.confirm_destructive(question("confirm-version-skew"))?
+        {
+            return Err(anyhow!(tr_args(
+                "version-skew-refused",
+                &[("version", version.to_string())]
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Write the JUnit XML report of the job with its duration and the failures of its pods
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `name` - &str
+    /// * `wait_err` - Option<String>
+    /// * `path` - &Path
+    async fn write_junit<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        name: &str,
+        wait_err: Option<String>,
+        path: &Path,
+    ) -> Result<()> {
+        let job: Job = kube_handler.get_object(name).await?;
+        let run = job::job_run(&job, Timestamp::now());
+
+        let failure = match (run.status, wait_err) {
+            (JobStatus::Succeeded, _) => None,
+            (status, wait_err) => {
+                // A failed job never completes, the wait only times out
+                let mut lines = match (&status, wait_err) {
+                    (JobStatus::Failed(_), _) | (_, None) => vec![status.to_string()],
+                    (_, Some(err)) => vec![err],
+                };
+                for pod in kube_handler.get_job_pods(name).await? {
+                    for failure in job::pod_failures(&pod) {
+                        lines.push(format!(
+                            "pod {}: {failure}",
+                            pod.metadata.name.as_deref().unwrap_or_default()
+                        ));
+                    }
+                }
+
+                Some(lines)
+            }
+        };
+
+        let report = junit::report(&[junit::TestCase {
+            namespace: self.namespace.clone(),
+            name: name.to_string(),
+            duration: run.duration,
+            failure,
+        }]);
+        fs::write(path, report).map_err(|err| {
+            anyhow!(tr_args(
+                "write-junit-failed",
+                &[
+                    ("path", path.display().to_string()),
+                    ("error", err.to_string())
+                ]
+            ))
+        })
+    }
+
+    /// Wait until the given run then resume the suspended job
+    ///
+    /// # Arguments
+    ///
+    /// * `kube_handler` - &KubeHandler<S>
+    /// * `job` - Job
+    /// * `run` - &Zoned
+    async fn resume_at<S: AsRef<str>>(
+        &self,
+        kube_handler: &KubeHandler<S>,
+        job: Job,
+        run: &Zoned,
+    ) -> Result<Job> {
+        let name = job.metadata.name.unwrap_or_default();
+        println!(
+            "{}",
+            tr_args(
+                "created-suspended",
+                &[
+                    (
+                        "name",
+                        name.truecolor(COLOR.0, COLOR.1, COLOR.2).to_string()
+                    ),
+                    ("run", schedule::format_run(run, &TimeZone::system())),
+                ]
+            )
+        );
+
+        // A run which is already in the past resumes the job right away
+        let delay = Duration::try_from(run.timestamp().duration_since(Timestamp::now()))
+            .unwrap_or_default();
+        tokio::time::sleep(delay).await;
+
+        kube_handler.set_job_suspend(&name, false).await
+    }
+
+    /// Ask the user to confirm a destructive operation. The confirmation is skipped when the --force or the --yes
+    /// option is used
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - Question
+    fn confirm_destructive(&self, msg: Question) -> Result<bool> {
+        if self.force {
+            log_forced(FORCE_OPTION, msg.text());
+            return Ok(true);
+        }
+
+        if self.yes {
+            log_forced(YES_OPTION, msg.text());
+            return Ok(true);
+        }
+
+        ui::confirm(msg, false)
+    }
+
+    /// Check whether the env can be changed. Protected env can only be changed with --force, in which case the decision is logged
+    ///
+    /// # Arguments
+    ///
+    /// * `protected` - &[String]
+    /// * `name` - &str
+    fn can_change_env(&self, protected: &[String], name: &str) -> bool {
+        if !protected.iter().any(|p| p == name) {
+            return true;
+        }
+
+        if self.force {
+            log_forced(
+                FORCE_OPTION,
+                &tr_args("forced-change-env", &[("name", name.to_string())]),
+            );
+        }
+
+        self.force
+    }
+
+    // Prompt the user to choose the literal environment variables of the containers to update then their values.
+    // The literals to replace by a reference to a Secret key are returned (index of the container and name of the env)
+    fn prompt_user_env(
+        &self,
+        envs: &mut [ContainerEnv],
+        config_maps: &BTreeMap<String, BTreeMap<String, String>>,
+        secrets: Option<&BTreeMap<String, BTreeMap<String, String>>>,
+        protected: &[String],
+    ) -> Result<Vec<(usize, String)>> {
+        // Display the values of the env coming from a ConfigMap or a Secret and the sources of the downward API env
+        for container in envs.iter() {
+            // The keys loaded with envFrom are edited with their source
+            for env_from in &container.env_from {
+                println!(
+                    "{}",
+                    tr_args(
+                        "env-from-source",
+                        &[
+                            ("source", env_from.to_string()),
+                            ("container", container.label())
+                        ]
+                    )
+                );
+            }
+
+            for (name, kind) in &container.envs {
+                let source = match kind {
+                    EnvKind::ConfigMap(source) | EnvKind::Secret(source) => source,
+                    // The value of the downward API env is only known once the pod is scheduled
+                    EnvKind::FieldRef(_) | EnvKind::ResourceFieldRef(_) => {
+                        let colored = name.truecolor(COLOR.0, COLOR.1, COLOR.2).to_string();
+                        println!(
+                            "{}",
+                            tr_args(
+                                "env-downward",
+                                &[("name", colored), ("source", kind.to_string())]
+                            )
+                        );
+                        continue;
+                    }
+                    EnvKind::Literal(_) => continue,
+                };
+
+                if let Some(selector) = &source.config_map_key_ref {
+                    let value = config_maps
+                        .get(&selector.name)
+                        .and_then(|data| data.get(&selector.key))
+                        .map_or(tr("env-value-not-found"), String::clone);
+
+                    println!(
+                        "{}",
+                        tr_args(
+                            "env-config-map-value",
+                            &[
+                                (
+                                    "name",
+                                    name.truecolor(COLOR.0, COLOR.1, COLOR.2).to_string()
+                                ),
+                                ("reference", format!("{}/{}", selector.name, selector.key)),
+                                ("value", value),
+                            ]
+                        )
+                    );
+                }
+
+                // Secret values are hidden unless the user explicitly asked to show them
+                if let Some(selector) = &source.secret_key_ref {
+                    let value = match secrets {
+                        Some(secrets) => secrets
+                            .get(&selector.name)
+                            .and_then(|data| data.get(&selector.key))
+                            .map_or(tr("env-value-not-found"), String::clone),
+                        None => SECRET_MASK.to_string(),
+                    };
+
+                    println!(
+                        "{}",
+                        tr_args(
+                            "env-secret-value",
+                            &[
+                                (
+                                    "name",
+                                    name.truecolor(COLOR.0, COLOR.1, COLOR.2).to_string()
+                                ),
+                                ("reference", format!("{}/{}", selector.name, selector.key)),
+                                ("value", value),
+                            ]
+                        )
+                    );
+                }
+            }
+        }
+
+        // The literals looking sensitive are masked unless the user asked to display the Secrets
+        let redact = secrets.is_none();
+
+        // The env referencing a ConfigMap key can be replaced by a literal, their current value is the one of the key
+        let editables = envs
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, container)| {
+                container.envs.iter().filter_map(move |(name, kind)| {
+                    editable_value(kind, config_maps).map(|value| (idx, name.to_owned(), value))
+                })
+            })
+            .filter(|(_, name, _)| {
+                let editable = self.can_change_env(protected, name);
+                if !editable {
+                    let colored = name.truecolor(COLOR.0, COLOR.1, COLOR.2).to_string();
+                    println!("{}", tr_args("env-protected", &[("name", colored)]));
+                }
+
+                editable
+            })
+            .collect::<Vec<_>>();
+
+        if editables.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        loop {
+            // Only the env chosen by the user are prompted
+            let chosen = select_envs(envs, &editables, redact)?;
+
+            // The chosen literals can reference a Secret key instead of being typed as plaintext
+            let literals = chosen
+                .iter()
+                .filter(|(idx, name, _)| {
+                    matches!(envs[*idx].envs.get(name), Some(EnvKind::Literal(_)))
+                })
+                .map(|editable| env_option(envs, editable, redact))
+                .collect::<Vec<_>>();
+            let referenced = match literals.is_empty() {
+                true => Vec::new(),
+                false => match ui::multi_select(question("select-secret-envs"), literals, &[]) {
+                    Err(err) if ui::is_back(&err) => continue,
+                    res => res?,
+                },
+            };
+
+            let (references, chosen): (Vec<_>, Vec<_>) = chosen
+                .into_iter()
+                .partition(|editable| referenced.contains(&env_option(envs, editable, redact)));
+
+            // Going back on the first env returns to the list of the env
+            match self.prompt_env_values(envs, &chosen, redact) {
+                Err(err) if ui::is_back(&err) => continue,
+                res => {
+                    return res.map(|_| {
+                        references
+                            .into_iter()
+                            .map(|(idx, name, _)| (idx, name))
+                            .collect()
+                    });
+                }
+            }
+        }
+    }
+
+    /// Prompt the new value of the env chosen by the user. An env referencing a ConfigMap key is replaced by a
+    /// literal when its value is changed. Going back returns to the previous variable
+    ///
+    /// # Arguments
+    ///
+    /// * `envs` - &mut [ContainerEnv]
+    /// * `editables` - &[(usize, String, String)] (index of the container, name and current value of the env)
+    /// * `redact` - bool (whether the literals looking sensitive are typed as a hidden input)
+    fn prompt_env_values(
+        &self,
+        envs: &mut [ContainerEnv],
+        editables: &[(usize, String, String)],
+        redact: bool,
+    ) -> Result<()> {
+        let mut pos = 0;
+        while let Some((idx, name, current)) = editables.get(pos) {
+            // The env of the sidecars and of the init containers is told apart from the env of the containers
+            let colored = name.truecolor(COLOR.0, COLOR.1, COLOR.2).to_string();
+            let container = &envs[*idx];
+            let kind = container.envs.get(name);
+            let secret = matches!(kind, Some(EnvKind::Secret(_)));
+            let sensitive = redact
+                && matches!(kind, Some(EnvKind::Literal(_)))
+                && redact::is_sensitive(name, current);
+            let reference = match kind {
+                Some(EnvKind::Literal(_)) | None => None,
+                Some(kind) => Some(kind.to_string()),
+            };
+            let title = match (reference, container.init, container.sidecar) {
+                _ if sensitive => question_args("env-title-sensitive", &[("name", colored)]),
+                (Some(reference), _, _) if secret => question_args(
+                    "env-title-secret",
+                    &[("name", colored), ("reference", reference)],
+                ),
+                (Some(reference), _, _) => question_args(
+                    "env-title-reference",
+                    &[("name", colored), ("reference", reference)],
+                ),
+                (_, true, _) => question_args(
+                    "env-title-init",
+                    &[("name", colored), ("init", container.name.clone())],
+                ),
+                (_, _, true) => question_args(
+                    "env-title-sidecar",
+                    &[("name", colored), ("sidecar", container.name.clone())],
+                ),
+                _ => question_args("env-title", &[("name", colored)]),
+            };
+            // The answer is typed right after the title
+            let title = title.suffixed(" ");
+
+            // The value of a Secret or looking sensitive is typed without being displayed, an empty answer keeps the
+            // current value. The multi-line values are edited in the editor of the user
+            let hidden = secret || sensitive;
+            let answer = match (hidden, current.contains('\n')) {
+                (true, _) => ui::password(title),
+                (false, true) => ui::editor(title, current),
+                (false, false) => ui::text(title, Some(current)),
+            };
+
+            match answer {
+                Ok(new_value) => {
+                    // The reference is kept when the value of the key isn't changed
+                    let referenced =
+                        !matches!(envs[*idx].envs.get(name), Some(EnvKind::Literal(_)));
+                    let unchanged = match hidden {
+                        true => new_value.is_empty(),
+                        false => new_value == *current,
+                    };
+
+                    // Only the typed values are expanded, the value of the source is kept as is
+                    let new_value = match unchanged {
+                        true => current.clone(),
+                        false => match self.expand_env(new_value) {
+                            Ok(value) => value,
+                            Err(err) => {
+                                eprintln!("{err}");
+                                continue;
+                            }
+                        },
+                    };
+                    if !referenced || !unchanged {
+                        envs[*idx]
+                            .envs
+                            .insert(name.to_owned(), EnvKind::Literal(new_value));
+                    }
+                    pos += 1;
+                }
+                Err(err) if ui::is_back(&err) && pos > 0 => pos -= 1,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(())
+    }
 
-                let list = match self.deployment {
-                    true => kube_handler.list::<Deployment>().await?,
-                    false => kube_handler.list::<CronJob>().await?,
-                };
+    /// Ask the user which value should be kept for the env names which are defined multiple times in a container
+    ///
+    /// # Arguments
+    ///
+    /// * `envs` - &mut [ContainerEnv]
+    fn prompt_duplicated_env(&self, envs: &mut [ContainerEnv]) -> Result<()> {
+        // The value kept by Kubernetes is used as there is no one to choose or the env is kept as is
+        if ui::is_non_interactive() || self.keep_env {
+            return Ok(());
+        }
 
-                // Stop the spinner after getting the list
-                spinner.stop();
+        for container in envs.iter_mut() {
+            for (name, values) in &container.duplicates {
+                println!(
+                    "{}",
+                    tr_args(
+                        "env-duplicated",
+                        &[
+                            (
+                                "name",
+                                name.truecolor(COLOR.0, COLOR.1, COLOR.2).to_string()
+                            ),
+                            ("count", values.len().to_string()),
+                            ("container", container.name.clone()),
+                        ]
+                    )
+                );
 
-                ui::select(
-                    "Select the cronjob that you want to use as a base of the job".to_string(),
-                    list,
-                )?
-            }
-        };
+                let options = values
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, value)| format!("{}) {value}", idx + 1))
+                    .collect::<Vec<_>>();
 
-        // Check if the targeted name already exist in the cluster
-        let target_job_name = match &self.target_name {
-            Some(name) => format!("{}-manual", name),
-            None => {
-                println!("Will use the name of the target job to create the job");
-                format!("{}-manual", name)
-            }
-        };
+                let answer = ui::select(
+                    question_args("select-env-value", &[("name", name.to_owned())]),
+                    options.clone(),
+                )?;
 
-        if kube_handler
-            .get_object::<Job, _>(&target_job_name)
-            .await
-            .is_ok()
-        {
-            match ui::confirm(
-                "An job with the same name already exist. Do you want to delete this job",
-                false,
-            )? {
-                true => kube_handler.delete_object(&target_job_name).await?,
-                false => {
-                    return Err(anyhow!(
-                        "Job with the same name already exist in the cluster"
-                    ));
+                if let Some(value) = options
+                    .iter()
+                    .position(|option| *option == answer)
+                    .and_then(|idx| values.get(idx))
+                {
+                    container.envs.insert(name.to_owned(), value.clone());
                 }
             }
         }
 
-        // Get the job details and stop the spinner if it exists
-        let mut object_spinner = SpinnerWrapper::new("Getting object details...");
+        Ok(())
+    }
 
-        let job_tmpl_spec = match self.deployment {
-            true => {
-                kube_handler
-                    .get_spec_for_object::<_, Deployment>(name)
-                    .await?
-            }
-            false => kube_handler.get_spec_for_object::<_, CronJob>(name).await?,
-        };
+    /// Set the env of the --env-file files, the env given with --env then the ones read with --env-from-file. The env
+    /// is set in the targeted container, otherwise in the containers defining it or added to the first container
+    ///
+    /// # Arguments
+    ///
+    /// * `envs` - &mut [ContainerEnv]
+    /// * `protected` - &[String]
+    fn set_flag_envs(&self, envs: &mut [ContainerEnv], protected: &[String]) -> Result<()> {
+        if let Some(name) = &self.container
+            && !envs.iter().any(|container| container.name == *name)
+        {
+            return Err(anyhow!(tr_args(
+                "container-not-found",
+                &[("name", name.clone())]
+            )));
+        }
 
-        // Stop the spinner after getting the job details
-        object_spinner.stop();
+        let mut flags = Vec::new();
+        for path in &self.env_files {
+            let contents = fs::read_to_string(path).map_err(|err| {
+                anyhow!(tr_args(
+                    "read-env-file-failed",
+                    &[
+                        ("path", path.display().to_string()),
+                        ("error", err.to_string())
+                    ]
+                ))
+            })?;
+            let envs = env::parse_env_file(&contents, self.raw_env).map_err(|err| {
+                anyhow!(tr_args(
+                    "invalid-env-file",
+                    &[
+                        ("path", path.display().to_string()),
+                        ("error", err.to_string())
+                    ]
+                ))
+            })?;
+            flags.extend(envs);
+        }
+        for input in &self.envs {
+            flags.push(env::parse_env(input, self.raw_env)?);
+        }
 
-        // Get the environment variable from the job spec
-        let Some(mut job_spec) = job_tmpl_spec.spec else {
-            return Err(anyhow!("Unable to get the job template spec"));
-        };
+        // The contents of the files read with --env-from-file aren't expanded
+        let mut flags = flags
+            .into_iter()
+            .map(|(key, value)| match self.expand_env(value) {
+                Ok(value) => Ok((key, value)),
+                Err(err) => Err(anyhow!(tr_args(
+                    "expand-env-failed",
+                    &[("name", key), ("error", err.to_string())]
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for input in &self.env_from_files {
+            flags.push(env::read_env_from_file(input)?);
+        }
 
-        let mut envs = job_spec.get_env()?;
+        for (key, value) in flags {
+            env::validate_name(&key)?;
+            if !self.can_change_env(protected, &key) {
+                return Err(anyhow!(tr_args("protected-env-flag", &[("name", key)])));
+            }
 
-        // Show the user the environment variable and let the user confirm the value to output
-        self.prompt_user_env(&mut envs)?;
+            let defined = envs
+                .iter()
+                .any(|container| container.envs.contains_key(&key));
+            for (idx, container) in envs.iter_mut().enumerate() {
+                let targeted = match &self.container {
+                    Some(name) => container.name == *name,
+                    None => container.envs.contains_key(&key) || (!defined && idx == 0),
+                };
 
-        if ui::confirm("Do you want to add additional env ?", false)? {
-            self.process_prompt_additional_env(&mut envs)?;
+                if targeted {
+                    container
+                        .envs
+                        .insert(key.clone(), EnvKind::Literal(value.clone()));
+                }
+            }
         }
 
-        // Rebuild the job spec with the updated environment variables
-        job_spec.rebuild_env(&mut envs)?;
+        Ok(())
+    }
 
-        // Upgrade the resources limits if needed
-        if ui::confirm("Do you want to update the resources limits ?", false)? {
-            let user_asked_resources = self.process_resources_prompt(&envs)?;
-            job_spec.update_resources(user_asked_resources)?;
+    /// Expand the ${VAR} of the value typed or given by the user with the local environment, unless --no-expand is used
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - String
+    fn expand_env(&self, value: String) -> Result<String> {
+        match self.no_expand {
+            true => Ok(value),
+            false => env::expand(&value, |name| std::env::var(name).ok()),
         }
+    }
 
-        // Apply the job spec and display the output
-        let mut apply_spinner = match self.dry_run {
-            true => SpinnerWrapper::new("Running a dry-run job..."),
-            false => SpinnerWrapper::new("Applying job..."),
+    /// Build the resources given with --memory and --cpu. They're set on the targeted container, otherwise on the
+    /// first container
+    ///
+    /// # Arguments
+    ///
+    /// * `envs` - &[ContainerEnv]
+    fn flag_resources(&self, envs: &[ContainerEnv]) -> Result<SpecResources> {
+        let (Some(memory), Some(cpu)) = (&self.memory, &self.cpu) else {
+            return Err(anyhow!(tr("missing-resources-flags")));
         };
 
-        let job = kube_handler
-            .build_manual_job(&target_job_name, job_spec, self.backoff_limit)?
-            .apply_manual_job()
-            .await?;
+        let container_name = match &self.container {
+            Some(name) => name.to_owned(),
+            None => envs
+                .first()
+                .map(|container| container.name.clone())
+                .ok_or_else(|| anyhow!("Unable to get the targeted container"))?,
+        };
 
-        let output = kube_handler
-            .wait_for_job(job, self.wait)
-            .await
-            .and_then(|job| {
-                // stop the spinner before displaying the output
-                apply_spinner.stop();
+        Ok(SpecResources {
+            memory: Quantity(memory.to_owned()),
+            cpu: Quantity(cpu.to_owned()),
+            container_name,
+        })
+    }
 
-                kube_handler.display_spec(job)
-            })
-            .inspect_err(|_| {
-                // stop the spinner before returning an error
-                apply_spinner.stop();
-            })?;
+    /// Prompt the user to select the env to remove from each container. The env previously removed are selected by
+    /// default and the protected env can only be removed with --force
+    ///
+    /// # Arguments
+    ///
+    /// * `envs` - &mut [ContainerEnv]
+    /// * `protected` - &[String]
+    fn prompt_remove_env(&self, envs: &mut [ContainerEnv], protected: &[String]) -> Result<()> {
+        for container in envs.iter_mut() {
+            let names = container
+                .envs
+                .keys()
+                .filter(|name| self.force || !protected.contains(name))
+                .cloned()
+                .collect::<Vec<_>>();
+            if names.is_empty() {
+                continue;
+            }
 
-        if let (Some(output_path), Some(contents)) = (&self.dry_run_output_path, output) {
-            fs::write(PathBuf::from(output_path), contents)?;
+            let defaults = names
+                .iter()
+                .enumerate()
+                .filter(|(_, name)| container.removed.contains(*name))
+                .map(|(idx, _)| idx)
+                .collect::<Vec<_>>();
+            let selected = ui::multi_select(
+                question_args("select-remove-envs", &[("container", container.label())]),
+                names,
+                &defaults,
+            )?;
+
+            for name in selected.iter().filter(|name| protected.contains(name)) {
+                log_forced(
+                    FORCE_OPTION,
+                    &tr_args("forced-remove-env", &[("name", name.clone())]),
+                );
+            }
+            container.removed = selected.into_iter().collect();
         }
 
         Ok(())
     }
 
-    // Prompt the user to add additional environment variables to the containers
-    fn prompt_user_env(&self, envs: &mut Vec<ContainerEnv>) -> Result<()> {
-        for container in envs {
-            for (name, kind) in &mut container.envs {
-                if let EnvKind::Literal(literal) = kind {
-                    let new_value = ui::text(
-                        &format!("Env for {}: ", name.truecolor(COLOR.0, COLOR.1, COLOR.2)),
-                        Some(literal),
-                    )?;
-                    *kind = EnvKind::Literal(new_value);
+    /// Ask the additional env written with the format KEY=VALUE
+    ///
+    /// # Arguments
+    ///
+    /// * `protected` - &[String]
+    fn prompt_literal_env(&self, protected: &[String]) -> Result<(String, String)> {
+        let raw = self.raw_env;
+        let force = self.force;
+        let no_expand = self.no_expand;
+        let protected_names = protected.to_vec();
+        let res = ui::text_with_validator(question("input-additional-env"), move |s: &str| {
+            match parse_additional_env(s, raw) {
+                Ok((key, _)) if !force && protected_names.contains(&key) => {
+                    Ok(Validation::Invalid(
+                        tr_args("protected-env-flag", &[("name", key.to_string())]).into(),
+                    ))
                 }
+                Ok((_, value)) if !no_expand => {
+                    match env::expand(&value, |name| std::env::var(name).ok()) {
+                        Ok(_) => Ok(Validation::Valid),
+                        Err(err) => Ok(Validation::Invalid(err.to_string().into())),
+                    }
+                }
+                Ok(_) => Ok(Validation::Valid),
+                Err(err) => Ok(Validation::Invalid(err.to_string().into())),
             }
-        }
+        })?;
 
-        Ok(())
+        let (key, value) = parse_additional_env(&res, raw)?;
+        Ok((key, self.expand_env(value)?))
+    }
+
+    /// Ask the env written with the format KEY=PATH whose value is the contents of the local file
+    ///
+    /// # Arguments
+    ///
+    /// * `protected` - &[String]
+    fn prompt_file_env(&self, protected: &[String]) -> Result<(String, String)> {
+        let force = self.force;
+        let protected_names = protected.to_vec();
+        let res = ui::text_with_validator(question("input-env-from-file"), move |s: &str| {
+            match env::read_env_from_file(s) {
+                Ok((key, _)) if !force && protected_names.contains(&key) => {
+                    Ok(Validation::Invalid(
+                        tr_args("protected-env-flag", &[("name", key.to_string())]).into(),
+                    ))
+                }
+                Ok(_) => Ok(Validation::Valid),
+                Err(err) => Ok(Validation::Invalid(err.to_string().into())),
+            }
+        })?;
+
+        env::read_env_from_file(&res)
+    }
+
+    /// Ask the field of the pod or the resource of the container exposed with the downward API, then the name of
+    /// the env
+    ///
+    /// # Arguments
+    ///
+    /// * `protected` - &[String]
+    /// * `title` - &str (key of the title of the list)
+    /// * `sources` - &[&str] (fields or resources which can be exposed)
+    fn prompt_downward_env(
+        &self,
+        protected: &[String],
+        title: &str,
+        sources: &[&str],
+    ) -> Result<(String, String)> {
+        let source = ui::select(
+            question(title),
+            sources.iter().map(ToString::to_string).collect(),
+        )?;
+
+        let force = self.force;
+        let protected_names = protected.to_vec();
+        let key = ui::text_with_validator(
+            question_args("input-downward-env", &[("source", source.clone())]),
+            move |s: &str| match env::validate_name(s.trim()).map(|_| s.trim()) {
+                Ok(key) if !force && protected_names.iter().any(|name| name == key) => {
+                    Ok(Validation::Invalid(
+                        tr_args("protected-env-flag", &[("name", key.to_string())]).into(),
+                    ))
+                }
+                Ok(_) => Ok(Validation::Valid),
+                Err(err) => Ok(Validation::Invalid(err.to_string().into())),
+            },
+        )?;
+
+        Ok((key.trim().to_string(), source))
     }
 
     /// Add additional environment variables to the list of existing environment variables present in the envs slice
@@ -214,54 +3791,253 @@ impl Cli {
     /// # Arguments
     ///
     /// * `envs` - &mut [Containers]
-    fn process_prompt_additional_env(&self, envs: &mut [ContainerEnv]) -> Result<()> {
+    /// * `protected` - &[String] (names of the env which can only be added with --force)
+    fn process_prompt_additional_env(
+        &self,
+        envs: &mut [ContainerEnv],
+        protected: &[String],
+    ) -> Result<()> {
         let mut ask_user_additional_env = true;
 
         // Select the container which will be used to add the additional environment variables
-        let containers_name = envs.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
-        let answer = ui::select(
-            "Select the container to add the additional environment variable".to_string(),
-            containers_name,
-        )?;
+        let containers_name = envs.iter().map(|c| c.label()).collect::<Vec<_>>();
+        let answer = ui::select(question("select-env-container"), containers_name)?;
 
         let tgt_container = envs
             .iter_mut()
-            .rfind(|c| c.name == answer)
+            .rfind(|c| c.label() == answer)
             .ok_or_else(|| anyhow!("Unable to found the targeted container"))?;
 
         while ask_user_additional_env {
-            if let Ok(res) =
-                ui::text_with_validator("Input the additional env separate with a =", |s: &str| {
-                    let v = s.split(SPLIT_ENV_OPERATOR).collect::<Vec<_>>();
-                    match v.len() != 2 {
-                        true => Ok(Validation::Invalid(
-                            "Environment variable should respect the format: ENV_NAME=VALUE".into(),
-                        )),
-                        false => Ok(Validation::Valid),
+            // The env is either a literal, the contents of a local file or exposes a field of the pod or a resource of
+            // the container
+            let kinds = ADDITIONAL_ENV_KINDS
+                .iter()
+                .map(|kind| tr(kind))
+                .collect::<Vec<_>>();
+            let answer = ui::select(question("select-additional-env-kind"), kinds)?;
+            let res = match ADDITIONAL_ENV_KINDS
+                .iter()
+                .position(|kind| tr(kind) == answer)
+            {
+                Some(1) => self
+                    .prompt_file_env(protected)
+                    .map(|(key, contents)| (key, EnvKind::Literal(contents))),
+                Some(2) => self
+                    .prompt_downward_env(protected, "select-env-field", &DOWNWARD_FIELDS)
+                    .map(|(key, field)| (key, EnvKind::field_ref(field))),
+                Some(3) => self
+                    .prompt_downward_env(protected, "select-env-resource", &DOWNWARD_RESOURCES)
+                    .map(|(key, resource)| (key, EnvKind::resource_field_ref(resource))),
+                _ => self
+                    .prompt_literal_env(protected)
+                    .map(|(key, value)| (key, EnvKind::Literal(value))),
+            };
+
+            match res {
+                // Going back asks again the kind of the env
+                Err(err) if ui::is_back(&err) => continue,
+                Err(err) => return Err(err),
+                Ok((key, kind)) => {
+                    // An existing env isn't shadowed silently, the env is asked again when the user declines
+                    if let Some(current) = tgt_container.envs.get(&key)
+                        && !tgt_container.removed.contains(&key)
+                    {
+                        let current = match current {
+                            EnvKind::Literal(value) => preview(value),
+                            EnvKind::Secret(_) => SECRET_MASK.to_string(),
+                            kind => kind.to_string(),
+                        };
+                        let args = [("name", key.clone()), ("current", current)];
+                        if !ui::confirm(question_args("confirm-overwrite-env", &args), false)? {
+                            continue;
+                        }
+                    }
+
+                    if protected.contains(&key) {
+                        log_forced(
+                            FORCE_OPTION,
+                            &tr_args("forced-add-env", &[("name", key.clone())]),
+                        );
+                    }
+
+                    // Push env to the containers envs. An env added back is no longer removed
+                    tgt_container.removed.remove(&key);
+                    tgt_container.envs.insert(key, kind);
+
+                    // Asking to the user whether it wants to add additional env
+                    if !ui::confirm(question("confirm-more-env"), false)? {
+                        ask_user_additional_env = false;
                     }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Let the user edit the envFrom of a container: load the keys of a ConfigMap or of a Secret of the namespace,
+    /// remove a source or set the prefix of a source. The actions are asked until the user is done. The name of
+    /// the ConfigMap or of the Secret is typed when they can't be listed
+    ///
+    /// # Arguments
+    ///
+    /// * `envs` - &mut [ContainerEnv]
+    /// * `config_maps` - Result<Vec<String>>
+    /// * `secrets` - Result<Vec<String>>
+    fn prompt_env_from(
+        &self,
+        envs: &mut [ContainerEnv],
+        config_maps: Result<Vec<String>>,
+        secrets: Result<Vec<String>>,
+    ) -> Result<()> {
+        let containers_name = envs.iter().map(|c| c.label()).collect::<Vec<_>>();
+        let answer = ui::select(question("select-env-container"), containers_name)?;
+        let container = envs
+            .iter_mut()
+            .find(|c| c.label() == answer)
+            .ok_or_else(|| anyhow!("Unable to found the targeted container"))?;
+
+        let config_maps = config_maps.unwrap_or_else(|err| {
+            eprintln!(
+                "{}",
+                tr_args("list-config-maps-failed", &[("error", err.to_string())])
+            );
+            Vec::new()
+        });
+        let secrets = secrets.unwrap_or_else(|err| {
+            eprintln!(
+                "{}",
+                tr_args("list-secrets-failed", &[("error", err.to_string())])
+            );
+            Vec::new()
+        });
+
+        loop {
+            let current = container
+                .env_from
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            let args = [
+                ("container", container.name.clone()),
+                ("current", current.join(", ")),
+            ];
+            println!("{}", tr_args("current-env-from", &args));
+
+            // A source can only be removed or prefixed when the container has some
+            let actions = ENV_FROM_ACTIONS
+                .iter()
+                .filter(|action| {
+                    !current.is_empty()
+                        || !matches!(**action, "env-from-remove" | "env-from-prefix")
                 })
+                .map(|action| tr(action))
+                .collect::<Vec<_>>();
+            let answer = ui::select(question("select-env-from-action"), actions)?;
+
+            let res = match ENV_FROM_ACTIONS
+                .iter()
+                .position(|action| tr(action) == answer)
             {
-                let properties = res.split(SPLIT_ENV_OPERATOR).collect::<Vec<_>>();
-                let (key, value) = (
-                    properties
-                        .first()
-                        .ok_or_else(|| anyhow!("Expect to retrieve the key of the env"))?,
-                    properties
-                        .last()
-                        .ok_or_else(|| anyhow!("Expect to retrieve the value of the env"))?,
-                );
+                Some(0) => prompt_env_from_name(EnvFromKind::ConfigMap, &config_maps).map(|name| {
+                    container
+                        .env_from
+                        .push(EnvFrom::new(EnvFromKind::ConfigMap, name))
+                }),
+                Some(1) => prompt_env_from_name(EnvFromKind::Secret, &secrets).map(|name| {
+                    container
+                        .env_from
+                        .push(EnvFrom::new(EnvFromKind::Secret, name))
+                }),
+                Some(2) => ui::select(question("select-env-from"), current.clone()).map(|answer| {
+                    container
+                        .env_from
+                        .retain(|source| source.to_string() != answer)
+                }),
+                Some(3) => {
+                    ui::select(question("select-env-from"), current.clone()).and_then(|answer| {
+                        let Some(env_from) = container
+                            .env_from
+                            .iter_mut()
+                            .find(|source| source.to_string() == answer)
+                        else {
+                            return Ok(());
+                        };
 
-                // Push env to the containers envs
-                tgt_container.envs.insert(
-                    key.to_string(),
-                    EnvKind::Literal(value.to_string().replace(REPLACE_STR, "")),
-                );
+                        let prefix = ui::text(
+                            question("input-env-from-prefix"),
+                            env_from.prefix.as_deref(),
+                        )?;
+                        env_from.prefix = (!prefix.is_empty()).then_some(prefix);
 
-                // Asking to the user whether it wants to add additional env
-                if !ui::confirm("Do you still want to add additional env ?", false)? {
-                    ask_user_additional_env = false;
+                        Ok(())
+                    })
                 }
+                _ => return Ok(()),
             };
+
+            // Going back returns to the actions
+            match res {
+                Err(err) if !ui::is_back(&err) => return Err(err),
+                _ => {}
+            }
+        }
+    }
+
+    /// Let the user set or remove the labels & annotations with the kubectl syntax: `KEY=VALUE` sets the key
+    /// and `KEY-` removes it. An empty answer moves to the next kind
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - &str (job or pods)
+    /// * `labels` - &mut BTreeMap<String, String>
+    /// * `annotations` - &mut BTreeMap<String, String>
+    fn prompt_metadata(
+        &self,
+        target: &str,
+        labels: &mut BTreeMap<String, String>,
+        annotations: &mut BTreeMap<String, String>,
+    ) -> Result<()> {
+        for (kind, map) in [("label", labels), ("annotation", annotations)] {
+            loop {
+                let current = map
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect::<Vec<_>>();
+                let args = [
+                    ("kind", kind.to_string()),
+                    ("target", target.to_string()),
+                    ("current", current.join(", ")),
+                ];
+                println!("{}", tr_args("current-metadata", &args));
+
+                let raw = self.raw_env;
+                let input = ui::text_with_validator(
+                    question_args("input-metadata", &args),
+                    move |s: &str| match s.is_empty() || metadata_removal(s).is_some() {
+                        true => Ok(Validation::Valid),
+                        false => match env::parse_env(s, raw) {
+                            Ok(_) => Ok(Validation::Valid),
+                            Err(err) => Ok(Validation::Invalid(err.to_string().into())),
+                        },
+                    },
+                )?;
+
+                if input.is_empty() {
+                    break;
+                }
+
+                match metadata_removal(&input) {
+                    Some(key) => {
+                        map.remove(key);
+                    }
+                    None => {
+                        let (key, value) = env::parse_env(&input, raw)?;
+                        map.insert(key, value);
+                    }
+                }
+            }
         }
 
         Ok(())
@@ -271,48 +4047,604 @@ impl Cli {
     ///
     /// * `envs` - &[ContainerEnv]
     fn process_resources_prompt(&self, envs: &[ContainerEnv]) -> Result<SpecResources> {
-        let containers_name = envs.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
-        let container = ui::select(
-            "Select the container to add the additional environment variable".to_string(),
-            containers_name,
-        )?;
+        let containers_name = envs.iter().map(|c| c.label()).collect::<Vec<_>>();
 
-        // Memory
-        let memory = ui::text_with_validator("Set the memory limits", |s: &str| {
-            match s.parse::<f64>().is_ok() {
-                true => Ok(Validation::Valid),
-                false => Ok(Validation::Invalid(
-                    "Memory should contains only numbers".into(),
-                )),
-            }
-        })?;
-        let memory_format = ui::select("Select a memory format", DECIMAL_SI.to_vec())?;
-
-        // Cpu
-        let cpu =
-            ui::text_with_validator("Set the cpu limits", |s: &str| match s.parse::<f64>() {
-                Ok(v) => {
-                    if v < 0.001 {
-                        return Ok(Validation::Invalid(
-                            "CPU should be greater >= to 0.001".into(),
-                        ));
+        // Answers in the order of the questions: container, memory, memory format, cpu, cpu format
+        let mut answers: Vec<String> = Vec::new();
+        while answers.len() < RESOURCES_QUESTIONS {
+            let res = match answers.len() {
+                0 => ui::select(question("select-env-container"), containers_name.clone()),
+                // Memory
+                1 => ui::text_with_validator(question("input-memory"), |s: &str| {
+                    match s.parse::<f64>().is_ok() {
+                        true => Ok(Validation::Valid),
+                        false => Ok(Validation::Invalid(tr("invalid-memory").into())),
                     }
+                }),
+                2 => ui::select(question("select-memory-format"), DECIMAL_SI.to_vec())
+                    .map(String::from),
+                // Cpu
+                3 => ui::text_with_validator(question("input-cpu"), |s: &str| {
+                    match s.parse::<f64>() {
+                        Ok(v) => {
+                            if v < 0.001 {
+                                return Ok(Validation::Invalid(tr("invalid-cpu-min").into()));
+                            }
 
-                    Ok(Validation::Valid)
+                            Ok(Validation::Valid)
+                        }
+                        Err(_) => Ok(Validation::Invalid(tr("invalid-cpu").into())),
+                    }
+                }),
+                _ => ui::select(question("select-cpu-format"), CPU.to_vec()).map(|format| {
+                    match format {
+                        "None" => String::new(),
+                        _ => format.to_string(),
+                    }
+                }),
+            };
+
+            match res {
+                Ok(answer) => answers.push(answer),
+                Err(err) if ui::is_back(&err) && !answers.is_empty() => {
+                    answers.pop();
                 }
-                Err(_) => Ok(Validation::Invalid("CPU should contains numbers".into())),
-            })?;
+                Err(err) => return Err(err),
+            }
+        }
 
-        let cpu_format =
-            ui::select("Select a cpu format", CPU.to_vec()).map(|format| match format {
-                "None" => "",
-                _ => format,
-            })?;
+        let [container, memory, memory_format, cpu, cpu_format] =
+            <[String; RESOURCES_QUESTIONS]>::try_from(answers)
+                .map_err(|_| anyhow!("Unable to get the answers of the resources prompts"))?;
 
         Ok(SpecResources {
             memory: Quantity(format!("{memory}{memory_format}")),
             cpu: Quantity(format!("{cpu}{cpu_format}")),
-            container_name: container,
+            container_name: envs
+                .iter()
+                .find(|c| c.label() == container)
+                .map_or(container, |c| c.name.clone()),
         })
     }
 }
+
+/// Log a decision which was forced with the --force or the --yes option
+///
+/// # Arguments
+///
+/// * `option` - &str
+/// * `msg` - &str
+fn log_forced(option: &str, msg: &str) {
+    let prefix = tr_args("forced", &[("option", option.to_string())]);
+    eprintln!("{} {msg}", prefix.bright_yellow().bold());
+}
+
+/// Display the colored unified diff between the original and the modified spec
+///
+/// # Arguments
+///
+/// * `original` - &JobSpec
+/// * `modified` - &JobSpec
+/// * `labels` - (&str, &str) (names of the original & modified spec)
+fn display_diff(original: &JobSpec, modified: &JobSpec, labels: (&str, &str)) -> Result<()> {
+    match diff::unified(original, modified, labels)? {
+        Some(diff) => println!("{}", diff::colorize(&diff)),
+        None => println!(
+            "{}",
+            tr_args(
+                "no-diff",
+                &[
+                    ("original", labels.0.to_string()),
+                    ("modified", labels.1.to_string())
+                ]
+            )
+        ),
+    }
+
+    Ok(())
+}
+
+/// Get None instead of an empty list, as done by the API server for the optional lists
+///
+/// # Arguments
+///
+/// * `values` - Vec<T>
+fn non_empty<T>(values: Vec<T>) -> Option<Vec<T>> {
+    (!values.is_empty()).then_some(values)
+}
+
+/// Get the key to remove when the input uses the kubectl removal syntax (`KEY-`)
+///
+/// # Arguments
+///
+/// * `input` - &str
+fn metadata_removal(input: &str) -> Option<&str> {
+    input
+        .strip_suffix('-')
+        .filter(|key| !key.is_empty() && !key.contains('='))
+}
+
+/// Label of the cronjob in the picker with its schedule and the time zone in which the schedule is evaluated
+///
+/// # Arguments
+///
+/// * `cron_job` - &CronJob
+fn cron_job_label(cron_job: &CronJob) -> String {
+    let name = cron_job.metadata.name.as_deref().unwrap_or_default();
+    match &cron_job.spec {
+        Some(spec) => format!(
+            "{name} ({}, {})",
+            spec.schedule,
+            spec.time_zone.as_deref().unwrap_or("UTC")
+        ),
+        None => name.to_string(),
+    }
+}
+
+/// Parse the additional env written with the format KEY=VALUE and check that its name is valid
+///
+/// # Arguments
+///
+/// * `input` - &str
+/// * `raw` - bool
+fn parse_additional_env(input: &str, raw: bool) -> Result<(String, String)> {
+    let (key, value) = env::parse_env(input, raw)?;
+    env::validate_name(&key)?;
+
+    Ok((key, value))
+}
+
+/// Ask the name of the ConfigMap or of the Secret whose keys are loaded as env. The name is picked from the list
+/// when the objects of the namespace could be listed
+///
+/// # Arguments
+///
+/// * `kind` - EnvFromKind
+/// * `names` - &[String]
+fn prompt_env_from_name(kind: EnvFromKind, names: &[String]) -> Result<String> {
+    let args = [("kind", kind.to_string())];
+    if !names.is_empty() {
+        return ui::select(question_args("select-env-from-name", &args), names.to_vec());
+    }
+
+    prompt_not_empty(question_args("input-env-from-name", &args))
+}
+
+/// Replace the literals by a reference to a Secret key. The Secret is picked from the list when the Secrets of the
+/// namespace could be listed and its key is typed, as the keys can't be known without reading the data of the
+/// Secret. Going back returns to the previous variable
+///
+/// # Arguments
+///
+/// * `envs` - &mut [ContainerEnv]
+/// * `references` - &[(usize, String)] (index of the container and name of the env)
+/// * `secrets` - Result<Vec<String>> (names of the Secrets of the namespace)
+fn prompt_secret_refs(
+    envs: &mut [ContainerEnv],
+    references: &[(usize, String)],
+    secrets: Result<Vec<String>>,
+) -> Result<()> {
+    let secrets = secrets.unwrap_or_else(|err| {
+        eprintln!(
+            "{}",
+            tr_args("list-secrets-failed", &[("error", err.to_string())])
+        );
+        Vec::new()
+    });
+
+    let mut pos = 0;
+    while let Some((idx, name)) = references.get(pos) {
+        let args = [("name", name.clone())];
+        let secret = match secrets.is_empty() {
+            true => prompt_not_empty(question_args("input-secret-ref", &args)),
+            false => ui::select(question_args("select-secret-ref", &args), secrets.clone()),
+        };
+
+        let key = secret.and_then(|secret| {
+            let args = [("name", name.clone()), ("secret", secret.clone())];
+            prompt_not_empty(question_args("input-secret-key", &args)).map(|key| (secret, key))
+        });
+
+        match key {
+            Ok((secret, key)) => {
+                envs[*idx]
+                    .envs
+                    .insert(name.clone(), EnvKind::secret_key(secret, key));
+                pos += 1;
+            }
+            Err(err) if ui::is_back(&err) && pos > 0 => pos -= 1,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(())
+}
+
+/// Ask a value which can't be empty. The value is trimmed
+///
+/// # Arguments
+///
+/// * `title` - Question
+fn prompt_not_empty(title: Question) -> Result<String> {
+    ui::text_with_validator(title, |s: &str| match s.trim().is_empty() {
+        true => Ok(Validation::Invalid(tr("value-empty").into())),
+        false => Ok(Validation::Valid),
+    })
+    .map(|value| value.trim().to_string())
+}
+
+/// Get the current value of the env which can be edited: the literal, or the value of the ConfigMap key that the
+/// env references (empty when the key can't be found). The value of a Secret key is never displayed hence empty.
+/// The downward API env can't be edited
+///
+/// # Arguments
+///
+/// * `kind` - &EnvKind
+/// * `config_maps` - &EnvValues
+fn editable_value(kind: &EnvKind, config_maps: &EnvValues) -> Option<String> {
+    match kind {
+        EnvKind::Literal(literal) => Some(literal.clone()),
+        EnvKind::ConfigMap(source) => source.config_map_key_ref.as_ref().map(|selector| {
+            config_maps
+                .get(&selector.name)
+                .and_then(|data| data.get(&selector.key))
+                .cloned()
+                .unwrap_or_default()
+        }),
+        EnvKind::Secret(source) => source.secret_key_ref.as_ref().map(|_| String::new()),
+        EnvKind::FieldRef(_) | EnvKind::ResourceFieldRef(_) => None,
+    }
+}
+
+/// Select the env to change by pages of ENV_PAGE_SIZE env. The options of a page are only built when the page is
+/// displayed and the env of the next pages can be kept unchanged. Going back returns to the previous page
+///
+/// # Arguments
+///
+/// * `envs` - &[ContainerEnv]
+/// * `editables` - &[(usize, String, String)] (index of the container, name and current value of the env)
+/// * `redact` - bool
+fn select_envs(
+    envs: &[ContainerEnv],
+    editables: &[(usize, String, String)],
+    redact: bool,
+) -> Result<Vec<(usize, String, String)>> {
+    let pages = editables.chunks(ENV_PAGE_SIZE).collect::<Vec<_>>();
+    let mut selected = vec![Vec::new(); pages.len()];
+
+    let mut page = 0;
+    while let Some(editables) = pages.get(page) {
+        let options = editables
+            .iter()
+            .map(|editable| env_option(envs, editable, redact))
+            .collect::<Vec<_>>();
+        let title = match pages.len() {
+            1 => question("select-envs"),
+            total => question_args(
+                "select-envs-page",
+                &[
+                    ("page", (page + 1).to_string()),
+                    ("total", total.to_string()),
+                ],
+            ),
+        };
+
+        let answer = match ui::multi_select(title, options.clone(), &[]) {
+            Err(err) if ui::is_back(&err) && page > 0 => {
+                page -= 1;
+                continue;
+            }
+            res => res?,
+        };
+        selected[page] = editables
+            .iter()
+            .zip(&options)
+            .filter(|(_, option)| answer.contains(option))
+            .map(|(editable, _)| editable.clone())
+            .collect();
+        page += 1;
+
+        let Some(next) = pages.get(page..).filter(|next| !next.is_empty()) else {
+            break;
+        };
+        let remaining = next.iter().map(|editables| editables.len()).sum::<usize>();
+        match ui::confirm(
+            question_args("confirm-next-envs", &[("remaining", remaining.to_string())]),
+            true,
+        ) {
+            Ok(true) => {}
+            // The env selected on the next pages before going back are kept unchanged as well
+            Ok(false) => {
+                selected.truncate(page);
+                break;
+            }
+            Err(err) if ui::is_back(&err) => page -= 1,
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(selected.into_iter().flatten().collect())
+}
+
+/// Get the option of the env in the list of the env to change. The value of the Secrets and of the sensitive
+/// literals is masked and the container is told apart when the pod has several
+///
+/// # Arguments
+///
+/// * `envs` - &[ContainerEnv]
+/// * `editable` - &(usize, String, String) (index of the container, name and current value of the env)
+/// * `redact` - bool
+fn env_option(
+    envs: &[ContainerEnv],
+    (idx, name, value): &(usize, String, String),
+    redact: bool,
+) -> String {
+    let option = match envs[*idx].envs.get(name) {
+        Some(kind @ EnvKind::Secret(_)) => format!("{name}={SECRET_MASK} ({kind})"),
+        Some(kind @ EnvKind::ConfigMap(_)) => format!("{name}={} ({kind})", preview(value)),
+        _ if redact && redact::is_sensitive(name, value) => format!("{name}={SECRET_MASK}"),
+        _ => format!("{name}={}", preview(value)),
+    };
+
+    match envs.len() > 1 {
+        true => format!("{option} [{}]", envs[*idx].label()),
+        false => option,
+    }
+}
+
+/// Shorten the value of the env displayed in the list of the env to change
+///
+/// # Arguments
+///
+/// * `value` - &str
+fn preview(value: &str) -> String {
+    let value = value.lines().next().unwrap_or_default();
+    match value.chars().count() > ENV_VALUE_PREVIEW {
+        true => format!(
+            "{}…",
+            value.chars().take(ENV_VALUE_PREVIEW).collect::<String>()
+        ),
+        false => value.to_string(),
+    }
+}
+
+/// Get the number of seconds between the next two runs of the cronjob
+///
+/// # Arguments
+///
+/// * `cron_job` - &CronJob
+fn schedule_interval(cron_job: &CronJob) -> Option<i64> {
+    cron_schedule(cron_job)?.interval_seconds(Timestamp::now())
+}
+
+/// Get the next scheduled run of the cronjob
+///
+/// # Arguments
+///
+/// * `cron_job` - &CronJob
+fn next_run(cron_job: &CronJob) -> Option<Zoned> {
+    cron_schedule(cron_job)?.next_after(Timestamp::now())
+}
+
+fn cron_schedule(cron_job: &CronJob) -> Option<Schedule> {
+    let spec = cron_job.spec.as_ref()?;
+    Schedule::parse(&spec.schedule, spec.time_zone.as_deref()).ok()
+}
+
+/// Subcommands of the CLI. Without subcommand, a job is dispatched
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Check the kubeconfig, the cluster, the API versions and the permissions needed to dispatch a job
+    Doctor,
+    /// Print the completion script of the shell (e.g. bakkutteh completions zsh > ~/.zfunc/_bakkutteh)
+    Completions {
+        #[arg(value_enum, help = "Shell of the completion script")]
+        shell: Shell,
+    },
+    /// Render the man pages of the CLI and of its subcommands
+    Man {
+        #[arg(
+            long,
+            help = "Directory where the man pages are written. The page of the CLI is printed on stdout when omitted"
+        )]
+        out_dir: Option<PathBuf>,
+    },
+    /// Replace the binary with the latest release after verifying its checksum
+    SelfUpdate {
+        #[arg(
+            long,
+            help = "Install the latest release even when the current version isn't older"
+        )]
+        force: bool,
+    },
+    /// List the jobs dispatched with bakkutteh in the namespace with their status, age and source
+    ListManual,
+    /// Print the logs of the pods of a job, each line prefixed with its pod and container
+    Logs {
+        #[arg(help = "Name of the job (e.g. momo-manual)")]
+        job: String,
+
+        #[arg(short, long, help = "Stream the logs until the containers exit")]
+        follow: bool,
+    },
+    /// Print the status of a job. Exits with an error when the job failed
+    Status {
+        #[arg(help = "Name of the job (e.g. momo-manual)")]
+        job: String,
+
+        #[arg(
+            short,
+            long,
+            help = "Print the transitions of the job and of its pods until the job is finished"
+        )]
+        watch: bool,
+    },
+    /// Describe a job with its conditions, its pods and their last events
+    Describe {
+        #[arg(help = "Name of the job (e.g. momo-manual)")]
+        job: String,
+    },
+    /// Cancel a job dispatched with bakkutteh by deleting it along with its pods
+    Cancel {
+        #[arg(help = "Name of the job (e.g. momo-manual)")]
+        job: String,
+
+        #[arg(long, help = "Wait until the job and its pods are deleted")]
+        wait: bool,
+    },
+    /// Dispatch again a job dispatched with bakkutteh under a new name, without any prompt
+    Rerun {
+        #[arg(help = "Name of the job (e.g. momo-manual)")]
+        job: String,
+
+        #[arg(
+            long,
+            help = "Name of the new job. Generated from the source of the job when omitted (e.g. momo-manual-x7k2p)"
+        )]
+        name: Option<String>,
+    },
+    /// Print the spec hash of a job manifest (e.g. written with --dry-run-output-path), to be compared with the
+    /// bakkutteh.io/spec-hash annotation of the dispatched job
+    Hash {
+        #[arg(help = "Path of the job manifest")]
+        file: PathBuf,
+    },
+    /// Browse the dispatches recorded in the local audit log
+    History {
+        #[arg(short = 'A', long, help = "List the dispatches of every namespace")]
+        all_namespaces: bool,
+
+        #[arg(long, help = "Only list the dispatches of this source (e.g. momo)")]
+        source: Option<String>,
+
+        #[arg(
+            long,
+            help = "Only list the dispatches made during this duration (e.g. 7d)"
+        )]
+        since: Option<Span>,
+
+        #[arg(long, help = "Only list the failed dispatches")]
+        failed: bool,
+
+        #[arg(
+            long,
+            default_value_t = 20,
+            help = "Maximum number of dispatches listed"
+        )]
+        limit: usize,
+    },
+    /// Suspend the scheduling of a cronjob
+    Suspend {
+        #[arg(help = "Name of the cronjob")]
+        cronjob: String,
+    },
+    /// Resume the scheduling of a suspended cronjob
+    Resume {
+        #[arg(help = "Name of the cronjob")]
+        cronjob: String,
+    },
+    /// Delete the succeeded / failed jobs dispatched with bakkutteh which are older than the given age
+    Prune {
+        #[arg(
+            long,
+            default_value = "24h",
+            help = "Only delete the jobs created before this duration (e.g. 24h, 7d)"
+        )]
+        older_than: Span,
+    },
+    /// Compare a job dispatched with bakkutteh with its source, or go through the edition of the job and compare it
+    /// with a previous dispatch instead of creating it
+    Diff {
+        #[arg(
+            help = "Name of the job dispatched with bakkutteh compared with its source (e.g. momo-manual)",
+            required_unless_present = "against_last"
+        )]
+        job: Option<String>,
+
+        #[arg(
+            long,
+            conflicts_with = "job",
+            help = "Compare with the last job dispatched from the same source"
+        )]
+        against_last: bool,
+    },
+}
+
+/// Which value is kept when a setting is defined by both the template and the CLI
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Precedence {
+    /// Ask for each setting
+    Ask,
+    /// The CLI value overrides the template value
+    Cli,
+    /// The template value is kept
+    Template,
+}
+
+/// Kind of the object used as the source of the job
+#[derive(Debug, Clone, PartialEq)]
+enum SourceKind<'a> {
+    CronJob,
+    Deployment,
+    DaemonSet,
+    Pod,
+    CronWorkflow,
+    /// Custom resource embedding a job template at the JSONPath
+    Custom {
+        gvk: GroupVersionKind,
+        path: &'a str,
+    },
+}
+
+/// Data of the ConfigMaps or of the Secrets referenced by the env, per name
+type EnvValues = BTreeMap<String, BTreeMap<String, String>>;
+
+/// State of the run shared by the jobs when multiple sources are dispatched
+#[derive(Debug, Default)]
+struct RunState {
+    server_version: Option<Version>,
+    /// Source read from a manifest, taken by the dispatch of the source
+    manifest: Option<Manifest>,
+    /// Resources answered for the first job. Some(None) when the resources were kept
+    resources: Option<Option<SpecResources>>,
+    backoff_limit: Option<i32>,
+}
+
+/// Steps of the interactive edition of the job spec
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EditStep {
+    Env,
+    RemoveEnv,
+    AdditionalEnv,
+    EnvFrom,
+    Resources,
+    JobMetadata,
+    PodMetadata,
+    Done,
+}
+
+impl EditStep {
+    fn next(self) -> Self {
+        match self {
+            EditStep::Env => EditStep::RemoveEnv,
+            EditStep::RemoveEnv => EditStep::AdditionalEnv,
+            EditStep::AdditionalEnv => EditStep::EnvFrom,
+            EditStep::EnvFrom => EditStep::Resources,
+            EditStep::Resources => EditStep::JobMetadata,
+            EditStep::JobMetadata => EditStep::PodMetadata,
+            EditStep::PodMetadata | EditStep::Done => EditStep::Done,
+        }
+    }
+
+    fn previous(self) -> Self {
+        match self {
+            EditStep::Env | EditStep::RemoveEnv => EditStep::Env,
+            EditStep::AdditionalEnv => EditStep::RemoveEnv,
+            EditStep::EnvFrom => EditStep::AdditionalEnv,
+            EditStep::Resources => EditStep::EnvFrom,
+            EditStep::JobMetadata => EditStep::Resources,
+            EditStep::PodMetadata => EditStep::JobMetadata,
+            EditStep::Done => EditStep::PodMetadata,
+        }
+    }
+}
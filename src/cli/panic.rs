@@ -0,0 +1,103 @@
+use crossterm::{cursor, execute, terminal};
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::io;
+use std::panic::{self, PanicHookInfo};
+
+// Constant
+const ISSUES_URL: &str = "https://github.com/shigedangao/bakkutteh/issues";
+// Values of the arguments are replaced as they may contain env values
+const HIDDEN_VALUE: &str = "<value>";
+
+/// Install a panic hook which restores the terminal left in raw mode by the prompts and prints the context
+/// needed for a bug report. The values of the arguments aren't printed
+pub fn install_hook() {
+    panic::set_hook(Box::new(|info| {
+        restore_terminal();
+
+        eprintln!("{}", report(info, std::env::args().skip(1)));
+
+        let backtrace = Backtrace::capture();
+        if backtrace.status() == BacktraceStatus::Captured {
+            eprintln!("{backtrace}");
+        }
+    }));
+}
+
+/// Leave the raw mode and show the cursor. Best effort, the terminal may not be in raw mode
+pub fn restore_terminal() {
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(io::stderr(), cursor::Show);
+}
+
+fn report<I: Iterator<Item = String>>(info: &PanicHookInfo, args: I) -> String {
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|msg| msg.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+    let location = info
+        .location()
+        .map(|loc| format!("{}:{}", loc.file(), loc.line()))
+        .unwrap_or_default();
+
+    format!(
+        "\nSorry, bakkutteh crashed unexpectedly. Please open an issue at {ISSUES_URL} with the report below\n\n\
+        version: {}\n\
+        os: {} ({})\n\
+        arguments: {}\n\
+        location: {location}\n\
+        message: {message}",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        sanitize_args(args).join(" "),
+    )
+}
+
+/// Keep the flags and the subcommands of the arguments and hide their values
+///
+/// # Arguments
+///
+/// * `args` - I
+fn sanitize_args<I: Iterator<Item = String>>(args: I) -> Vec<String> {
+    let mut flags_seen = false;
+
+    args.map(|arg| match arg.split_once('=') {
+        Some((flag, _)) if flag.starts_with('-') => {
+            flags_seen = true;
+            format!("{flag}={HIDDEN_VALUE}")
+        }
+        _ if arg.starts_with('-') => {
+            flags_seen = true;
+            arg
+        }
+        // Subcommands are written before the flags
+        _ if !flags_seen => arg,
+        _ => HIDDEN_VALUE.to_string(),
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sanitize_args;
+
+    #[test]
+    fn expect_to_hide_argument_values() {
+        let args = [
+            "doctor",
+            "-n",
+            "prod",
+            "--config=/home/me/config.yaml",
+            "--dry-run",
+        ]
+        .into_iter()
+        .map(String::from);
+
+        assert_eq!(
+            sanitize_args(args),
+            vec!["doctor", "-n", "<value>", "--config=<value>", "--dry-run"]
+        );
+    }
+}
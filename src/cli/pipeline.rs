@@ -0,0 +1,104 @@
+use anyhow::{Result, anyhow};
+use jiff::Zoned;
+use k8s_openapi::api::batch::v1::{CronJob, JobSpec};
+use std::collections::BTreeMap;
+
+/// Job being dispatched. It's built from the source by the extract stage then passed from a stage to the next one
+/// until it's applied
+#[derive(Debug, Clone)]
+pub struct Dispatch {
+    /// Name of the cronjob or deployment used as the source of the job
+    pub source: String,
+    /// Name of the job which will be created
+    pub target: String,
+    /// The cronjob is kept in order to compute its schedule. None when the source is a deployment or a daemonset
+    pub cron_job: Option<CronJob>,
+    pub job_spec: JobSpec,
+    pub backoff_limit: i32,
+    pub labels: BTreeMap<String, String>,
+    pub annotations: BTreeMap<String, String>,
+    /// Run of the cronjob at which the suspended job is resumed (--align-next-run)
+    pub start_at: Option<Zoned>,
+}
+
+/// Custom transform of the job. The stages run in order once the job has been edited and before it's validated
+/// (e.g. to inject the labels required by a company policy)
+pub trait Stage {
+    /// Name of the stage used in the errors
+    fn name(&self) -> &str;
+
+    /// Transform the job being dispatched. An error stops the dispatch
+    ///
+    /// # Arguments
+    ///
+    /// * `dispatch` - &mut Dispatch
+    fn transform(&self, dispatch: &mut Dispatch) -> Result<()>;
+}
+
+/// Run the stages in order on the dispatch
+///
+/// # Arguments
+///
+/// * `stages` - &[Box<dyn Stage>]
+/// * `dispatch` - &mut Dispatch
+pub fn run_stages(stages: &[Box<dyn Stage>], dispatch: &mut Dispatch) -> Result<()> {
+    for stage in stages {
+        stage
+            .transform(dispatch)
+            .map_err(|err| anyhow!("Stage {} failed: {err}", stage.name()))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dispatch, Stage, run_stages};
+    use anyhow::{Result, anyhow};
+    use k8s_openapi::api::batch::v1::JobSpec;
+    use std::collections::BTreeMap;
+
+    struct TeamLabel;
+
+    impl Stage for TeamLabel {
+        fn name(&self) -> &str {
+            "team-label"
+        }
+
+        fn transform(&self, dispatch: &mut Dispatch) -> Result<()> {
+            match dispatch.labels.contains_key("team") {
+                true => Err(anyhow!("team label already set")),
+                false => {
+                    dispatch
+                        .labels
+                        .insert("team".to_string(), "ops".to_string());
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn expect_stages_to_transform_dispatch() {
+        let mut dispatch = Dispatch {
+            source: "cronjob".to_string(),
+            target: "cronjob-manual".to_string(),
+            cron_job: None,
+            job_spec: JobSpec::default(),
+            backoff_limit: 3,
+            labels: BTreeMap::new(),
+            annotations: BTreeMap::new(),
+            start_at: None,
+        };
+
+        let stages: Vec<Box<dyn Stage>> = vec![Box::new(TeamLabel)];
+        run_stages(&stages, &mut dispatch).unwrap();
+        assert_eq!(dispatch.labels.get("team").map(String::as_str), Some("ops"));
+
+        let err = run_stages(&stages, &mut dispatch).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Stage team-label failed: team label already set"
+        );
+    }
+}
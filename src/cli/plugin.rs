@@ -0,0 +1,216 @@
+use crate::cli::i18n::tr_args;
+use crate::cli::pipeline::{Dispatch, Stage};
+use anyhow::{Result, anyhow};
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+// Constant
+// Context of the dispatch given to the plugins
+const NAMESPACE_ENV: &str = "BAKKUTTEH_NAMESPACE";
+const SOURCE_ENV: &str = "BAKKUTTEH_SOURCE";
+const TARGET_ENV: &str = "BAKKUTTEH_TARGET";
+
+/// Executable which receives the JobSpec as JSON on stdin and writes the modified JobSpec as JSON on stdout
+#[derive(Debug, Clone)]
+pub struct Plugin {
+    name: String,
+    path: PathBuf,
+    namespace: String,
+}
+
+/// Discover the executables of the plugins directory. Plugins run in the order of their names (e.g. 10-cost-labels
+/// runs before 20-proxy-env). No plugin is found when the directory does not exist
+///
+/// # Arguments
+///
+/// * `dir` - &Path
+/// * `namespace` - &str
+pub fn discover(dir: &Path, namespace: &str) -> Result<Vec<Plugin>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut plugins = fs::read_dir(dir)
+        .map_err(|err| {
+            anyhow!(tr_args(
+                "read-plugins-failed",
+                &[
+                    ("path", dir.display().to_string()),
+                    ("error", err.to_string())
+                ]
+            ))
+        })?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| is_executable(path))
+        .map(|path| Plugin {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path,
+            namespace: namespace.to_string(),
+        })
+        .collect::<Vec<_>>();
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(plugins)
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path).is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+impl Stage for Plugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn transform(&self, dispatch: &mut Dispatch) -> Result<()> {
+        let mut child = Command::new(&self.path)
+            .env(NAMESPACE_ENV, &self.namespace)
+            .env(SOURCE_ENV, &dispatch.source)
+            .env(TARGET_ENV, &dispatch.target)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| {
+                anyhow!(tr_args(
+                    "run-plugin-failed",
+                    &[
+                        ("path", self.path.display().to_string()),
+                        ("error", err.to_string())
+                    ]
+                ))
+            })?;
+
+        // The spec is written while the output is read, otherwise a plugin writing before the end of its input would
+        // block both processes once the pipes are full. The stdin is closed once the spec is written so that the
+        // plugin knows that the input is complete
+        let input = serde_json::to_vec(&dispatch.job_spec)?;
+        let writer = child
+            .stdin
+            .take()
+            .map(|mut stdin| thread::spawn(move || stdin.write_all(&input)));
+
+        let output = child.wait_with_output()?;
+        // A plugin may not read its whole input
+        if let Some(Ok(Err(err))) = writer.map(|writer| writer.join())
+            && err.kind() != ErrorKind::BrokenPipe
+        {
+            return Err(err.into());
+        }
+        if !output.status.success() {
+            return Err(anyhow!(tr_args(
+                "plugin-exited",
+                &[
+                    ("status", output.status.to_string()),
+                    (
+                        "stderr",
+                        String::from_utf8_lossy(&output.stderr).trim().to_string()
+                    )
+                ]
+            )));
+        }
+
+        dispatch.job_spec = serde_json::from_slice(&output.stdout).map_err(|err| {
+            anyhow!(tr_args(
+                "plugin-invalid-spec",
+                &[("error", err.to_string())]
+            ))
+        })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::discover;
+    use crate::cli::pipeline::{Dispatch, Stage};
+    use k8s_openapi::api::batch::v1::JobSpec;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+
+    fn write_plugin(dir: &Path, name: &str, script: &str) {
+        let path = dir.join(name);
+        fs::write(&path, script).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    fn dispatch(job_spec: JobSpec) -> Dispatch {
+        Dispatch {
+            source: "cronjob".to_string(),
+            target: "cronjob-manual".to_string(),
+            cron_job: None,
+            job_spec,
+            backoff_limit: 3,
+            labels: BTreeMap::new(),
+            annotations: BTreeMap::new(),
+            start_at: None,
+        }
+    }
+
+    #[test]
+    fn expect_plugin_to_modify_spec() {
+        let dir = std::env::temp_dir().join(format!("bakkutteh-plugins-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        write_plugin(
+            &dir,
+            "10-ttl",
+            "#!/bin/sh\ncat > /dev/null\necho '{\"ttlSecondsAfterFinished\": 60, \"template\": {}}'\n",
+        );
+        // Files which aren't executable aren't plugins
+        fs::write(dir.join("README"), "").unwrap();
+
+        let plugins = discover(&dir, "default").unwrap();
+        assert_eq!(plugins.len(), 1);
+
+        let mut dispatch = dispatch(JobSpec::default());
+        plugins[0].transform(&mut dispatch).unwrap();
+        assert_eq!(dispatch.job_spec.ttl_seconds_after_finished, Some(60));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn expect_plugin_writing_before_reading_not_to_block() {
+        let dir =
+            std::env::temp_dir().join(format!("bakkutteh-plugins-large-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // The output and the input are larger than the buffer of the pipes
+        write_plugin(
+            &dir,
+            "10-echo",
+            "#!/bin/sh\nhead -c 200000 /dev/zero | tr '\\0' ' '\ncat\n",
+        );
+        let plugins = discover(&dir, "default").unwrap();
+
+        let mut job_spec = JobSpec::default();
+        job_spec.template.metadata = Some(ObjectMeta {
+            annotations: Some(BTreeMap::from([("large".to_string(), "x".repeat(200_000))])),
+            ..Default::default()
+        });
+        let mut dispatch = dispatch(job_spec.clone());
+        plugins[0].transform(&mut dispatch).unwrap();
+        assert_eq!(dispatch.job_spec, job_spec);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,172 @@
+use super::i18n;
+use anyhow::{Result, anyhow};
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+// Escape character starting the color sequences which may be present in a prompt title
+const ESCAPE_CHAR: char = '\u{1b}';
+
+static SESSION: Mutex<Session> = Mutex::new(Session::new());
+
+/// Session holds the answers replayed from a session file and the answers which are recorded during the run.
+/// Answers are stored per question as the same question may be asked multiple times (e.g. additional envs)
+#[derive(Debug, Default)]
+struct Session {
+    replay: BTreeMap<String, VecDeque<String>>,
+    record: BTreeMap<String, Vec<String>>,
+    record_path: Option<PathBuf>,
+}
+
+impl Session {
+    const fn new() -> Self {
+        Self {
+            replay: BTreeMap::new(),
+            record: BTreeMap::new(),
+            record_path: None,
+        }
+    }
+}
+
+fn session() -> MutexGuard<'static, Session> {
+    // A poisoned lock only means that a prompt panicked, the answers are still usable
+    SESSION.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Load the answers of a previously recorded session
+///
+/// # Arguments
+///
+/// * `path` - S
+pub fn load_replay<S: AsRef<str>>(path: S) -> Result<()> {
+    let contents = fs::read_to_string(path.as_ref()).map_err(|err| {
+        anyhow!(i18n::tr_args(
+            "read-session-failed",
+            &[
+                ("path", path.as_ref().to_string()),
+                ("error", err.to_string())
+            ]
+        ))
+    })?;
+    let answers: BTreeMap<String, Vec<String>> = serde_yml::from_str(&contents)?;
+
+    session().replay = answers
+        .into_iter()
+        .map(|(key, values)| (key, VecDeque::from(values)))
+        .collect();
+
+    Ok(())
+}
+
+/// Record every answer given during the run. The answers are written with [`save`]
+///
+/// # Arguments
+///
+/// * `path` - S
+pub fn start_record<S: AsRef<str>>(path: S) {
+    session().record_path = Some(PathBuf::from(path.as_ref()));
+}
+
+/// Write the recorded answers to the record file if the user asked for it
+pub fn save() -> Result<()> {
+    let session = session();
+    let Some(path) = &session.record_path else {
+        return Ok(());
+    };
+
+    fs::write(path, serde_yml::to_string(&session.record)?).map_err(|err| {
+        anyhow!(i18n::tr_args(
+            "write-session-failed",
+            &[
+                ("path", path.display().to_string()),
+                ("error", err.to_string())
+            ]
+        ))
+    })
+}
+
+/// Take the next replayed answer for the question if any
+///
+/// # Arguments
+///
+/// * `key` - &str (see [`i18n::Question::key`])
+pub fn replayed(key: &str) -> Option<String> {
+    session()
+        .replay
+        .get_mut(key)
+        .and_then(|answers| answers.pop_front())
+}
+
+/// Record the answer of the question
+///
+/// # Arguments
+///
+/// * `key` - &str (see [`i18n::Question::key`])
+/// * `answer` - S
+pub fn record<S: ToString>(key: &str, answer: S) {
+    let mut session = session();
+    if session.record_path.is_some() {
+        session
+            .record
+            .entry(key.to_string())
+            .or_default()
+            .push(answer.to_string());
+    }
+}
+
+/// Get the text of the question without the color sequences and the surrounding whitespaces
+///
+/// # Arguments
+///
+/// * `question` - &str
+pub fn text_key(question: &str) -> String {
+    let mut key = String::with_capacity(question.len());
+    let mut chars = question.chars();
+
+    while let Some(c) = chars.next() {
+        if c == ESCAPE_CHAR {
+            // Skip the sequence until the terminating letter e.g: \x1b[38;2;180;140;247m
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+
+        key.push(c);
+    }
+
+    key.trim().trim_end_matches(':').trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::text_key;
+    use crate::cli::i18n::{Question, question_args};
+
+    #[test]
+    fn expect_key_to_strip_colors() {
+        assert_eq!(
+            text_key("Env for \u{1b}[38;2;180;140;247mFOO\u{1b}[0m: "),
+            "Env for FOO"
+        );
+    }
+
+    #[test]
+    fn expect_key_to_be_message_id() {
+        let name = "\u{1b}[38;2;180;140;247mBAR\u{1b}[0m".to_string();
+        let title = question_args("env-title", &[("name", name)]).suffixed(" ");
+
+        assert_eq!(title.key(), "env-title name=BAR");
+        assert_eq!(
+            title.text(),
+            "Env for \u{1b}[38;2;180;140;247mBAR\u{1b}[0m: "
+        );
+        assert_eq!(
+            Question::from("Unknown question ?").key(),
+            "Unknown question ?"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+// Constant
+const PHASES: [Phase; 4] = [
+    Phase::Listing,
+    Phase::Fetching,
+    Phase::Prompting,
+    Phase::Applying,
+];
+
+/// Phase of an invocation. Listing, fetching and applying wait for the cluster while prompting waits for the user
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    Listing,
+    Fetching,
+    Prompting,
+    Applying,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Pad in order to align the summary
+        f.pad(match self {
+            Phase::Listing => "listing",
+            Phase::Fetching => "fetching",
+            Phase::Prompting => "prompting",
+            Phase::Applying => "applying",
+        })
+    }
+}
+
+/// Timings accumulates the time spent in each phase of an invocation
+#[derive(Debug)]
+pub struct Timings {
+    spent: [Duration; PHASES.len()],
+    current: Option<(Phase, Instant)>,
+}
+
+impl Timings {
+    /// Create new timings. The invocation starts by processing the CLI arguments which is accounted as prompting
+    pub fn new() -> Self {
+        Self {
+            spent: [Duration::ZERO; PHASES.len()],
+            current: Some((Phase::Prompting, Instant::now())),
+        }
+    }
+
+    /// Stop the current phase and start the given one
+    ///
+    /// # Arguments
+    ///
+    /// * `phase` - Phase
+    pub fn enter(&mut self, phase: Phase) {
+        self.stop();
+        self.current = Some((phase, Instant::now()));
+    }
+
+    /// Stop the current phase and get the summary of the time spent in each phase
+    pub fn summary(&mut self) -> String {
+        self.stop();
+
+        let total: Duration = self.spent.iter().sum();
+        let mut summary = PHASES
+            .iter()
+            .zip(self.spent)
+            .map(|(phase, spent)| format!("{phase:<10} {:>8.2}s\n", spent.as_secs_f64()))
+            .collect::<String>();
+        summary.push_str(&format!("{:<10} {:>8.2}s", "total", total.as_secs_f64()));
+
+        summary
+    }
+
+    fn stop(&mut self) {
+        if let Some((phase, start)) = self.current.take() {
+            self.add(phase, start.elapsed());
+        }
+    }
+
+    fn add(&mut self, phase: Phase, elapsed: Duration) {
+        self.spent[phase as usize] += elapsed;
+    }
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Phase, Timings};
+    use std::time::Duration;
+
+    #[test]
+    fn expect_to_accumulate_phases() {
+        let mut timings = Timings::new();
+        timings.current = None;
+        timings.add(Phase::Fetching, Duration::from_millis(1500));
+        timings.add(Phase::Prompting, Duration::from_millis(250));
+        timings.add(Phase::Fetching, Duration::from_millis(500));
+
+        let summary = timings.summary();
+        assert_eq!(
+            summary,
+            [
+                "listing        0.00s",
+                "fetching       2.00s",
+                "prompting      0.25s",
+                "applying       0.00s",
+                "total          2.25s",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -1,19 +1,137 @@
+use super::editor;
+use super::events::{self, Event};
+use super::i18n::{Question, tr, tr_args};
+use super::session;
 use anyhow::{Result, anyhow};
 use inquire::{
-    Confirm, Select, Text, set_global_render_config,
+    Confirm, InquireError, MultiSelect, Password, PasswordDisplayMode, Select, Text,
+    set_global_render_config,
     ui::{
         Attributes, Color, ErrorMessageRenderConfig, IndexPrefix, RenderConfig, StyleSheet, Styled,
     },
-    validator::StringValidator,
+    validator::{ErrorMessage, StringValidator, Validation},
 };
 use spinners::{Spinner, Spinners};
 use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
 
 // Constant
 const SELECT_PAGE_SIZE: usize = 20;
+// Separator of the selected options when a multi select answer is recorded
+const MULTI_SELECT_SEPARATOR: char = '\n';
+// Answer used to go back to the previous question in plain mode
+const PLAIN_BACK: &str = "back";
+// Interval at which a plain prompt checks whether it was interrupted
+const INTERRUPT_POLL: Duration = Duration::from_millis(100);
 
-/// SpinnerWrapper is a wrapper around the spinners::Spinner struct
-pub struct SpinnerWrapper(Spinner);
+// Whether the prompts should be rendered as plain numbered / line based prompts
+static PLAIN_PROMPTS: AtomicBool = AtomicBool::new(false);
+// Whether the prompts are answered without the user (e.g. CI pipelines)
+static NON_INTERACTIVE: AtomicBool = AtomicBool::new(false);
+// Whether Ctrl-C was pressed while a plain prompt was waiting for its answer
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+// Lines of the stdin, read by a dedicated thread so that the plain prompts can be interrupted
+type Lines = Receiver<io::Result<Option<String>>>;
+static LINES: OnceLock<Mutex<Lines>> = OnceLock::new();
+
+/// Enable the plain prompt mode. Prompts are read line by line from the stdin and no cursor control
+/// sequences or colors are written, which makes the CLI usable with screen readers and dumb terminals.
+pub fn enable_plain_prompts() {
+    PLAIN_PROMPTS.store(true, Ordering::Relaxed);
+    colored::control::set_override(false);
+}
+
+fn is_plain() -> bool {
+    PLAIN_PROMPTS.load(Ordering::Relaxed)
+}
+
+/// Interrupt the plain prompt waiting for its answer. The Ctrl-C handler of the runtime catches the signal, which
+/// doesn't interrupt the blocking read of the stdin anymore
+pub fn interrupt() {
+    INTERRUPTED.store(true, Ordering::Relaxed);
+}
+
+/// Enable the non-interactive mode. The prompts aren't displayed: the confirmations are answered with their
+/// default, the selects with their first option and the texts with their default value. The prompts without a
+/// default return an error. The answers of a replayed session are still used
+pub fn enable_non_interactive() {
+    NON_INTERACTIVE.store(true, Ordering::Relaxed);
+}
+
+pub fn is_non_interactive() -> bool {
+    NON_INTERACTIVE.load(Ordering::Relaxed)
+}
+
+/// Error returned by the prompts which can't be answered in non-interactive mode
+///
+/// # Arguments
+///
+/// * `question` - &Question
+fn unanswered(question: &Question) -> anyhow::Error {
+    anyhow!(tr_args(
+        "non-interactive-unanswered",
+        &[("question", question.key().to_string())]
+    ))
+}
+
+/// GoBack is returned by the prompts when the user asked to go back to the previous question
+/// by pressing Esc (or answering `back` in plain mode)
+#[derive(Debug)]
+pub struct GoBack;
+
+impl fmt::Display for GoBack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Going back to the previous question")
+    }
+}
+
+impl std::error::Error for GoBack {}
+
+/// Check whether the error is a request of the user to go back to the previous question
+///
+/// # Arguments
+///
+/// * `err` - &anyhow::Error
+pub fn is_back(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<GoBack>().is_some()
+}
+
+/// Record the answer of the question in the session and emit it as an event. The replayed answers are recorded
+/// as well so that a replayed session can be completed in a new session file
+///
+/// # Arguments
+///
+/// * `question` - &Question
+/// * `answer` - S
+fn answered<S: ToString>(question: &Question, answer: S) {
+    let answer = answer.to_string();
+    events::emit(Event::PromptAnswered {
+        question: question.key(),
+        answer: &answer,
+    });
+    session::record(question.key(), answer);
+}
+
+/// Convert the inquire error. Canceling the prompt with Esc is used to go back to the previous question
+///
+/// # Arguments
+///
+/// * `err` - InquireError
+/// * `id` - &str (message of the error, with the inquire error as its argument)
+fn prompt_err(err: InquireError, id: &str) -> anyhow::Error {
+    match err {
+        InquireError::OperationCanceled => GoBack.into(),
+        err => anyhow!(tr_args(id, &[("error", err.to_string())])),
+    }
+}
+
+/// SpinnerWrapper is a wrapper around the spinners::Spinner struct. No spinner is rendered in plain mode
+pub struct SpinnerWrapper(Option<Spinner>);
 
 impl SpinnerWrapper {
     /// new creates a new SpinnerWrapper with the given message
@@ -22,12 +140,38 @@ impl SpinnerWrapper {
     ///
     /// * `msg` - S
     pub fn new<S: Into<String>>(msg: S) -> Self {
-        Self(Spinner::new(Spinners::Dots9, msg.into()))
+        if is_plain() {
+            println!("{}", msg.into());
+            return Self(None);
+        }
+
+        Self(Some(Spinner::new(Spinners::Dots9, msg.into())))
+    }
+
+    /// hidden creates a SpinnerWrapper which renders nothing, e.g. for the jobs created concurrently
+    pub fn hidden() -> Self {
+        Self(None)
+    }
+
+    /// set_message replaces the message of the spinner. Nothing is printed in plain mode
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - S
+    pub fn set_message<S: Into<String>>(&mut self, msg: S) {
+        // The message of a running spinner can't be changed, the spinner is restarted on the same line
+        if let Some(spinner) = self.0.as_mut() {
+            spinner.stop();
+            *spinner = Spinner::new(Spinners::Dots9, msg.into());
+        }
     }
 
     /// stop stops the spinner and prints a newline
     pub fn stop(&mut self) {
-        self.0.stop_with_newline();
+        // The spinner is taken as it can only be stopped once
+        if let Some(mut spinner) = self.0.take() {
+            spinner.stop_with_newline();
+        }
     }
 }
 
@@ -35,33 +179,156 @@ impl SpinnerWrapper {
 ///
 /// # Arguments
 ///
-/// * `title` - S
-/// * `default_value` - Option<S>
-pub fn text<S: AsRef<str>>(title: S, default_value: Option<S>) -> Result<String> {
-    let mut text = Text::new(title.as_ref());
-    if let Some(ref def) = default_value {
-        text = text.with_default(def.as_ref());
+/// * `title` - Q
+/// * `default_value` - Option<&str>
+pub fn text<Q: Into<Question>>(title: Q, default_value: Option<&str>) -> Result<String> {
+    let title = title.into();
+    if let Some(answer) = session::replayed(title.key()) {
+        answered(&title, &answer);
+        return Ok(answer);
+    }
+
+    let res = prompt_text(&title, default_value)?;
+    answered(&title, &res);
+
+    Ok(res)
+}
+
+fn prompt_text(question: &Question, default_value: Option<&str>) -> Result<String> {
+    let title = question.text();
+    if is_non_interactive() {
+        return default_value
+            .map(|def| def.trim().to_string())
+            .ok_or_else(|| unanswered(question));
+    }
+
+    if is_plain() {
+        let prompt = match default_value {
+            Some(def) => format!("{title} [{def}] "),
+            None => format!("{title} "),
+        };
+
+        let res = read_line(prompt)?;
+        return match (res.is_empty(), default_value) {
+            (true, Some(def)) => Ok(def.trim().to_string()),
+            _ => Ok(res),
+        };
+    }
+
+    let mut text = Text::new(title);
+    if let Some(def) = default_value {
+        text = text.with_default(def);
     }
 
     match text.prompt() {
         Ok(res) => Ok(res.trim().to_string()),
-        Err(err) => Err(anyhow!("Operation canceled: {:?}", err)),
+        Err(err) => Err(prompt_err(err, "prompt-canceled")),
+    }
+}
+
+/// Password implements a wrapper around the inquire's password component. The input is masked and the answer
+/// is neither replayed, recorded nor emitted as an event as it's sensitive
+///
+/// # Arguments
+///
+/// * `title` - Q
+pub fn password<Q: Into<Question>>(title: Q) -> Result<String> {
+    let title = title.into();
+    if is_non_interactive() {
+        return Err(unanswered(&title));
+    }
+
+    // The input can't be hidden without cursor control
+    if is_plain() {
+        return read_line(format!("{title} "));
+    }
+
+    Password::new(title.text())
+        .with_display_mode(PasswordDisplayMode::Masked)
+        .without_confirmation()
+        .prompt()
+        .map_err(|err| prompt_err(err, "prompt-canceled"))
+}
+
+/// Edit a multi-line value in the editor of the user, a single line prompt would mangle it (e.g. JSON, PEM).
+/// The value is returned as is when the editor is closed without changes
+///
+/// # Arguments
+///
+/// * `title` - Q
+/// * `value` - &str
+pub fn editor<Q: Into<Question>>(title: Q, value: &str) -> Result<String> {
+    let title = title.into();
+    if let Some(answer) = session::replayed(title.key()) {
+        answered(&title, &answer);
+        return Ok(answer);
     }
+
+    if is_non_interactive() {
+        answered(&title, value);
+        return Ok(value.to_string());
+    }
+
+    println!("{title}");
+    let res = editor::edit_text(value)?;
+    answered(&title, &res);
+
+    Ok(res)
 }
 
 /// Text with validator add a validator to the text prompt
 ///
 /// # Arguments
 ///
-/// * `title` - S
+/// * `title` - Q
 /// * `validator` - F
-pub fn text_with_validator<S: AsRef<str>, F: StringValidator>(
-    title: S,
+pub fn text_with_validator<Q: Into<Question>, F: StringValidator>(
+    title: Q,
+    validator: F,
+) -> Result<String> {
+    let title = title.into();
+    if let Some(answer) = session::replayed(title.key())
+        && matches!(validator.validate(&answer), Ok(Validation::Valid))
+    {
+        answered(&title, &answer);
+        return Ok(answer);
+    }
+
+    let res = prompt_text_with_validator(&title, validator)?;
+    answered(&title, &res);
+
+    Ok(res)
+}
+
+fn prompt_text_with_validator<F: StringValidator>(
+    question: &Question,
     validator: F,
 ) -> Result<String> {
-    match Text::new(title.as_ref()).with_validator(validator).prompt() {
+    if is_non_interactive() {
+        return Err(unanswered(question));
+    }
+
+    let title = question.text();
+
+    if is_plain() {
+        loop {
+            let res = read_line(format!("{title} "))?;
+            match validator.validate(&res).map_err(|err| {
+                anyhow!(tr_args(
+                    "prompt-validation-failed",
+                    &[("error", err.to_string())]
+                ))
+            })? {
+                Validation::Valid => return Ok(res),
+                Validation::Invalid(ErrorMessage::Custom(msg)) => println!("{msg}"),
+                Validation::Invalid(ErrorMessage::Default) => println!("{}", tr("invalid-input")),
+            }
+        }
+    }
+
+    match Text::new(title).with_validator(validator).prompt() {
         Ok(res) => Ok(res),
-        Err(err) => Err(anyhow!("Validation did not passed due to: {err}")),
+        Err(err) => Err(prompt_err(err, "prompt-validation-failed")),
     }
 }
 
@@ -69,15 +336,90 @@ pub fn text_with_validator<S: AsRef<str>, F: StringValidator>(
 ///
 /// # Arguments
 ///
-/// * `msg` - S
+/// * `msg` - Q
 /// * `list` - Vec<S>
-pub fn select<S: AsRef<str> + fmt::Display>(msg: S, list: Vec<S>) -> Result<S> {
-    match Select::new(msg.as_ref(), list)
+pub fn select<Q: Into<Question>, S: fmt::Display>(msg: Q, mut list: Vec<S>) -> Result<S> {
+    let msg = msg.into();
+    if let Some(answer) = session::replayed(msg.key())
+        && let Some(idx) = list.iter().position(|item| item.to_string() == answer)
+    {
+        answered(&msg, &answer);
+        return Ok(list.swap_remove(idx));
+    }
+
+    let res = prompt_select(&msg, list)?;
+    answered(&msg, &res);
+
+    Ok(res)
+}
+
+fn prompt_select<S: fmt::Display>(msg: &Question, list: Vec<S>) -> Result<S> {
+    if is_non_interactive() {
+        return list.into_iter().next().ok_or_else(|| unanswered(msg));
+    }
+
+    if is_plain() {
+        return plain_select(msg.text(), list);
+    }
+
+    match Select::new(msg.text(), list)
+        .with_page_size(SELECT_PAGE_SIZE)
+        .prompt()
+    {
+        Ok(res) => Ok(res),
+        Err(err) => Err(prompt_err(err, "prompt-select-failed")),
+    }
+}
+
+/// Multi select implements a wrapper around the inquire's multi select component
+///
+/// # Arguments
+///
+/// * `msg` - Q
+/// * `list` - Vec<String>
+/// * `defaults` - &[usize] (indexes of the options selected by default)
+pub fn multi_select<Q: Into<Question>>(
+    msg: Q,
+    list: Vec<String>,
+    defaults: &[usize],
+) -> Result<Vec<String>> {
+    let msg = msg.into();
+    if let Some(answer) = session::replayed(msg.key()) {
+        let selected = answer.split(MULTI_SELECT_SEPARATOR).collect::<Vec<_>>();
+        let res = list
+            .into_iter()
+            .filter(|item| selected.contains(&item.as_str()))
+            .collect::<Vec<_>>();
+        answered(&msg, res.join(&MULTI_SELECT_SEPARATOR.to_string()));
+
+        return Ok(res);
+    }
+
+    let res = prompt_multi_select(msg.text(), list, defaults)?;
+    answered(&msg, res.join(&MULTI_SELECT_SEPARATOR.to_string()));
+
+    Ok(res)
+}
+
+fn prompt_multi_select(msg: &str, list: Vec<String>, defaults: &[usize]) -> Result<Vec<String>> {
+    if is_non_interactive() {
+        return Ok(defaults
+            .iter()
+            .filter_map(|idx| list.get(*idx).cloned())
+            .collect());
+    }
+
+    if is_plain() {
+        return plain_multi_select(msg, list, defaults);
+    }
+
+    match MultiSelect::new(msg, list)
+        .with_default(defaults)
         .with_page_size(SELECT_PAGE_SIZE)
         .prompt()
     {
         Ok(res) => Ok(res),
-        Err(err) => Err(anyhow!("Unable to select the element due to: {err}")),
+        Err(err) => Err(prompt_err(err, "prompt-multi-select-failed")),
     }
 }
 
@@ -85,13 +427,183 @@ pub fn select<S: AsRef<str> + fmt::Display>(msg: S, list: Vec<S>) -> Result<S> {
 ///
 /// # Arguments
 ///
-/// * `msg` - S
+/// * `msg` - Q
 /// * `default_value` - bool
-pub fn confirm<S: AsRef<str>>(msg: S, default_value: bool) -> Result<bool> {
-    Confirm::new(msg.as_ref())
+pub fn confirm<Q: Into<Question>>(msg: Q, default_value: bool) -> Result<bool> {
+    let msg = msg.into();
+    if let Some(answer) = session::replayed(msg.key())
+        && let Ok(answer) = answer.parse::<bool>()
+    {
+        answered(&msg, answer);
+        return Ok(answer);
+    }
+
+    let res = prompt_confirm(msg.text(), default_value)?;
+    answered(&msg, res);
+
+    Ok(res)
+}
+
+fn prompt_confirm(msg: &str, default_value: bool) -> Result<bool> {
+    if is_non_interactive() {
+        return Ok(default_value);
+    }
+
+    if is_plain() {
+        let hint = match default_value {
+            true => "[Y/n]",
+            false => "[y/N]",
+        };
+
+        loop {
+            match read_line(format!("{msg} {hint} "))?.to_lowercase().as_str() {
+                "" => return Ok(default_value),
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                _ => println!("{}", tr("plain-confirm-invalid")),
+            }
+        }
+    }
+
+    Confirm::new(msg)
         .with_default(default_value)
         .prompt()
-        .map_err(|err| anyhow!("Unable to get the confirmation from the user: {err}"))
+        .map_err(|err| prompt_err(err, "prompt-confirm-failed"))
+}
+
+/// Select an element of the list by typing its number. Used in place of the select component in plain mode
+///
+/// # Arguments
+///
+/// * `msg` - &str
+/// * `list` - Vec<S>
+fn plain_select<S: fmt::Display>(msg: &str, mut list: Vec<S>) -> Result<S> {
+    if list.is_empty() {
+        return Err(anyhow!(tr("select-no-options")));
+    }
+
+    println!("{msg}");
+    for (idx, item) in list.iter().enumerate() {
+        println!("  {}) {item}", idx + 1);
+    }
+
+    loop {
+        let prompt = tr_args("plain-select", &[("count", list.len().to_string())]);
+        let res = read_line(format!("{prompt} "))?;
+        match res.parse::<usize>() {
+            Ok(idx) if idx >= 1 && idx <= list.len() => return Ok(list.swap_remove(idx - 1)),
+            _ => println!("{}", tr_args("plain-invalid-choice", &[("answer", res)])),
+        }
+    }
+}
+
+/// Select elements of the list by typing their numbers separated by commas. An empty answer keeps the default selection
+///
+/// # Arguments
+///
+/// * `msg` - &str
+/// * `list` - Vec<String>
+/// * `defaults` - &[usize]
+fn plain_multi_select(msg: &str, list: Vec<String>, defaults: &[usize]) -> Result<Vec<String>> {
+    println!("{msg}");
+    for (idx, item) in list.iter().enumerate() {
+        let mark = match defaults.contains(&idx) {
+            true => "x",
+            false => " ",
+        };
+        println!("  {}) [{mark}] {item}", idx + 1);
+    }
+
+    loop {
+        let res = read_line(format!("{} ", tr("plain-multi-select")))?;
+        if res.is_empty() {
+            return Ok(defaults
+                .iter()
+                .filter_map(|idx| list.get(*idx).cloned())
+                .collect());
+        }
+
+        let indexes = res
+            .split(',')
+            .map(|idx| idx.trim().parse::<usize>())
+            .collect::<Result<Vec<_>, _>>();
+
+        match indexes {
+            Ok(indexes) if indexes.iter().all(|idx| *idx >= 1 && *idx <= list.len()) => {
+                return Ok(list
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(idx, _)| indexes.contains(&(idx + 1)))
+                    .map(|(_, item)| item)
+                    .collect());
+            }
+            _ => println!("{}", tr_args("plain-invalid-choice", &[("answer", res)])),
+        }
+    }
+}
+
+/// Print the prompt and read a single line from the stdin. Answering `back` goes back to the previous question
+///
+/// # Arguments
+///
+/// * `prompt` - S
+fn read_line<S: AsRef<str>>(prompt: S) -> Result<String> {
+    print!("{}", prompt.as_ref());
+    io::stdout().flush()?;
+
+    // A Ctrl-C pressed before the prompt doesn't interrupt it
+    INTERRUPTED.store(false, Ordering::Relaxed);
+    let lines = stdin_lines().lock().unwrap_or_else(|err| err.into_inner());
+    let Some(line) = recv_line(&lines)? else {
+        return Err(anyhow!(tr("end-of-input")));
+    };
+
+    match line.trim() {
+        PLAIN_BACK => Err(GoBack.into()),
+        line => Ok(line.to_string()),
+    }
+}
+
+/// Get the lines of the stdin. The thread reading them is started on the first plain prompt and stops at the end
+/// of the input
+fn stdin_lines() -> &'static Mutex<Lines> {
+    LINES.get_or_init(|| {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            loop {
+                let mut line = String::new();
+                let res = io::stdin()
+                    .lock()
+                    .read_line(&mut line)
+                    .map(|read| (read > 0).then_some(line));
+                let end = !matches!(res, Ok(Some(_)));
+                if tx.send(res).is_err() || end {
+                    break;
+                }
+            }
+        });
+
+        Mutex::new(rx)
+    })
+}
+
+/// Wait for the next line of the stdin until the prompt is interrupted. None is returned at the end of the input
+///
+/// # Arguments
+///
+/// * `lines` - &Lines
+fn recv_line(lines: &Lines) -> Result<Option<String>> {
+    loop {
+        if INTERRUPTED.swap(false, Ordering::Relaxed) {
+            return Err(anyhow!(tr("interrupted")));
+        }
+
+        match lines.recv_timeout(INTERRUPT_POLL) {
+            Ok(line) => return Ok(line?),
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => return Ok(None),
+        }
+    }
 }
 
 /// Initializes the Clack purple theme for the UI components. (done by Claude).
@@ -141,3 +653,24 @@ pub fn init_clack_purple_theme() {
 
     set_global_render_config(config);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{interrupt, recv_line};
+    use std::sync::mpsc;
+
+    // Manual check: run `bakkutteh --plain-prompts -j <cronjob>` and press Ctrl-C at a prompt, the run stops
+    #[test]
+    fn expect_plain_prompt_to_be_interrupted() {
+        let (tx, rx) = mpsc::channel();
+
+        interrupt();
+        assert!(recv_line(&rx).is_err());
+
+        tx.send(Ok(Some("momo\n".to_string()))).unwrap();
+        assert_eq!(recv_line(&rx).unwrap().as_deref(), Some("momo\n"));
+
+        drop(tx);
+        assert!(recv_line(&rx).unwrap().is_none());
+    }
+}
@@ -0,0 +1,201 @@
+use super::i18n::{tr, tr_args};
+use anyhow::{Result, anyhow};
+use semver::Version;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use zip::ZipArchive;
+
+// Constant
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/shigedangao/bakkutteh/releases/latest";
+const USER_AGENT: &str = concat!("bakkutteh/", env!("CARGO_PKG_VERSION"));
+// Checksums are published next to the artifacts by the release workflow
+const CHECKSUM_EXTENSION: &str = ".sha256";
+const BINARY_NAME: &str = "bakkutteh";
+// The binary is bigger than the default limit of the HTTP client
+const MAX_ARTIFACT_SIZE: u64 = 200 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// Replace the current binary with the one of the latest GitHub release when it's newer. The checksum of the
+/// artifact is verified before the binary is replaced
+///
+/// # Arguments
+///
+/// * `force` - bool
+pub fn self_update(force: bool) -> Result<()> {
+    let current = Version::parse(env!("CARGO_PKG_VERSION"))?;
+    let release = ureq::get(LATEST_RELEASE_URL)
+        .header("User-Agent", USER_AGENT)
+        .call()?
+        .body_mut()
+        .read_json::<Release>()?;
+
+    let latest = parse_tag(&release.tag_name)?;
+    if latest <= current && !force {
+        println!(
+            "{}",
+            tr_args("already-latest", &[("version", current.to_string())])
+        );
+        return Ok(());
+    }
+
+    let artifact_name = artifact_name()?;
+    let artifact = download(&release, artifact_name)?;
+    let checksum = download(&release, &format!("{artifact_name}{CHECKSUM_EXTENSION}"))?;
+    verify_checksum(&artifact, &String::from_utf8_lossy(&checksum))?;
+
+    let mut archive = ZipArchive::new(Cursor::new(artifact))?;
+    let mut binary = Vec::new();
+    archive.by_name(BINARY_NAME)?.read_to_end(&mut binary)?;
+
+    replace_binary(&env::current_exe()?, &binary)?;
+    println!(
+        "{}",
+        tr_args(
+            "updated",
+            &[
+                ("current", current.to_string()),
+                ("latest", latest.to_string())
+            ]
+        )
+    );
+
+    Ok(())
+}
+
+/// Get the name of the artifact built for the current platform
+fn artifact_name() -> Result<&'static str> {
+    match (env::consts::OS, env::consts::ARCH) {
+        ("linux", "x86_64") => Ok("bakkutteh-linux.zip"),
+        ("macos", "aarch64") => Ok("bakkutteh-arm64.zip"),
+        (os, arch) => Err(anyhow!(tr_args(
+            "unsupported-platform",
+            &[("os", os.to_string()), ("arch", arch.to_string())]
+        ))),
+    }
+}
+
+/// Parse the version of a release tag which may be prefixed by a `v`
+///
+/// # Arguments
+///
+/// * `tag` - &str
+fn parse_tag(tag: &str) -> Result<Version> {
+    Version::parse(tag.trim_start_matches('v')).map_err(|err| {
+        anyhow!(tr_args(
+            "invalid-release-version",
+            &[("tag", tag.to_string()), ("error", err.to_string())]
+        ))
+    })
+}
+
+fn download(release: &Release, name: &str) -> Result<Vec<u8>> {
+    let asset = release
+        .assets
+        .iter()
+        .find(|asset| asset.name == name)
+        .ok_or_else(|| {
+            anyhow!(tr_args(
+                "missing-release-asset",
+                &[
+                    ("tag", release.tag_name.clone()),
+                    ("name", name.to_string())
+                ]
+            ))
+        })?;
+
+    let content = ureq::get(&asset.browser_download_url)
+        .header("User-Agent", USER_AGENT)
+        .call()?
+        .body_mut()
+        .with_config()
+        .limit(MAX_ARTIFACT_SIZE)
+        .read_to_vec()?;
+
+    Ok(content)
+}
+
+/// Check the sha256 of the content against a checksum file written by `shasum` (e.g. `<digest>  <file>`)
+///
+/// # Arguments
+///
+/// * `content` - &[u8]
+/// * `checksum_file` - &str
+fn verify_checksum(content: &[u8], checksum_file: &str) -> Result<()> {
+    let expected = checksum_file
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| anyhow!(tr("empty-checksum")))?;
+
+    let digest = format!("{:x}", Sha256::digest(content));
+    match digest.eq_ignore_ascii_case(expected) {
+        true => Ok(()),
+        false => Err(anyhow!(tr_args(
+            "checksum-mismatch",
+            &[("expected", expected.to_string()), ("digest", digest)]
+        ))),
+    }
+}
+
+/// Write the new binary next to the current one and rename it so that the current binary is replaced at once
+///
+/// # Arguments
+///
+/// * `current` - &Path
+/// * `binary` - &[u8]
+fn replace_binary(current: &Path, binary: &[u8]) -> Result<()> {
+    let staged = current.with_extension("new");
+    fs::write(&staged, binary)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+
+    fs::rename(&staged, current).map_err(|err| {
+        let _ = fs::remove_file(&staged);
+        anyhow!(tr_args(
+            "replace-binary-failed",
+            &[
+                ("path", current.display().to_string()),
+                ("error", err.to_string())
+            ]
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_tag, verify_checksum};
+
+    #[test]
+    fn expect_to_parse_release_tag() {
+        assert_eq!(parse_tag("v0.3.0").unwrap().to_string(), "0.3.0");
+        assert!(parse_tag("0.2.10").unwrap() > parse_tag("0.2.9").unwrap());
+        assert!(parse_tag("latest").is_err());
+    }
+
+    #[test]
+    fn expect_to_verify_checksum() {
+        let digest = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert!(verify_checksum(b"hello", &format!("{digest}  bakkutteh-linux.zip\n")).is_ok());
+        assert!(verify_checksum(b"other", digest).is_err());
+        assert!(verify_checksum(b"hello", "").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+// Constant
+const CONFIG_DIR: &str = "bakkutteh";
+const CONFIG_FILE: &str = "config.yaml";
+const PLUGINS_DIR: &str = "plugins";
+// Suffix of a pattern matching every key starting with the pattern
+const WILDCARD: char = '*';
+
+/// Config of the CLI loaded from a YAML file
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Config {
+    pub propagation: Propagation,
+    // Refuse every mutating call, same as the --read-only option
+    pub read_only: bool,
+    // Names of the env which can't be changed or added without --force
+    pub protected_env: Vec<String>,
+    // Run the plugins of the plugins directory, same as the --plugins option
+    pub plugins: bool,
+    // Directory of the plugins transforming the job. Defaults to ~/.config/bakkutteh/plugins
+    pub plugins_dir: Option<PathBuf>,
+}
+
+impl Config {
+    /// Get the directory of the plugins
+    pub fn plugins_dir(&self) -> Option<PathBuf> {
+        self.plugins_dir
+            .clone()
+            .or_else(|| dirs::config_dir().map(|dir| dir.join(CONFIG_DIR).join(PLUGINS_DIR)))
+    }
+}
+
+/// Propagation controls which labels & annotations of the source template are copied onto the manual job.
+/// A key is copied when it matches the allow list (or when the allow list is empty) and doesn't match the deny list.
+/// Patterns ending with `*` match every key starting with the pattern e.g: `argocd.argoproj.io/*`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Propagation {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+impl Propagation {
+    /// Check whether the label or annotation should be copied onto the manual job
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - &str
+    pub fn is_propagated(&self, key: &str) -> bool {
+        let allowed = self.allow.is_empty() || self.allow.iter().any(|p| matches(p, key));
+
+        allowed && !self.deny.iter().any(|p| matches(p, key))
+    }
+}
+
+fn matches(pattern: &str, key: &str) -> bool {
+    match pattern.strip_suffix(WILDCARD) {
+        Some(prefix) => key.starts_with(prefix),
+        None => pattern == key,
+    }
+}
+
+/// Load the config from the given path or from the default location (e.g. ~/.config/bakkutteh/config.yaml).
+/// The default config is used when no path is given and the default file does not exist
+///
+/// # Arguments
+///
+/// * `path` - Option<&str>
+pub fn load(path: Option<&str>) -> Result<Config> {
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => match dirs::config_dir().map(|dir| dir.join(CONFIG_DIR).join(CONFIG_FILE)) {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Config::default()),
+        },
+    };
+
+    let contents = fs::read_to_string(&path)
+        .map_err(|err| anyhow!("Unable to read the config {}: {err}", path.display()))?;
+
+    serde_yml::from_str(&contents)
+        .map_err(|err| anyhow!("Unable to parse the config {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+
+    #[test]
+    fn expect_to_filter_propagated_keys() {
+        let config: Config = serde_yml::from_str(
+            "propagation:\n  deny:\n    - argocd.argoproj.io/*\n    - app.kubernetes.io/instance\n",
+        )
+        .unwrap();
+
+        assert!(config.propagation.is_propagated("team"));
+        assert!(
+            !config
+                .propagation
+                .is_propagated("argocd.argoproj.io/tracking-id")
+        );
+        assert!(
+            !config
+                .propagation
+                .is_propagated("app.kubernetes.io/instance")
+        );
+
+        let config: Config = serde_yml::from_str("propagation:\n  allow:\n    - team\n").unwrap();
+        assert!(config.propagation.is_propagated("team"));
+        assert!(!config.propagation.is_propagated("owner"));
+    }
+}
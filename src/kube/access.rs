@@ -0,0 +1,76 @@
+use k8s_openapi::api::core::v1::PodSpec;
+
+/// Get the host-level accesses granted to the pods (host namespaces, hostPath volumes and privileged containers)
+///
+/// # Arguments
+///
+/// * `spec` - &PodSpec
+pub fn host_access(spec: &PodSpec) -> Vec<String> {
+    let mut accesses = [
+        ("hostNetwork", spec.host_network),
+        ("hostPID", spec.host_pid),
+        ("hostIPC", spec.host_ipc),
+    ]
+    .into_iter()
+    .filter(|(_, enabled)| enabled.unwrap_or_default())
+    .map(|(field, _)| format!("{field} is enabled"))
+    .collect::<Vec<_>>();
+
+    for volume in spec.volumes.iter().flatten() {
+        if let Some(host_path) = &volume.host_path {
+            accesses.push(format!(
+                "volume {} mounts the host path {}",
+                volume.name, host_path.path
+            ));
+        }
+    }
+
+    let containers = spec
+        .init_containers
+        .iter()
+        .flatten()
+        .chain(spec.containers.iter());
+
+    for container in containers {
+        let privileged = container
+            .security_context
+            .as_ref()
+            .and_then(|ctx| ctx.privileged)
+            .unwrap_or_default();
+
+        if privileged {
+            accesses.push(format!("container {} is privileged", container.name));
+        }
+    }
+
+    accesses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::host_access;
+    use k8s_openapi::api::core::v1::PodSpec;
+
+    #[test]
+    fn expect_to_detect_host_access() {
+        let spec: PodSpec = serde_json::from_value(serde_json::json!({
+            "hostNetwork": true,
+            "hostPID": false,
+            "volumes": [{ "name": "root", "hostPath": { "path": "/" } }],
+            "containers": [
+                { "name": "agent", "securityContext": { "privileged": true } },
+                { "name": "main" }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            host_access(&spec),
+            vec![
+                "hostNetwork is enabled",
+                "volume root mounts the host path /",
+                "container agent is privileged"
+            ]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use futures::future::{BoxFuture, poll_fn};
+use http::{Request, Response};
+use http_body_util::{BodyExt, Full};
+use kube::client::{Body, DynBody};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tokio::sync::Mutex;
+use tower::{BoxError, Layer, Service};
+
+// Constant
+// Level from which the bodies of the requests & responses are logged
+const BODY_LEVEL: u8 = 2;
+// Responses of watch requests are streamed and can't be buffered
+const WATCH_QUERY: &str = "watch=true";
+
+/// Layer logging each request sent to the API server on the stderr (method, path, status and duration).
+/// The bodies of the requests and responses are logged as well at the highest level
+#[derive(Debug, Clone, Copy)]
+pub struct ApiLogLayer(pub u8);
+
+impl<S> Layer<S> for ApiLogLayer {
+    type Service = ApiLog<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiLog {
+            inner: Arc::new(Mutex::new(inner)),
+            level: self.0,
+        }
+    }
+}
+
+/// ApiLog wraps the service stack of the client. The stack can't be cloned, it is shared between the requests
+/// and only locked while a request is dispatched
+pub struct ApiLog<S> {
+    inner: Arc<Mutex<S>>,
+    level: u8,
+}
+
+impl<S> Service<Request<Body>> for ApiLog<S>
+where
+    S: Service<Request<Body>, Response = Response<Box<DynBody>>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<BoxError>,
+{
+    type Response = Response<Box<DynBody>>;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // The readiness of the stack is checked when the request is dispatched
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let inner = Arc::clone(&self.inner);
+        let level = self.level;
+
+        Box::pin(async move {
+            let method = req.method().clone();
+            let uri = req.uri().clone();
+            let dump = level >= BODY_LEVEL;
+
+            let req = match dump {
+                true => {
+                    let (parts, body) = req.into_parts();
+                    let bytes = body.collect().await?.to_bytes();
+                    log_body(">", &bytes);
+
+                    Request::from_parts(parts, Body::from(bytes))
+                }
+                false => req,
+            };
+
+            let start = Instant::now();
+            let fut = {
+                let mut svc = inner.lock().await;
+                poll_fn(|cx| svc.poll_ready(cx)).await.map_err(Into::into)?;
+                svc.call(req)
+            };
+
+            let res = match fut.await.map_err(Into::into) {
+                Ok(res) => res,
+                Err(err) => {
+                    eprintln!(
+                        "[api] {method} {uri} failed after {}ms: {err}",
+                        start.elapsed().as_millis()
+                    );
+                    return Err(err);
+                }
+            };
+
+            eprintln!(
+                "[api] {method} {uri} {} {}ms",
+                res.status(),
+                start.elapsed().as_millis()
+            );
+
+            let is_watch = uri.query().is_some_and(|q| q.contains(WATCH_QUERY));
+            match dump && !is_watch {
+                true => {
+                    let (parts, body) = res.into_parts();
+                    let bytes = body.collect().await?.to_bytes();
+                    log_body("<", &bytes);
+
+                    let body: Box<DynBody> = Box::new(Full::new(bytes).map_err(Into::into));
+                    Ok(Response::from_parts(parts, body))
+                }
+                false => Ok(res),
+            }
+        })
+    }
+}
+
+fn log_body(direction: &str, body: &[u8]) {
+    if !body.is_empty() {
+        eprintln!("[api] {direction} {}", String::from_utf8_lossy(body));
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::{Result, anyhow};
+use k8s_openapi::api::batch::v1::{JobSpec, JobTemplateSpec};
+use k8s_openapi::api::core::v1::{PodSpec, PodTemplateSpec};
+use kube::api::GroupVersionKind;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+// Constant
+// Containers of the Argo templates are usually unnamed
+const CONTAINER_NAME: &str = "main";
+
+/// Get the kind of the Argo CronWorkflow
+pub fn cron_workflow() -> GroupVersionKind {
+    GroupVersionKind::gvk("argoproj.io", "v1alpha1", "CronWorkflow")
+}
+
+/// Convert a container template of the CronWorkflow into the template of a job. The entrypoint is used when it's a
+/// container template, otherwise the first container template of the workflow is used. The parameters of the
+/// workflow ({{inputs.parameters.x}}) aren't resolved
+///
+/// # Arguments
+///
+/// * `value` - &Value (the CronWorkflow)
+pub fn template_spec(value: &Value) -> Result<JobTemplateSpec> {
+    let workflow = value
+        .pointer("/spec/workflowSpec")
+        .ok_or_else(|| anyhow!("The CronWorkflow has no spec.workflowSpec"))?;
+    let entrypoint = workflow.get("entrypoint").and_then(Value::as_str);
+
+    // Comparing with the entrypoint puts it first, the first template is kept otherwise
+    let template = workflow
+        .get("templates")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|template| template.get("container").is_some())
+        .min_by_key(|template| template.get("name").and_then(Value::as_str) != entrypoint)
+        .ok_or_else(|| anyhow!("The workflow has no container template"))?;
+
+    let mut container = template["container"].clone();
+    if container.get("name").is_none() {
+        container["name"] = Value::from(CONTAINER_NAME);
+    }
+
+    let pod_spec = PodSpec {
+        containers: vec![serde_json::from_value(container)?],
+        restart_policy: Some("Never".to_string()),
+        service_account_name: field(&[template, workflow], "serviceAccountName")?,
+        node_selector: field(&[template, workflow], "nodeSelector")?,
+        tolerations: field(&[template, workflow], "tolerations")?,
+        image_pull_secrets: field(&[workflow], "imagePullSecrets")?,
+        volumes: field(&[workflow], "volumes")?,
+        ..Default::default()
+    };
+
+    let metadata = field(&[template], "metadata")?;
+    Ok(JobTemplateSpec {
+        metadata: metadata.clone(),
+        spec: Some(JobSpec {
+            template: PodTemplateSpec {
+                metadata,
+                spec: Some(pod_spec),
+            },
+            ..Default::default()
+        }),
+    })
+}
+
+/// Get the field from the first object defining it (e.g. the template then the workflow)
+///
+/// # Arguments
+///
+/// * `objects` - &[&Value]
+/// * `key` - &str
+fn field<T: DeserializeOwned>(objects: &[&Value], key: &str) -> Result<Option<T>> {
+    objects
+        .iter()
+        .find_map(|object| object.get(key))
+        .map(|value| serde_json::from_value(value.clone()))
+        .transpose()
+        .map_err(|err| anyhow!("Invalid {key} in the workflow: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::template_spec;
+    use serde_json::json;
+
+    #[test]
+    fn expect_to_convert_container_template() {
+        let cron_workflow = json!({
+            "spec": {
+                "schedule": "0 0 * * *",
+                "workflowSpec": {
+                    "entrypoint": "pipeline",
+                    "serviceAccountName": "reports",
+                    "templates": [
+                        { "name": "pipeline", "steps": [[{ "name": "export", "template": "export" }]] },
+                        {
+                            "name": "export",
+                            "nodeSelector": { "pool": "batch" },
+                            "container": { "image": "exporter:1.0", "args": ["--all"] }
+                        }
+                    ]
+                }
+            }
+        });
+
+        let spec = template_spec(&cron_workflow)
+            .unwrap()
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+
+        assert_eq!(spec.containers[0].name, "main");
+        assert_eq!(spec.containers[0].image.as_deref(), Some("exporter:1.0"));
+        assert_eq!(spec.service_account_name.as_deref(), Some("reports"));
+        assert_eq!(
+            spec.node_selector.unwrap().get("pool").map(String::as_str),
+            Some("batch")
+        );
+        assert_eq!(spec.restart_policy.as_deref(), Some("Never"));
+    }
+}
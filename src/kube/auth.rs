@@ -0,0 +1,83 @@
+use crate::cli::i18n::tr_args;
+use kube::{Error, client::AuthError, config::Kubeconfig};
+
+// Status code returned by the API server when the credentials are rejected
+const UNAUTHORIZED: u16 = 401;
+// Commands used to refresh the credentials of the common exec plugins
+const LOGIN_HINTS: [(&str, &str); 4] = [
+    ("aws", "aws sso login"),
+    ("gke-gcloud-auth-plugin", "gcloud auth login"),
+    ("gcloud", "gcloud auth login"),
+    ("kubelogin", "kubectl oidc-login setup"),
+];
+
+/// Check whether the error is caused by rejected or expired credentials, or by an exec plugin which failed to run
+///
+/// # Arguments
+///
+/// * `err` - &Error
+pub fn is_auth_error(err: &Error) -> bool {
+    match err {
+        Error::Api(resp) => resp.code == UNAUTHORIZED,
+        Error::Auth(_) => true,
+        Error::Service(err) => err.is::<AuthError>(),
+        _ => false,
+    }
+}
+
+/// Get the command of the exec plugin used by the user of the current context of the kubeconfig if any
+pub fn exec_command() -> Option<String> {
+    let config = Kubeconfig::read().ok()?;
+    let current = config.current_context.as_deref()?;
+    let user = config
+        .contexts
+        .iter()
+        .find(|ctx| ctx.name == current)?
+        .context
+        .as_ref()?
+        .user
+        .clone()?;
+
+    config
+        .auth_infos
+        .into_iter()
+        .find(|info| info.name == user)?
+        .auth_info?
+        .exec?
+        .command
+}
+
+/// Explain how to refresh the credentials of the exec plugin
+///
+/// # Arguments
+///
+/// * `command` - &str
+pub fn hint(command: &str) -> String {
+    // The command may be a path e.g: /usr/local/bin/aws
+    let binary = command.rsplit('/').next().unwrap_or(command);
+
+    match LOGIN_HINTS
+        .iter()
+        .find(|(name, _)| binary.starts_with(name))
+    {
+        Some((_, login)) => tr_args("exec-login-hint", &[("login", login.to_string())]),
+        None => tr_args("exec-run-hint", &[("command", command.to_string())]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hint;
+
+    #[test]
+    fn expect_to_hint_login_command() {
+        assert_eq!(
+            hint("/usr/local/bin/aws"),
+            "try to log in again with `aws sso login`"
+        );
+        assert_eq!(
+            hint("my-plugin"),
+            "make sure that `my-plugin` can be run and that you're logged in"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use super::spec;
+use k8s_openapi::api::batch::v1::JobSpec;
+use k8s_openapi::apimachinery::pkg::version::Info;
+use std::fmt;
+
+// Constant
+const INDEXED: &str = "Indexed";
+
+/// Version of the API server
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl Version {
+    /// Parse the version of the API server. Some providers suffix the minor version (e.g. `27+` on EKS)
+    ///
+    /// # Arguments
+    ///
+    /// * `info` - &Info
+    pub fn parse(info: &Info) -> Option<Self> {
+        let number = |value: &str| {
+            value
+                .trim_end_matches(|c: char| !c.is_ascii_digit())
+                .parse::<u32>()
+                .ok()
+        };
+
+        Some(Self {
+            major: number(&info.major)?,
+            minor: number(&info.minor)?,
+        })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Feature of the job which isn't supported by the version of the API server
+#[derive(Debug, PartialEq)]
+pub struct Skew {
+    pub feature: &'static str,
+    pub since: Version,
+    // Whether the feature was removed from the spec. Features changing the behavior of the job can't be removed
+    pub removed: bool,
+}
+
+/// Feature of the job along with the version from which it's enabled by default
+struct Feature {
+    name: &'static str,
+    since: Version,
+    used: fn(&JobSpec) -> bool,
+    // Remove the feature from the spec when the job can still run without it
+    remove: Option<fn(&mut JobSpec)>,
+}
+
+const FEATURES: [Feature; 4] = [
+    Feature {
+        name: "suspend",
+        since: Version {
+            major: 1,
+            minor: 22,
+        },
+        used: |spec| spec.suspend.is_some_and(|suspend| suspend),
+        remove: None,
+    },
+    Feature {
+        name: "completionMode: Indexed",
+        since: Version {
+            major: 1,
+            minor: 22,
+        },
+        used: |spec| spec.completion_mode.as_deref() == Some(INDEXED),
+        remove: None,
+    },
+    Feature {
+        name: "podFailurePolicy",
+        since: Version {
+            major: 1,
+            minor: 26,
+        },
+        used: |spec| spec.pod_failure_policy.is_some(),
+        remove: Some(|spec| spec.pod_failure_policy = None),
+    },
+    Feature {
+        name: "native sidecars",
+        since: Version {
+            major: 1,
+            minor: 29,
+        },
+        used: |job_spec| {
+            job_spec
+                .template
+                .spec
+                .as_ref()
+                .is_some_and(|pod_spec| spec::sidecars(pod_spec).next().is_some())
+        },
+        remove: None,
+    },
+];
+
+/// Get the features of the job which aren't supported by the version of the API server. The features which the
+/// job can run without are removed from the spec
+///
+/// # Arguments
+///
+/// * `spec` - &mut JobSpec
+/// * `version` - Version
+pub fn downgrade(spec: &mut JobSpec, version: Version) -> Vec<Skew> {
+    let mut skews = Vec::new();
+    for feature in FEATURES.iter() {
+        if version >= feature.since || !(feature.used)(spec) {
+            continue;
+        }
+
+        if let Some(remove) = feature.remove {
+            remove(spec);
+        }
+
+        skews.push(Skew {
+            feature: feature.name,
+            since: feature.since,
+            removed: feature.remove.is_some(),
+        });
+    }
+
+    skews
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Version, downgrade};
+    use k8s_openapi::api::batch::v1::{JobSpec, PodFailurePolicy};
+    use k8s_openapi::apimachinery::pkg::version::Info;
+
+    #[test]
+    fn expect_to_parse_suffixed_version() {
+        let info = Info {
+            major: "1".to_string(),
+            minor: "27+".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Version::parse(&info),
+            Some(Version {
+                major: 1,
+                minor: 27
+            })
+        );
+    }
+
+    #[test]
+    fn expect_to_remove_unsupported_features() {
+        let mut spec = JobSpec {
+            suspend: Some(true),
+            pod_failure_policy: Some(PodFailurePolicy::default()),
+            ..Default::default()
+        };
+
+        let skews = downgrade(
+            &mut spec,
+            Version {
+                major: 1,
+                minor: 21,
+            },
+        );
+        assert_eq!(
+            skews
+                .iter()
+                .map(|skew| (skew.feature, skew.removed))
+                .collect::<Vec<_>>(),
+            vec![("suspend", false), ("podFailurePolicy", true)]
+        );
+        assert!(spec.pod_failure_policy.is_none());
+        assert_eq!(spec.suspend, Some(true));
+    }
+}
@@ -0,0 +1,159 @@
+use anyhow::{Result, anyhow};
+use k8s_openapi::api::batch::v1::{JobSpec, JobTemplateSpec};
+use k8s_openapi::api::core::v1::PodTemplateSpec;
+use kube::api::GroupVersionKind;
+use serde_json::Value;
+
+// Constant
+pub const DEFAULT_TEMPLATE_PATH: &str = ".spec.jobTemplate";
+// The jobs of a KEDA ScaledJob are created from the JobSpec of spec.jobTargetRef
+pub const SCALED_JOB_TEMPLATE_PATH: &str = ".spec.jobTargetRef";
+const JOB_RESTART_POLICIES: [&str; 2] = ["Never", "OnFailure"];
+
+/// Parse the kind of the custom resource given as group/version/kind (e.g. keda.sh/v1alpha1/ScaledJob). The group
+/// is omitted for the core API (e.g. v1/Pod)
+///
+/// # Arguments
+///
+/// * `input` - &str
+pub fn parse_kind(input: &str) -> Result<GroupVersionKind> {
+    let parts = input.split('/').collect::<Vec<_>>();
+    match parts.as_slice() {
+        [group, version, kind] if !group.is_empty() && !version.is_empty() && !kind.is_empty() => {
+            Ok(GroupVersionKind::gvk(group, version, kind))
+        }
+        [version, kind] if !version.is_empty() && !kind.is_empty() => {
+            Ok(GroupVersionKind::gvk("", version, kind))
+        }
+        _ => Err(anyhow!(
+            "Invalid kind {input}, expected group/version/kind (e.g. keda.sh/v1alpha1/ScaledJob)"
+        )),
+    }
+}
+
+/// Get the kind of the KEDA ScaledJob
+pub fn scaled_job() -> GroupVersionKind {
+    GroupVersionKind::gvk("keda.sh", "v1alpha1", "ScaledJob")
+}
+
+/// Select the value at the JSONPath. Only the fields and the indexes of arrays are supported, the braces of kubectl
+/// are optional (e.g. .spec.jobTemplate, {.spec.templates[0]})
+///
+/// # Arguments
+///
+/// * `value` - &Value
+/// * `path` - &str
+pub fn select<'a>(value: &'a Value, path: &str) -> Result<&'a Value> {
+    let trimmed = path
+        .trim()
+        .trim_start_matches('{')
+        .trim_end_matches('}')
+        .trim_start_matches('$');
+
+    let mut current = value;
+    for segment in trimmed.split('.').filter(|segment| !segment.is_empty()) {
+        let (field, indexes) = match segment.find('[') {
+            Some(idx) => segment.split_at(idx),
+            None => (segment, ""),
+        };
+
+        if !field.is_empty() {
+            current = current
+                .get(field)
+                .ok_or_else(|| anyhow!("Unable to find {field} of the path {path}"))?;
+        }
+
+        for index in indexes.split('[').filter(|index| !index.is_empty()) {
+            let index = index
+                .trim_end_matches(']')
+                .parse::<usize>()
+                .map_err(|_| anyhow!("Invalid index [{index} of the path {path}"))?;
+            current = current
+                .get(index)
+                .ok_or_else(|| anyhow!("Unable to find the index {index} of the path {path}"))?;
+        }
+    }
+
+    Ok(current)
+}
+
+/// Build the template of the job from the value selected in the custom resource. The value can be the template of
+/// a job (spec.template), the spec of a job (template) or the template of a pod (spec.containers)
+///
+/// # Arguments
+///
+/// * `value` - &Value
+pub fn template_spec(value: &Value) -> Result<JobTemplateSpec> {
+    if value.pointer("/spec/template").is_some() {
+        return Ok(serde_json::from_value(value.clone())?);
+    }
+
+    if value.get("template").is_some() {
+        let spec: JobSpec = serde_json::from_value(value.clone())?;
+        return Ok(JobTemplateSpec {
+            metadata: spec.template.metadata.clone(),
+            spec: Some(spec),
+        });
+    }
+
+    if value.pointer("/spec/containers").is_some() {
+        let mut template: PodTemplateSpec = serde_json::from_value(value.clone())?;
+        // Pod templates of other workloads are usually restarted, the job runs once
+        if let Some(spec) = template.spec.as_mut()
+            && !spec
+                .restart_policy
+                .as_deref()
+                .is_some_and(|policy| JOB_RESTART_POLICIES.contains(&policy))
+        {
+            spec.restart_policy = Some("Never".to_string());
+        }
+
+        return Ok(JobTemplateSpec {
+            metadata: template.metadata.clone(),
+            spec: Some(JobSpec {
+                template,
+                ..Default::default()
+            }),
+        });
+    }
+
+    Err(anyhow!(
+        "The selected value isn't the template of a job or of a pod"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_kind, select, template_spec};
+    use serde_json::json;
+
+    #[test]
+    fn expect_to_select_template_of_custom_resource() {
+        let gvk = parse_kind("keda.sh/v1alpha1/ScaledJob").unwrap();
+        assert_eq!(
+            (gvk.group.as_str(), gvk.kind.as_str()),
+            ("keda.sh", "ScaledJob")
+        );
+        assert!(parse_kind("ScaledJob").is_err());
+
+        let object = json!({
+            "spec": {
+                "templates": [{
+                    "spec": {
+                        "restartPolicy": "Always",
+                        "containers": [{ "name": "main", "image": "busybox" }]
+                    }
+                }]
+            }
+        });
+
+        let value = select(&object, "{.spec.templates[0]}").unwrap();
+        let spec = template_spec(value).unwrap().spec.unwrap();
+        assert_eq!(
+            spec.template.spec.unwrap().restart_policy.as_deref(),
+            Some("Never")
+        );
+
+        assert!(select(&object, ".spec.jobTemplate").is_err());
+    }
+}
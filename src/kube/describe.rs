@@ -0,0 +1,271 @@
+use crate::kube::job;
+use jiff::Timestamp;
+use k8s_openapi::api::{
+    batch::v1::Job,
+    core::v1::{ContainerState, Event, Pod},
+};
+use std::fmt::Write;
+
+// Constant
+// Only the last events are printed, like kubectl describe
+const MAX_EVENTS: usize = 10;
+const INDENT: &str = "  ";
+
+/// Render a summary of the job similar to kubectl describe: its conditions, the phases of its pods and the last
+/// events of the job and of its pods
+///
+/// # Arguments
+///
+/// * `job` - &Job
+/// * `source` - Option<&str>
+/// * `pods` - &[Pod]
+/// * `events` - &[Event]
+/// * `now` - Timestamp
+pub fn render(
+    job: &Job,
+    source: Option<&str>,
+    pods: &[Pod],
+    events: &[Event],
+    now: Timestamp,
+) -> String {
+    let mut output = String::new();
+    let fields = [
+        ("Name", job.metadata.name.clone().unwrap_or_default()),
+        (
+            "Namespace",
+            job.metadata.namespace.clone().unwrap_or_default(),
+        ),
+        ("Source", source.unwrap_or("-").to_string()),
+        ("Status", job::job_status(job).to_string()),
+        ("Age", job::age(job, now)),
+    ];
+    for (field, value) in fields {
+        let _ = writeln!(output, "{:<11}{value}", format!("{field}:"));
+    }
+
+    let conditions = job
+        .status
+        .as_ref()
+        .and_then(|status| status.conditions.as_ref())
+        .into_iter()
+        .flatten()
+        .map(|condition| {
+            [
+                condition.type_.clone(),
+                condition.status.clone(),
+                condition.reason.clone().unwrap_or_default(),
+                condition.message.clone().unwrap_or_default(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    section(
+        &mut output,
+        "Conditions",
+        ["TYPE", "STATUS", "REASON", "MESSAGE"],
+        conditions,
+    );
+
+    let pods = pods
+        .iter()
+        .map(|pod| {
+            [
+                pod.metadata.name.clone().unwrap_or_default(),
+                pod.status
+                    .as_ref()
+                    .and_then(|status| status.phase.clone())
+                    .unwrap_or_default(),
+                pod_reason(pod).unwrap_or_default(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    section(&mut output, "Pods", ["NAME", "PHASE", "REASON"], pods);
+
+    let mut events = events
+        .iter()
+        .map(|event| (event_time(event), event))
+        .collect::<Vec<_>>();
+    events.sort_by_key(|(time, _)| *time);
+    let skipped = events.len().saturating_sub(MAX_EVENTS);
+    let events = events
+        .into_iter()
+        .skip(skipped)
+        .map(|(time, event)| {
+            let object = &event.involved_object;
+            [
+                event.type_.clone().unwrap_or_default(),
+                event.reason.clone().unwrap_or_default(),
+                time.map(|time| job::elapsed(time, now))
+                    .unwrap_or_else(|| "-".to_string()),
+                format!(
+                    "{}/{}",
+                    object.kind.as_deref().unwrap_or_default().to_lowercase(),
+                    object.name.as_deref().unwrap_or_default()
+                ),
+                event.message.clone().unwrap_or_default().trim().to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    section(
+        &mut output,
+        "Events",
+        ["TYPE", "REASON", "AGE", "OBJECT", "MESSAGE"],
+        events,
+    );
+
+    output
+}
+
+/// Get why the pod isn't running: the reason of the first waiting or terminated container, otherwise the reason of
+/// the pod (e.g. Unschedulable)
+///
+/// # Arguments
+///
+/// * `pod` - &Pod
+fn pod_reason(pod: &Pod) -> Option<String> {
+    let status = pod.status.as_ref()?;
+    let containers = status
+        .init_container_statuses
+        .iter()
+        .flatten()
+        .chain(status.container_statuses.iter().flatten());
+
+    for container in containers {
+        let (reason, message) = match &container.state {
+            Some(ContainerState {
+                waiting: Some(waiting),
+                ..
+            }) => (waiting.reason.clone(), waiting.message.clone()),
+            Some(ContainerState {
+                terminated: Some(terminated),
+                ..
+            }) if terminated.exit_code != 0 => {
+                (terminated.reason.clone(), terminated.message.clone())
+            }
+            _ => continue,
+        };
+
+        return Some(match (reason, message) {
+            (Some(reason), Some(message)) => format!("{reason}: {}", message.trim()),
+            (reason, message) => reason.or(message).unwrap_or_default(),
+        });
+    }
+
+    status
+        .conditions
+        .iter()
+        .flatten()
+        .find(|condition| condition.type_ == "PodScheduled" && condition.status != "True")
+        .and_then(|condition| condition.reason.clone().or(condition.message.clone()))
+        .or(status.reason.clone())
+}
+
+/// Get the last time that the event was seen
+///
+/// # Arguments
+///
+/// * `event` - &Event
+fn event_time(event: &Event) -> Option<Timestamp> {
+    event
+        .last_timestamp
+        .as_ref()
+        .map(|time| time.0)
+        .or(event.event_time.as_ref().map(|time| time.0))
+        .or(event.first_timestamp.as_ref().map(|time| time.0))
+}
+
+/// Write the section as a table whose columns are aligned. The section says <none> when it has no row
+///
+/// # Arguments
+///
+/// * `output` - &mut String
+/// * `title` - &str
+/// * `header` - [&str; N]
+/// * `rows` - Vec<[String; N]>
+fn section<const N: usize>(
+    output: &mut String,
+    title: &str,
+    header: [&str; N],
+    rows: Vec<[String; N]>,
+) {
+    let _ = writeln!(output, "\n{title}:");
+    if rows.is_empty() {
+        let _ = writeln!(output, "{INDENT}<none>");
+        return;
+    }
+
+    let header = header.map(String::from);
+    let widths = std::iter::once(&header)
+        .chain(&rows)
+        .fold([0; N], |mut widths, row| {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+            widths
+        });
+
+    for row in std::iter::once(&header).chain(&rows) {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(INDENT);
+        let _ = writeln!(output, "{INDENT}{}", line.trim_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+    use jiff::Timestamp;
+    use k8s_openapi::api::{batch::v1::Job, core::v1::Event, core::v1::Pod};
+    use serde_json::json;
+
+    #[test]
+    fn expect_to_render_pending_job() {
+        let job: Job = serde_json::from_value(json!({
+            "metadata": {
+                "name": "momo-manual",
+                "namespace": "default",
+                "creationTimestamp": "2025-01-01T01:55:00Z"
+            },
+            "status": { "active": 1 }
+        }))
+        .unwrap();
+        let pod: Pod = serde_json::from_value(json!({
+            "metadata": { "name": "momo-manual-x7k2p" },
+            "status": {
+                "phase": "Pending",
+                "containerStatuses": [{
+                    "name": "main",
+                    "image": "momo:1.0",
+                    "imageID": "",
+                    "ready": false,
+                    "restartCount": 0,
+                    "state": { "waiting": { "reason": "ImagePullBackOff", "message": "Back-off pulling image" } }
+                }]
+            }
+        }))
+        .unwrap();
+        let event: Event = serde_json::from_value(json!({
+            "metadata": { "name": "momo-manual-x7k2p.1" },
+            "involvedObject": { "kind": "Pod", "name": "momo-manual-x7k2p" },
+            "type": "Warning",
+            "reason": "Failed",
+            "message": "Failed to pull image momo:1.0",
+            "lastTimestamp": "2025-01-01T01:59:30Z"
+        }))
+        .unwrap();
+
+        let now: Timestamp = "2025-01-01T02:00:00Z".parse().unwrap();
+        let output = render(&job, Some("momo"), &[pod], &[event], now);
+
+        assert!(output.contains("Status:    Running"));
+        assert!(output.contains("Age:       5m"));
+        assert!(output.contains("Conditions:\n  <none>"));
+        assert!(output.contains("ImagePullBackOff: Back-off pulling image"));
+        assert!(output.contains(
+            "Warning  Failed  30s  pod/momo-manual-x7k2p  Failed to pull image momo:1.0"
+        ));
+    }
+}
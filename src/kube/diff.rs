@@ -0,0 +1,187 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use std::fmt::Write;
+
+// Constant
+// Number of unchanged lines displayed around the changes, same as diff -u
+const CONTEXT: usize = 3;
+
+/// Line of the diff along with its index in the original and in the modified text
+#[derive(Debug, Clone, Copy)]
+struct Op<'a> {
+    kind: char,
+    line: &'a str,
+    old: usize,
+    new: usize,
+}
+
+/// Render the unified diff between the YAML of the original and of the modified value. None is returned when
+/// they're identical
+///
+/// # Arguments
+///
+/// * `original` - &T
+/// * `modified` - &T
+/// * `labels` - (&str, &str) (names of the original & modified values in the header)
+pub fn unified<T: Serialize>(
+    original: &T,
+    modified: &T,
+    labels: (&str, &str),
+) -> Result<Option<String>> {
+    let original = serde_yml::to_string(original)?;
+    let modified = serde_yml::to_string(modified)?;
+    if original == modified {
+        return Ok(None);
+    }
+
+    let ops = ops(
+        &original.lines().collect::<Vec<_>>(),
+        &modified.lines().collect::<Vec<_>>(),
+    );
+
+    let mut diff = format!("--- {}\n+++ {}\n", labels.0, labels.1);
+    for (start, end) in hunks(&ops) {
+        let hunk = &ops[start..end];
+        let old_len = hunk.iter().filter(|op| op.kind != '+').count();
+        let new_len = hunk.iter().filter(|op| op.kind != '-').count();
+        // An empty range starts at the line before it
+        let old_start = hunk[0].old + usize::from(old_len > 0);
+        let new_start = hunk[0].new + usize::from(new_len > 0);
+
+        let _ = writeln!(diff, "@@ -{old_start},{old_len} +{new_start},{new_len} @@");
+        for op in hunk {
+            let _ = writeln!(diff, "{}{}", op.kind, op.line);
+        }
+    }
+
+    Ok(Some(diff))
+}
+
+/// Color the lines of the unified diff: removals in red, additions in green and the hunk headers in cyan
+///
+/// # Arguments
+///
+/// * `diff` - &str
+pub fn colorize(diff: &str) -> String {
+    diff.lines()
+        .map(|line| match line {
+            line if line.starts_with("---") || line.starts_with("+++") => line.bold().to_string(),
+            line if line.starts_with("@@") => line.cyan().to_string(),
+            line if line.starts_with('-') => line.red().to_string(),
+            line if line.starts_with('+') => line.green().to_string(),
+            line => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Get the lines removed, added and kept from the longest common subsequence of the lines
+///
+/// # Arguments
+///
+/// * `original` - &[&str]
+/// * `modified` - &[&str]
+fn ops<'a>(original: &[&'a str], modified: &[&'a str]) -> Vec<Op<'a>> {
+    let (n, m) = (original.len(), modified.len());
+    // Length of the longest common subsequence of the suffixes starting at i & j
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = match original[i] == modified[j] {
+                true => lcs[i + 1][j + 1] + 1,
+                false => lcs[i + 1][j].max(lcs[i][j + 1]),
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut ops = Vec::new();
+    while i < n || j < m {
+        let (kind, line) = match (original.get(i), modified.get(j)) {
+            (Some(before), Some(after)) if before == after => (' ', *before),
+            (Some(before), Some(_)) if lcs[i + 1][j] >= lcs[i][j + 1] => ('-', *before),
+            (Some(before), None) => ('-', *before),
+            (_, Some(after)) => ('+', *after),
+            (None, None) => break,
+        };
+
+        ops.push(Op {
+            kind,
+            line,
+            old: i,
+            new: j,
+        });
+        match kind {
+            ' ' => (i, j) = (i + 1, j + 1),
+            '-' => i += 1,
+            _ => j += 1,
+        }
+    }
+
+    ops
+}
+
+/// Get the ranges of the operations displayed in each hunk. The changes closer than twice the context are merged
+/// in the same hunk
+///
+/// # Arguments
+///
+/// * `ops` - &[Op]
+fn hunks(ops: &[Op]) -> Vec<(usize, usize)> {
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (idx, _) in ops.iter().enumerate().filter(|(_, op)| op.kind != ' ') {
+        let start = idx.saturating_sub(CONTEXT);
+        let end = (idx + CONTEXT + 1).min(ops.len());
+
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::unified;
+    use serde_json::json;
+
+    #[test]
+    fn expect_to_render_unified_diff() {
+        let original = json!({
+            "a": 1, "b": 2, "c": 3, "d": 4, "e": 5, "f": 6, "g": 7, "h": 8, "i": 9
+        });
+        let mut modified = original.clone();
+        modified["b"] = json!(20);
+        modified["j"] = json!(10);
+
+        let diff = unified(&original, &modified, ("momo", "momo-manual"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            diff,
+            [
+                "--- momo",
+                "+++ momo-manual",
+                "@@ -1,5 +1,5 @@",
+                " a: 1",
+                "-b: 2",
+                "+b: 20",
+                " c: 3",
+                " d: 4",
+                " e: 5",
+                "@@ -7,3 +7,4 @@",
+                " g: 7",
+                " h: 8",
+                " i: 9",
+                "+j: 10",
+                "",
+            ]
+            .join("\n")
+        );
+
+        assert!(unified(&original, &original, ("a", "b")).unwrap().is_none());
+    }
+}
@@ -0,0 +1,198 @@
+use anyhow::{Result, anyhow};
+use k8s_openapi::api::authorization::v1::{
+    ResourceAttributes, SelfSubjectAccessReview, SelfSubjectAccessReviewSpec,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::APIGroupList;
+use kube::{
+    Client, Config,
+    api::{Api, PostParams},
+};
+
+// Constant
+// Group versions used by the tool
+const REQUIRED_VERSIONS: [&str; 2] = ["batch/v1", "apps/v1"];
+const METRICS_GROUP: &str = "metrics.k8s.io";
+// Permissions needed to dispatch a job (verb, group, resource)
+const PERMISSIONS: [(&str, &str, &str); 7] = [
+    ("list", "batch", "cronjobs"),
+    ("get", "batch", "cronjobs"),
+    ("create", "batch", "jobs"),
+    ("delete", "batch", "jobs"),
+    ("list", "", "pods"),
+    ("get", "", "configmaps"),
+    ("list", "apps", "deployments"),
+];
+
+/// Result of a check of the environment
+#[derive(Debug)]
+pub struct Check {
+    pub name: String,
+    pub result: Result<String>,
+}
+
+impl Check {
+    fn new<S: Into<String>>(name: S, result: Result<String>) -> Self {
+        Self {
+            name: name.into(),
+            result,
+        }
+    }
+}
+
+/// Check the kubeconfig, the reachability of the cluster, the API versions, the permissions needed to dispatch
+/// a job in the namespace and the availability of the metrics-server. The checks depending on the cluster are
+/// skipped when the kubeconfig is invalid
+///
+/// # Arguments
+///
+/// * `namespace` - &str
+pub async fn diagnose(namespace: &str) -> Vec<Check> {
+    let config = match Config::infer().await {
+        Ok(config) => config,
+        Err(err) => return vec![Check::new("kubeconfig", Err(err.into()))],
+    };
+
+    let mut checks = vec![Check::new(
+        "kubeconfig",
+        Ok(format!("cluster {}", config.cluster_url)),
+    )];
+
+    let client = match Client::try_from(config) {
+        Ok(client) => client,
+        Err(err) => {
+            checks.push(Check::new("client", Err(err.into())));
+            return checks;
+        }
+    };
+
+    match client.apiserver_version().await {
+        Ok(info) => checks.push(Check::new(
+            "cluster reachability",
+            Ok(format!("Kubernetes {}", info.git_version)),
+        )),
+        Err(err) => {
+            checks.push(Check::new("cluster reachability", Err(err.into())));
+            return checks;
+        }
+    }
+
+    match client.list_api_groups().await {
+        Ok(groups) => {
+            let missing = missing_versions(&groups, &REQUIRED_VERSIONS);
+            checks.push(Check::new(
+                "API versions",
+                match missing.is_empty() {
+                    true => Ok(REQUIRED_VERSIONS.join(", ")),
+                    false => Err(anyhow!("{} not served", missing.join(", "))),
+                },
+            ));
+
+            let metrics = groups
+                .groups
+                .iter()
+                .any(|group| group.name == METRICS_GROUP);
+            checks.push(Check::new(
+                "metrics-server",
+                match metrics {
+                    true => Ok(format!("{METRICS_GROUP} available")),
+                    false => Err(anyhow!("{METRICS_GROUP} isn't served by the cluster")),
+                },
+            ));
+        }
+        Err(err) => checks.push(Check::new("API versions", Err(err.into()))),
+    }
+
+    for (verb, group, resource) in PERMISSIONS {
+        let result = can_i(&client, namespace, verb, group, resource)
+            .await
+            .and_then(|allowed| match allowed {
+                true => Ok("allowed".to_string()),
+                false => Err(anyhow!("denied")),
+            });
+
+        checks.push(Check::new(
+            format!("{verb} {resource} in {namespace}"),
+            result,
+        ));
+    }
+
+    checks
+}
+
+/// Ask the API server whether the current user is allowed to perform the verb on the resource
+///
+/// # Arguments
+///
+/// * `client` - &Client
+/// * `namespace` - &str
+/// * `verb` - &str
+/// * `group` - &str
+/// * `resource` - &str
+async fn can_i(
+    client: &Client,
+    namespace: &str,
+    verb: &str,
+    group: &str,
+    resource: &str,
+) -> Result<bool> {
+    let review = SelfSubjectAccessReview {
+        spec: SelfSubjectAccessReviewSpec {
+            resource_attributes: Some(ResourceAttributes {
+                namespace: Some(namespace.to_string()),
+                verb: Some(verb.to_string()),
+                group: Some(group.to_string()),
+                resource: Some(resource.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let api: Api<SelfSubjectAccessReview> = Api::all(client.clone());
+    let review = api.create(&PostParams::default(), &review).await?;
+
+    Ok(review.status.is_some_and(|status| status.allowed))
+}
+
+/// Get the group versions which aren't served by the API server
+///
+/// # Arguments
+///
+/// * `groups` - &APIGroupList
+/// * `required` - &[&'a str]
+fn missing_versions<'a>(groups: &APIGroupList, required: &[&'a str]) -> Vec<&'a str> {
+    required
+        .iter()
+        .filter(|version| {
+            !groups
+                .groups
+                .iter()
+                .flat_map(|group| group.versions.iter())
+                .any(|served| served.group_version == **version)
+        })
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::missing_versions;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::APIGroupList;
+
+    #[test]
+    fn expect_to_find_missing_versions() {
+        let groups: APIGroupList = serde_json::from_value(serde_json::json!({
+            "groups": [{
+                "name": "batch",
+                "versions": [{ "groupVersion": "batch/v1", "version": "v1" }]
+            }]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            missing_versions(&groups, &["batch/v1", "apps/v1"]),
+            vec!["apps/v1"]
+        );
+    }
+}
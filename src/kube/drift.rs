@@ -0,0 +1,160 @@
+use super::spec::SpecHandler;
+use anyhow::Result;
+use k8s_openapi::api::batch::v1::JobSpec;
+use k8s_openapi::api::core::v1::Container;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Get the env & resources which changed between the spec of a previous job and the current one
+///
+/// # Arguments
+///
+/// * `previous` - &JobSpec
+/// * `current` - &JobSpec
+pub fn drift(previous: &JobSpec, current: &JobSpec) -> Result<Vec<String>> {
+    let previous_envs = previous
+        .get_env()?
+        .into_iter()
+        .map(|container| (container.name, container.envs))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut changes = Vec::new();
+    for container in current.get_env()? {
+        let name = &container.name;
+        let current = stringify(&container.envs);
+        let previous = previous_envs.get(name).map(stringify).unwrap_or_default();
+
+        changes.extend(
+            compare(&previous, &current)
+                .into_iter()
+                .map(|change| format!("{name}: env {change}")),
+        );
+    }
+
+    let previous_containers = containers(previous);
+    for container in containers(current) {
+        let name = &container.name;
+        let previous = previous_containers
+            .iter()
+            .find(|c| &c.name == name)
+            .map(resources)
+            .unwrap_or_default();
+
+        changes.extend(
+            compare(&previous, &resources(container))
+                .into_iter()
+                .map(|change| format!("{name}: {change}")),
+        );
+    }
+
+    Ok(changes)
+}
+
+fn stringify<V: ToString>(map: &BTreeMap<String, V>) -> BTreeMap<String, String> {
+    map.iter()
+        .map(|(key, value)| (key.to_owned(), value.to_string()))
+        .collect()
+}
+
+fn containers(spec: &JobSpec) -> &[Container] {
+    spec.template
+        .spec
+        .as_ref()
+        .map(|pod| pod.containers.as_slice())
+        .unwrap_or_default()
+}
+
+/// Get the requests & limits of the container keyed by e.g. `limits.cpu`
+///
+/// # Arguments
+///
+/// * `container` - &Container
+fn resources(container: &Container) -> BTreeMap<String, String> {
+    let Some(resources) = container.resources.as_ref() else {
+        return BTreeMap::new();
+    };
+
+    [
+        ("requests", &resources.requests),
+        ("limits", &resources.limits),
+    ]
+    .into_iter()
+    .flat_map(|(kind, quantities)| {
+        quantities
+            .iter()
+            .flatten()
+            .map(move |(name, quantity)| (format!("{kind}.{name}"), quantity.0.to_owned()))
+    })
+    .collect()
+}
+
+/// Describe the keys which were added, removed or changed
+///
+/// # Arguments
+///
+/// * `previous` - &BTreeMap<String, String>
+/// * `current` - &BTreeMap<String, String>
+fn compare(previous: &BTreeMap<String, String>, current: &BTreeMap<String, String>) -> Vec<String> {
+    previous
+        .keys()
+        .chain(current.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|key| match (previous.get(key), current.get(key)) {
+            (Some(before), Some(after)) if before != after => {
+                Some(format!("{key} changed: {before} -> {after}"))
+            }
+            (None, Some(after)) => Some(format!("{key} added: {after}")),
+            (Some(_), None) => Some(format!("{key} removed")),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::drift;
+    use k8s_openapi::api::batch::v1::JobSpec;
+
+    fn spec(env: serde_json::Value, cpu: &str) -> JobSpec {
+        serde_json::from_value(serde_json::json!({
+            "template": {
+                "spec": {
+                    "containers": [{
+                        "name": "main",
+                        "env": env,
+                        "resources": { "limits": { "cpu": cpu } }
+                    }]
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn expect_to_find_env_and_resources_drift() {
+        let previous = spec(
+            serde_json::json!([
+                { "name": "START", "value": "2025-01-01" },
+                { "name": "DEBUG", "value": "true" }
+            ]),
+            "500m",
+        );
+        let current = spec(
+            serde_json::json!([
+                { "name": "START", "value": "2025-02-01" },
+                { "name": "LIMIT", "value": "10" }
+            ]),
+            "1",
+        );
+
+        assert_eq!(
+            drift(&previous, &current).unwrap(),
+            vec![
+                "main: env DEBUG removed",
+                "main: env LIMIT added: 10",
+                "main: env START changed: 2025-01-01 -> 2025-02-01",
+                "main: limits.cpu changed: 500m -> 1",
+            ]
+        );
+    }
+}
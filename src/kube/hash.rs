@@ -0,0 +1,129 @@
+use anyhow::{Result, anyhow};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use k8s_openapi::api::batch::v1::Job;
+use serde::{Serialize, de::DeserializeOwned};
+use sha2::{Digest, Sha256};
+use std::io::{Read, Write};
+
+// Constant
+const ALGORITHM: &str = "sha256";
+
+/// Compute a stable hash of the value. The value is serialized as JSON which has a stable field order
+/// (struct fields are serialized in the order of declaration and maps are sorted)
+///
+/// # Arguments
+///
+/// * `value` - &T
+pub fn hash<T: Serialize>(value: &T) -> Result<String> {
+    let json = serde_json::to_vec(value)?;
+    let digest = Sha256::digest(&json);
+
+    Ok(format!("{ALGORITHM}:{digest:x}"))
+}
+
+/// Compute the hash of the spec of a job manifest (e.g. written with --dry-run-output-path), which is the one
+/// annotated on the job dispatched from it
+///
+/// # Arguments
+///
+/// * `manifest` - &str (YAML or JSON)
+pub fn manifest_hash(manifest: &str) -> Result<String> {
+    let job: Job =
+        serde_yml::from_str(manifest).map_err(|err| anyhow!("Invalid job manifest: {err}"))?;
+    let spec = job
+        .spec
+        .ok_or_else(|| anyhow!("The manifest doesn't define the spec of the job"))?;
+
+    hash(&spec)
+}
+
+/// Compress the JSON of the value with gzip and encode it in base64 so that it can be stored in an annotation.
+/// It can be decoded with `base64 -d | gunzip`
+///
+/// # Arguments
+///
+/// * `value` - &T
+pub fn compress<T: Serialize>(value: &T) -> Result<String> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&serde_json::to_vec(value)?)?;
+
+    Ok(STANDARD.encode(encoder.finish()?))
+}
+
+/// Decode a value compressed with [`compress`]
+///
+/// # Arguments
+///
+/// * `encoded` - &str
+pub fn decompress<T: DeserializeOwned>(encoded: &str) -> Result<T> {
+    let compressed = STANDARD.decode(encoded.trim())?;
+    let mut json = Vec::new();
+    GzDecoder::new(compressed.as_slice()).read_to_end(&mut json)?;
+
+    Ok(serde_json::from_slice(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compress, decompress, hash, manifest_hash};
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use flate2::read::GzDecoder;
+    use k8s_openapi::api::batch::v1::Job;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use std::io::Read;
+
+    #[test]
+    fn expect_hash_to_be_stable() {
+        let first = BTreeMap::from([("b", "2"), ("a", "1")]);
+        let second = BTreeMap::from([("a", "1"), ("b", "2")]);
+
+        assert_eq!(hash(&first).unwrap(), hash(&second).unwrap());
+        assert!(hash(&first).unwrap().starts_with("sha256:"));
+    }
+
+    #[test]
+    fn expect_manifest_hash_to_match_spec_hash() {
+        let job: Job = serde_json::from_value(json!({
+            "apiVersion": "batch/v1",
+            "kind": "Job",
+            "metadata": { "name": "momo-manual" },
+            "spec": {
+                "backoffLimit": 3,
+                "template": {
+                    "spec": {
+                        "containers": [{
+                            "name": "main",
+                            "image": "momo:1.0",
+                            "resources": { "limits": { "cpu": "500m", "memory": "512Mi" } }
+                        }]
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let manifest = serde_yml::to_string(&job).unwrap();
+        assert_eq!(
+            manifest_hash(&manifest).unwrap(),
+            hash(job.spec.as_ref().unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn expect_to_decompress_value() {
+        let value = BTreeMap::from([("name", "dodo")]);
+        let compressed = STANDARD.decode(compress(&value).unwrap()).unwrap();
+
+        let mut json = String::new();
+        GzDecoder::new(compressed.as_slice())
+            .read_to_string(&mut json)
+            .unwrap();
+        assert_eq!(json, r#"{"name":"dodo"}"#);
+
+        let decompressed: BTreeMap<String, String> =
+            decompress(&compress(&value).unwrap()).unwrap();
+        assert_eq!(decompressed.get("name").map(String::as_str), Some("dodo"));
+    }
+}
@@ -0,0 +1,320 @@
+use jiff::{SignedDuration, Timestamp};
+use k8s_openapi::{
+    api::{batch::v1::Job, core::v1::Pod},
+    apimachinery::pkg::apis::meta::v1::Time,
+};
+use std::collections::BTreeMap;
+use std::fmt;
+
+// Constant
+const CONDITION_TRUE: &str = "True";
+
+/// Status of a job derived from its conditions
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed(Option<String>),
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobStatus::Pending => write!(f, "Pending"),
+            JobStatus::Running => write!(f, "Running"),
+            JobStatus::Succeeded => write!(f, "Succeeded"),
+            JobStatus::Failed(Some(reason)) => write!(f, "Failed ({reason})"),
+            JobStatus::Failed(None) => write!(f, "Failed"),
+        }
+    }
+}
+
+/// Last status of the watched objects, used to only report the transitions of their status
+#[derive(Debug, Default)]
+pub struct Transitions {
+    last: BTreeMap<String, String>,
+}
+
+impl Transitions {
+    /// Record the status of the object and get the transition (e.g. Pending → Running). None is returned when the
+    /// status didn't change
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - &str
+    /// * `status` - &str
+    pub fn update(&mut self, object: &str, status: &str) -> Option<String> {
+        match self.last.insert(object.to_string(), status.to_string()) {
+            Some(previous) if previous == status => None,
+            Some(previous) => Some(format!("{previous} → {status}")),
+            None => Some(status.to_string()),
+        }
+    }
+}
+
+/// Summary of a run of a job
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobRun {
+    pub start: Option<Timestamp>,
+    pub duration: Option<SignedDuration>,
+    pub status: JobStatus,
+}
+
+/// Get the start, the duration and the result of a job. The duration of a running job is computed until now
+///
+/// # Arguments
+///
+/// * `job` - &Job
+/// * `now` - Timestamp
+pub fn job_run(job: &Job, now: Timestamp) -> JobRun {
+    let status = job_status(job);
+    let start = job
+        .status
+        .as_ref()
+        .and_then(|s| s.start_time.as_ref())
+        .map(timestamp);
+    let end = job
+        .status
+        .as_ref()
+        .and_then(|s| s.completion_time.as_ref())
+        .map(timestamp)
+        .or_else(|| (status == JobStatus::Running).then_some(now));
+
+    JobRun {
+        start,
+        duration: start.zip(end).map(|(start, end)| end.duration_since(start)),
+        status,
+    }
+}
+
+/// Get the status of the job
+///
+/// # Arguments
+///
+/// * `job` - &Job
+pub fn job_status(job: &Job) -> JobStatus {
+    let Some(status) = job.status.as_ref() else {
+        return JobStatus::Pending;
+    };
+
+    for cond in status.conditions.iter().flatten() {
+        if cond.status != CONDITION_TRUE {
+            continue;
+        }
+
+        match cond.type_.as_str() {
+            "Complete" => return JobStatus::Succeeded,
+            "Failed" => {
+                let reason = match (&cond.reason, &cond.message) {
+                    (Some(reason), Some(message)) => Some(format!("{reason}: {message}")),
+                    (reason, message) => reason.clone().or(message.clone()),
+                };
+
+                return JobStatus::Failed(reason);
+            }
+            _ => {}
+        }
+    }
+
+    match status.active.unwrap_or_default() > 0 {
+        true => JobStatus::Running,
+        false => JobStatus::Pending,
+    }
+}
+
+/// Get the failures of the containers of a pod (non zero exit code) and the pod message if any
+///
+/// # Arguments
+///
+/// * `pod` - &Pod
+pub fn pod_failures(pod: &Pod) -> Vec<String> {
+    let Some(status) = pod.status.as_ref() else {
+        return Vec::new();
+    };
+
+    let mut failures = status
+        .init_container_statuses
+        .iter()
+        .flatten()
+        .chain(status.container_statuses.iter().flatten())
+        .filter_map(|cs| {
+            let terminated = cs.state.as_ref()?.terminated.as_ref()?;
+            if terminated.exit_code == 0 {
+                return None;
+            }
+
+            let mut failure = format!(
+                "container {} exited with code {}",
+                cs.name, terminated.exit_code
+            );
+            if let Some(reason) = &terminated.reason {
+                failure.push_str(&format!(" ({reason})"));
+            }
+            if let Some(message) = &terminated.message {
+                failure.push_str(&format!(": {}", message.trim()));
+            }
+
+            Some(failure)
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(message) = &status.message {
+        failures.push(message.to_owned());
+    }
+
+    failures
+}
+
+/// Check whether the job is finished (succeeded or failed) and was created before the cutoff
+///
+/// # Arguments
+///
+/// * `job` - &Job
+/// * `cutoff` - Timestamp
+pub fn is_prunable(job: &Job, cutoff: Timestamp) -> bool {
+    let finished = matches!(job_status(job), JobStatus::Succeeded | JobStatus::Failed(_));
+
+    finished
+        && job
+            .metadata
+            .creation_timestamp
+            .as_ref()
+            .is_some_and(|created| timestamp(created) < cutoff)
+}
+
+/// Get the age of the job formatted like kubectl (e.g. 45s, 12m, 5h, 3d)
+///
+/// # Arguments
+///
+/// * `job` - &Job
+/// * `now` - Timestamp
+pub fn age(job: &Job, now: Timestamp) -> String {
+    match job.metadata.creation_timestamp.as_ref().map(timestamp) {
+        Some(created) => elapsed(created, now),
+        None => "-".to_string(),
+    }
+}
+
+/// Format the time elapsed since the timestamp with the largest unit (e.g. 90s, 5m, 3h, 2d)
+///
+/// # Arguments
+///
+/// * `since` - Timestamp
+/// * `now` - Timestamp
+pub fn elapsed(since: Timestamp, now: Timestamp) -> String {
+    match now.duration_since(since).as_secs().max(0) {
+        secs if secs < 120 => format!("{secs}s"),
+        secs if secs < 2 * 3600 => format!("{}m", secs / 60),
+        secs if secs < 2 * 86400 => format!("{}h", secs / 3600),
+        secs => format!("{}d", secs / 86400),
+    }
+}
+
+fn timestamp(time: &Time) -> Timestamp {
+    time.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JobStatus, Transitions, age, is_prunable, job_run, job_status};
+    use jiff::{SignedDuration, Timestamp};
+    use k8s_openapi::api::batch::v1::{Job, JobCondition, JobStatus as K8sJobStatus};
+
+    #[test]
+    fn expect_to_get_job_status() {
+        let mut job = Job::default();
+        assert_eq!(job_status(&job), JobStatus::Pending);
+
+        job.status = Some(K8sJobStatus {
+            active: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(job_status(&job), JobStatus::Running);
+
+        job.status = Some(K8sJobStatus {
+            conditions: Some(vec![JobCondition {
+                type_: "Failed".to_string(),
+                status: "True".to_string(),
+                reason: Some("BackoffLimitExceeded".to_string()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        assert_eq!(
+            job_status(&job),
+            JobStatus::Failed(Some("BackoffLimitExceeded".to_string()))
+        );
+    }
+
+    #[test]
+    fn expect_to_get_duration_of_running_job() {
+        let start = "2025-01-01T02:00:00Z".parse::<Timestamp>().unwrap();
+        let job: Job = serde_json::from_value(serde_json::json!({
+            "status": {
+                "active": 1,
+                "startTime": "2025-01-01T02:00:00Z"
+            }
+        }))
+        .unwrap();
+
+        let run = job_run(&job, "2025-01-01T02:03:12Z".parse().unwrap());
+        assert_eq!(run.start, Some(start));
+        assert_eq!(run.duration, Some(SignedDuration::from_secs(192)));
+        assert_eq!(run.status, JobStatus::Running);
+        assert_eq!(age(&job, "2025-01-01T02:03:12Z".parse().unwrap()), "-");
+    }
+
+    #[test]
+    fn expect_to_format_age() {
+        let job: Job = serde_json::from_value(serde_json::json!({
+            "metadata": { "creationTimestamp": "2025-01-01T00:00:00Z" }
+        }))
+        .unwrap();
+
+        assert_eq!(age(&job, "2025-01-01T00:00:45Z".parse().unwrap()), "45s");
+        assert_eq!(age(&job, "2025-01-01T00:12:30Z".parse().unwrap()), "12m");
+        assert_eq!(age(&job, "2025-01-01T05:00:00Z".parse().unwrap()), "5h");
+        assert_eq!(age(&job, "2025-01-04T01:00:00Z".parse().unwrap()), "3d");
+    }
+
+    #[test]
+    fn expect_to_report_status_transitions() {
+        let mut transitions = Transitions::default();
+        assert_eq!(
+            transitions.update("job/momo", "Pending").as_deref(),
+            Some("Pending")
+        );
+        assert_eq!(transitions.update("job/momo", "Pending"), None);
+        assert_eq!(
+            transitions.update("job/momo", "Running").as_deref(),
+            Some("Pending → Running")
+        );
+        assert_eq!(
+            transitions.update("pod/momo-x2k", "Running").as_deref(),
+            Some("Running")
+        );
+    }
+
+    #[test]
+    fn expect_only_old_finished_jobs_to_be_prunable() {
+        let mut job: Job = serde_json::from_value(serde_json::json!({
+            "metadata": { "creationTimestamp": "2025-01-01T00:00:00Z" },
+            "status": { "active": 1 }
+        }))
+        .unwrap();
+        let cutoff = "2025-01-02T00:00:00Z".parse().unwrap();
+        assert!(!is_prunable(&job, cutoff));
+
+        job.status = Some(K8sJobStatus {
+            conditions: Some(vec![JobCondition {
+                type_: "Complete".to_string(),
+                status: "True".to_string(),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        assert!(is_prunable(&job, cutoff));
+        assert!(!is_prunable(&job, "2024-12-31T00:00:00Z".parse().unwrap()));
+    }
+}
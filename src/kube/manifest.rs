@@ -0,0 +1,182 @@
+use super::template::TemplateSpecOps;
+use anyhow::{Result, anyhow};
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment};
+use k8s_openapi::api::batch::v1::{CronJob, JobTemplateSpec};
+use k8s_openapi::api::core::v1::Pod;
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Constant
+const KINDS: [&str; 4] = ["CronJob", "Deployment", "DaemonSet", "Pod"];
+
+/// Source of the job read from a manifest instead of the cluster
+#[derive(Debug, Clone)]
+pub struct Manifest {
+    pub name: String,
+    pub template: JobTemplateSpec,
+    /// The cronjob is kept in order to compute its schedule. None when the manifest isn't a cronjob
+    pub cron_job: Option<CronJob>,
+}
+
+/// Read the manifest of a cronjob, deployment, daemonset or pod
+///
+/// # Arguments
+///
+/// * `path` - &Path
+pub fn read(path: &Path) -> Result<Manifest> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| anyhow!("Unable to read the manifest {}: {err}", path.display()))?;
+
+    parse(&contents)
+}
+
+/// Read the manifest piped on the stdin (e.g. kubectl get cronjob foo -o yaml)
+pub fn read_stdin() -> Result<Manifest> {
+    let contents = io::read_to_string(io::stdin())
+        .map_err(|err| anyhow!("Unable to read the manifest from the stdin: {err}"))?;
+
+    parse(&contents)
+}
+
+/// Parse the YAML manifest. The first cronjob, deployment, daemonset or pod of the documents (or of the items of a
+/// List) is used as the source
+///
+/// # Arguments
+///
+/// * `contents` - &str
+pub fn parse(contents: &str) -> Result<Manifest> {
+    let mut kinds = Vec::new();
+    for document in serde_yml::Deserializer::from_str(contents) {
+        let value = Value::deserialize(document)
+            .map_err(|err| anyhow!("Unable to parse the manifest: {err}"))?;
+
+        // kubectl outputs a List when getting several objects
+        let objects = match value.get("kind").and_then(Value::as_str) {
+            Some("List") => value
+                .get("items")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default(),
+            _ => vec![value],
+        };
+
+        for object in objects {
+            let kind = object
+                .get("kind")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if KINDS.contains(&kind) {
+                return from_object(object);
+            }
+
+            kinds.push(kind.to_string());
+        }
+    }
+
+    Err(anyhow!(
+        "Unsupported kind {}, expected a {} manifest",
+        kinds.join(", "),
+        KINDS.join(", ")
+    ))
+}
+
+/// Get the source from the object of the manifest
+///
+/// # Arguments
+///
+/// * `value` - Value
+fn from_object(value: Value) -> Result<Manifest> {
+    let kind = value
+        .get("kind")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let name = value
+        .pointer("/metadata/name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("The manifest has no metadata.name"))?
+        .to_string();
+
+    let (template, cron_job) = match kind.as_str() {
+        "CronJob" => {
+            let cron_job: CronJob = serde_json::from_value(value)?;
+            (cron_job.get_template_spec(), Some(cron_job))
+        }
+        "Deployment" => (
+            serde_json::from_value::<Deployment>(value)?.get_template_spec(),
+            None,
+        ),
+        "DaemonSet" => (
+            serde_json::from_value::<DaemonSet>(value)?.get_template_spec(),
+            None,
+        ),
+        "Pod" => (
+            serde_json::from_value::<Pod>(value)?.get_template_spec(),
+            None,
+        ),
+        _ => return Err(anyhow!("Unsupported kind {kind}")),
+    };
+
+    Ok(Manifest {
+        template: template.ok_or_else(|| anyhow!("Unable to get the template spec for {name}"))?,
+        name,
+        cron_job,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn expect_to_parse_cron_job_manifest() {
+        let manifest = parse(
+            r#"
+apiVersion: batch/v1
+kind: CronJob
+metadata:
+  name: report
+spec:
+  schedule: "0 0 * * *"
+  jobTemplate:
+    spec:
+      template:
+        spec:
+          restartPolicy: Never
+          containers:
+            - name: main
+              image: busybox
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.name, "report");
+        assert!(manifest.cron_job.is_some());
+        assert!(manifest.template.spec.is_some());
+
+        // The first source of the documents is used
+        let manifest = parse(
+            r#"
+kind: Service
+metadata:
+  name: api
+---
+kind: Deployment
+metadata:
+  name: api
+spec:
+  selector: {}
+  template: {}
+"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.name, "api");
+        assert!(manifest.cron_job.is_none());
+
+        let err = parse("kind: Service\nmetadata:\n  name: api\n").unwrap_err();
+        assert!(err.to_string().starts_with("Unsupported kind Service"));
+    }
+}
@@ -1,35 +1,108 @@
-use crate::cli::COLOR;
+use crate::cli::events::{self, Event};
+use crate::cli::i18n::{tr, tr_args};
+use crate::cli::{COLOR, bundle};
 use anyhow::{Ok, Result, anyhow};
 use colored::{self, Colorize};
+use futures::{AsyncBufReadExt, StreamExt, future, stream};
 use jiff::Span;
+use job::JobStatus;
 use k8s_openapi::{
     NamespaceResourceScope,
-    api::batch::v1::{Job, JobSpec, JobTemplateSpec},
+    api::{
+        batch::v1::{CronJob, Job, JobSpec, JobTemplateSpec},
+        core::v1::{
+            ConfigMap, EnvVarSource, Event as K8sEvent, Namespace, PersistentVolumeClaim, Pod,
+            Secret,
+        },
+        node::v1::RuntimeClass,
+    },
+    apimachinery::pkg::apis::meta::v1::ObjectMeta,
     serde::de::DeserializeOwned,
 };
 use kube::{
-    Client, Resource,
-    api::{Api, DeleteParams, ListParams, PostParams},
-    runtime::{conditions::is_job_completed, wait::await_condition},
+    Client, Config, Resource,
+    api::{
+        Api, ApiResource, DeleteParams, DynamicObject, GroupVersionKind, ListParams, LogParams,
+        ObjectList, Patch, PatchParams, PostParams,
+    },
+    client::ClientBuilder,
+    discovery::{self, Scope},
+    runtime::{
+        WatchStreamExt,
+        wait::{await_condition, conditions::is_deleted},
+        watcher::{self, watcher},
+    },
 };
 use serde_json::json;
-use std::{fmt::Debug, time::Duration};
+use spec::{ContainerEnv, EnvKind};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+    pin::pin,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 use template::TemplateSpecOps;
 
+pub(crate) mod access;
+pub(crate) mod api_log;
+pub(crate) mod argo;
+pub(crate) mod auth;
+pub(crate) mod compat;
+pub(crate) mod crd;
+pub(crate) mod describe;
+pub(crate) mod diff;
+pub(crate) mod doctor;
+pub(crate) mod drift;
+pub(crate) mod hash;
+pub(crate) mod job;
+pub(crate) mod manifest;
+pub(crate) mod pod;
+pub(crate) mod rbac;
+pub(crate) mod redact;
+pub(crate) mod release;
+pub(crate) mod schedule;
+pub(crate) mod security;
 pub(crate) mod spec;
 pub(crate) mod template;
+pub(crate) mod validate;
 
 // Constant
 const BATCH_UID_REMOVE: &str = "batch.kubernetes.io/controller-uid";
 const UID_REMOVE: &str = "controller-uid";
+// Annotation recording that the source cronjob was suspended when the job was dispatched
+pub const SOURCE_SUSPENDED_ANNOTATION: &str = "bakkutteh.io/source-suspended";
+// Annotations holding the hash and a compressed copy (gzip + base64 JSON) of the unmodified source template
+pub const SOURCE_TEMPLATE_HASH_ANNOTATION: &str = "bakkutteh.io/source-template-hash";
+pub const SOURCE_TEMPLATE_ANNOTATION: &str = "bakkutteh.io/source-template";
+// Annotation holding the hash of the spec of the job
+const SPEC_HASH_ANNOTATION: &str = "bakkutteh.io/spec-hash";
+// Labels set on the manual jobs in order to find the jobs dispatched from a source
+const MANAGED_BY_LABEL: &str = "app.kubernetes.io/managed-by";
+const MANAGED_BY: &str = "bakkutteh";
+const SOURCE_LABEL: &str = "bakkutteh.io/source";
+// Maximum length of a label value
+const MAX_LABEL_VALUE_LENGTH: usize = 63;
+// Label set by the job controller on the pods of a job
+const JOB_NAME_LABEL: &str = "job-name";
+const BATCH_JOB_NAME_LABEL: &str = "batch.kubernetes.io/job-name";
+// Status code returned by the API server when a permission is denied
+const FORBIDDEN: u16 = 403;
+// Namespace in which the schedulers are discovered
+const SYSTEM_NAMESPACE: &str = "kube-system";
+// Number of items fetched per page when listing resources
+const LIST_PAGE_SIZE: u32 = 100;
 
 #[derive(Clone)]
 pub struct KubeHandler<S: AsRef<str>> {
-    client: Client,
+    // The client is replaced when the credentials of an exec plugin have to be refreshed
+    client: Arc<RwLock<Client>>,
     namespace: S,
     job: Option<Job>,
     dry_run: bool,
     dry_run_output_path: bool,
+    read_only: bool,
+    api_log: u8,
 }
 
 impl<S> KubeHandler<S>
@@ -41,18 +114,173 @@ where
     /// # Arguments
     ///
     /// * `ns` - S
-    pub async fn new(ns: S, dry_run: bool, dry_run_output_path: bool) -> Result<Self> {
-        let client = Client::try_default().await?;
+    /// * `dry_run` - bool
+    /// * `dry_run_output_path` - bool
+    /// * `read_only` - bool (refuse every mutating call)
+    /// * `api_log` - u8 (level of the logs of the API requests, 0 disables them)
+    pub async fn new(
+        ns: S,
+        dry_run: bool,
+        dry_run_output_path: bool,
+        read_only: bool,
+        api_log: u8,
+    ) -> Result<Self> {
+        let client = build_client(api_log).await?;
 
         Ok(Self {
-            client,
+            client: Arc::new(RwLock::new(client)),
             namespace: ns,
             job: None,
             dry_run,
             dry_run_output_path,
+            read_only,
+            api_log,
         })
     }
 
+    /// Refuse the mutating operation when the read-only mode is enabled
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - String
+    fn ensure_writable(&self, action: String) -> Result<()> {
+        match self.read_only {
+            true => Err(anyhow!(tr_args("read-only-refused", &[("action", action)]))),
+            false => Ok(()),
+        }
+    }
+
+    fn client(&self) -> Client {
+        self.client
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    fn api<K>(&self) -> Api<K>
+    where
+        K: Resource<Scope = NamespaceResourceScope>,
+        <K as Resource>::DynamicType: Default,
+    {
+        Api::namespaced(self.client(), self.namespace.as_ref())
+    }
+
+    /// Run the request against the API. When the credentials provided by an exec plugin (aws, gcloud, oidc-login...)
+    /// are rejected or expired, the plugin is run again with a new client and the request is retried once
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - F
+    async fn request<K, T, F, Fut>(&self, request: F) -> Result<T>
+    where
+        K: Resource<Scope = NamespaceResourceScope>,
+        <K as Resource>::DynamicType: Default,
+        F: Fn(Api<K>) -> Fut,
+        Fut: Future<Output = kube::Result<T>>,
+    {
+        self.request_with(
+            |client| Api::namespaced(client, self.namespace.as_ref()),
+            request,
+        )
+        .await
+    }
+
+    /// Run the request against the API built from the client (e.g. another namespace or a cluster-scoped resource)
+    /// with the same retry as [`Self::request`]
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - A
+    /// * `request` - F
+    async fn request_with<K, T, A, F, Fut>(&self, api: A, request: F) -> Result<T>
+    where
+        A: Fn(Client) -> Api<K>,
+        F: Fn(Api<K>) -> Fut,
+        Fut: Future<Output = kube::Result<T>>,
+    {
+        self.request_client(|client| request(api(client))).await
+    }
+
+    /// Run the request with the client (e.g. the discovery of the API server) with the same retry as
+    /// [`Self::request`]
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - F
+    async fn request_client<T, F, Fut>(&self, request: F) -> Result<T>
+    where
+        F: Fn(Client) -> Fut,
+        Fut: Future<Output = kube::Result<T>>,
+    {
+        let err = match request(self.client()).await {
+            Err(err) if auth::is_auth_error(&err) => err,
+            res => return res.map_err(api_error),
+        };
+
+        let Some(command) = auth::exec_command() else {
+            return Err(api_error(err));
+        };
+
+        eprintln!(
+            "{}",
+            tr_args(
+                "exec-credentials-rejected",
+                &[("command", command.clone()), ("error", err.to_string())]
+            )
+        );
+        let client = build_client(self.api_log).await?;
+        *self.client.write().unwrap_or_else(|err| err.into_inner()) = client;
+
+        request(self.client())
+            .await
+            .map_err(|err| match auth::is_auth_error(&err) {
+                true => anyhow!(tr_args(
+                    "exec-credentials-invalid",
+                    &[
+                        ("command", command.clone()),
+                        ("error", err.to_string()),
+                        ("hint", auth::hint(&command)),
+                    ]
+                )),
+                false => api_error(err),
+            })
+    }
+
+    /// Get the version of the API server
+    pub async fn server_version(&self) -> Result<compat::Version> {
+        let info = self
+            .request_client(|client| async move { client.apiserver_version().await })
+            .await?;
+
+        compat::Version::parse(&info).ok_or_else(|| {
+            anyhow!(tr_args(
+                "server-version-invalid",
+                &[("major", info.major.clone()), ("minor", info.minor.clone())]
+            ))
+        })
+    }
+
+    /// Get the version of the API server and the API groups it serves
+    pub async fn discovery(&self) -> Result<String> {
+        let client = self.client();
+        let version = client.apiserver_version().await?;
+        let groups = client
+            .list_api_groups()
+            .await?
+            .groups
+            .into_iter()
+            .flat_map(|group| group.versions)
+            .map(|version| version.group_version)
+            .collect::<Vec<_>>();
+
+        let discovery = serde_yml::to_string(&json!({
+            "version": version,
+            "groups": groups,
+        }))?;
+
+        Ok(discovery)
+    }
+
     /// Get the object for the targeted api
     ///
     /// # Arguments
@@ -65,8 +293,16 @@ where
         <K as Resource>::DynamicType: Default,
         N: AsRef<str>,
     {
-        let api: Api<K> = Api::namespaced(self.client.clone(), self.namespace.as_ref());
-        let object = api.get(name.as_ref()).await?;
+        let name = name.as_ref();
+        let object = self
+            .request(|api: Api<K>| async move { api.get(name).await })
+            .await?;
+
+        events::emit(Event::ObjectFetched {
+            kind: &K::kind(&Default::default()),
+            namespace: self.namespace.as_ref(),
+            name,
+        });
 
         Ok(object)
     }
@@ -80,17 +316,87 @@ where
     where
         N: AsRef<str>,
     {
-        let api: Api<Job> = Api::namespaced(self.client.clone(), self.namespace.as_ref());
-        let delete_params = DeleteParams::default();
+        let name = name.as_ref();
+        self.ensure_writable(tr_args("action-delete-job", &[("name", name.to_string())]))?;
 
-        api.delete(name.as_ref(), &delete_params)
+        // The default propagation of the jobs orphans their pods
+        let delete_params = &DeleteParams::background();
+
+        self.request(|api: Api<Job>| async move { api.delete(name, delete_params).await })
             .await
-            .map_err(|err| anyhow!("Unable to delete the job due to {:?}", err))?
-            .map_right(|s| println!("Job deleted with status {s:?}"));
+            .map_err(|err| {
+                anyhow!(tr_args(
+                    "delete-job-failed",
+                    &[("name", name.to_string()), ("error", err.to_string())]
+                ))
+            })?
+            .map_right(|s| {
+                println!(
+                    "{}",
+                    tr_args("job-deleted-status", &[("status", format!("{s:?}"))])
+                )
+            });
 
         Ok(())
     }
 
+    /// Get the labels of the namespace of the handler
+    pub async fn get_namespace_labels(&self) -> Result<BTreeMap<String, String>> {
+        let name = self.namespace.as_ref();
+        let namespace = self
+            .request_with(
+                Api::<Namespace>::all,
+                |api| async move { api.get(name).await },
+            )
+            .await?;
+
+        Ok(namespace.metadata.labels.unwrap_or_default())
+    }
+
+    /// Discover the schedulers running in the kube-system namespace
+    pub async fn list_schedulers(&self) -> Result<BTreeSet<String>> {
+        let mut pods = Vec::new();
+        self.for_each_item_in(
+            SYSTEM_NAMESPACE,
+            |client| Api::<Pod>::namespaced(client, SYSTEM_NAMESPACE),
+            ListParams::default(),
+            |pod| pods.push(pod),
+            |_, _| {},
+        )
+        .await?;
+
+        Ok(pod::scheduler_names(&pods))
+    }
+
+    /// List the names of the RuntimeClasses of the cluster (e.g. gVisor, kata)
+    pub async fn list_runtime_classes(&self) -> Result<Vec<String>> {
+        let mut classes = Vec::new();
+        // RuntimeClasses are cluster-scoped, they have no namespace
+        self.for_each_item_in(
+            "",
+            Api::<RuntimeClass>::all,
+            ListParams::default(),
+            |class| classes.extend(class.metadata.name),
+            |_, _| {},
+        )
+        .await?;
+
+        Ok(classes)
+    }
+
+    /// List the names of the PersistentVolumeClaims of the namespace
+    pub async fn list_pvcs(&self) -> Result<Vec<String>> {
+        let mut claims = Vec::new();
+        self.for_each_item::<PersistentVolumeClaim, _, _>(
+            ListParams::default(),
+            |claim| claims.extend(claim.metadata.name),
+            |_, _| {},
+        )
+        .await?;
+
+        Ok(claims)
+    }
+
     /// Get the spec for a targeted kubernetes object
     ///
     /// # Arguments
@@ -104,30 +410,648 @@ where
         <K as Resource>::DynamicType: Default,
     {
         let object: K = self.get_object(name.as_ref()).await?;
-        object
-            .get_template_spec()
-            .ok_or_else(|| anyhow!("Unable to get the template spec for {}", name.as_ref()))
+        object.get_template_spec().ok_or_else(|| {
+            anyhow!(tr_args(
+                "template-spec-missing",
+                &[("name", name.as_ref().to_string())]
+            ))
+        })
+    }
+
+    /// Find the resource of a custom kind and its scope with the discovery of the API server
+    ///
+    /// # Arguments
+    ///
+    /// * `gvk` - &GroupVersionKind
+    async fn discover_custom(&self, gvk: &GroupVersionKind) -> Result<(ApiResource, Scope)> {
+        let (resource, capabilities) = self
+            .request_client(|client| async move { discovery::pinned_kind(&client, gvk).await })
+            .await?;
+
+        Ok((resource, capabilities.scope))
+    }
+
+    /// Get the API of a custom resource found with [`Self::discover_custom`]
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Client
+    /// * `resource` - &ApiResource
+    /// * `scope` - &Scope
+    fn custom_api(
+        &self,
+        client: Client,
+        resource: &ApiResource,
+        scope: &Scope,
+    ) -> Api<DynamicObject> {
+        match scope {
+            Scope::Namespaced => Api::namespaced_with(client, self.namespace.as_ref(), resource),
+            Scope::Cluster => Api::all_with(client, resource),
+        }
     }
 
-    /// List the existing resources on the cluster
-    pub async fn list<K>(&self) -> Result<Vec<String>>
+    /// Get the template spec embedded in a custom resource (e.g. a KEDA ScaledJob)
+    ///
+    /// # Arguments
+    ///
+    /// * `gvk` - &GroupVersionKind
+    /// * `name` - &str
+    /// * `extract` - F (get the template spec from the object)
+    pub async fn get_spec_for_custom_object<F>(
+        &self,
+        gvk: &GroupVersionKind,
+        name: &str,
+        extract: F,
+    ) -> Result<JobTemplateSpec>
+    where
+        F: FnOnce(&serde_json::Value) -> Result<JobTemplateSpec>,
+    {
+        let (resource, scope) = self.discover_custom(gvk).await?;
+        let object = self
+            .request_with(
+                |client| self.custom_api(client, &resource, &scope),
+                |api| async move { api.get(name).await },
+            )
+            .await?;
+
+        events::emit(Event::ObjectFetched {
+            kind: &gvk.kind,
+            namespace: self.namespace.as_ref(),
+            name,
+        });
+
+        let value = serde_json::to_value(&object)?;
+        extract(&value).map_err(|err| {
+            anyhow!(tr_args(
+                "template-spec-invalid",
+                &[("name", name.to_string()), ("error", err.to_string())]
+            ))
+        })
+    }
+
+    /// List the custom resources of the kind
+    ///
+    /// # Arguments
+    ///
+    /// * `gvk` - &GroupVersionKind
+    pub async fn list_custom_objects(&self, gvk: &GroupVersionKind) -> Result<Vec<DynamicObject>> {
+        let (resource, scope) = self.discover_custom(gvk).await?;
+        let namespace = match scope {
+            Scope::Namespaced => self.namespace.as_ref(),
+            Scope::Cluster => "",
+        };
+
+        let mut objects = Vec::new();
+        self.for_each_page(
+            &resource.kind,
+            namespace,
+            |client, lp| {
+                let api = self.custom_api(client, &resource, &scope);
+                async move { api.list(&lp).await }
+            },
+            ListParams::default(),
+            |object| objects.push(object),
+            |_, _| {},
+        )
+        .await?;
+
+        Ok(objects)
+    }
+
+    /// List the existing resources on the cluster and only keep what is extracted from each item
+    ///
+    /// # Arguments
+    ///
+    /// * `extract` - F
+    /// * `progress` - P (called with the number of pages & items fetched so far)
+    pub async fn list_with<K, T, F, P>(&self, mut extract: F, progress: P) -> Result<Vec<T>>
+    where
+        K: Resource<Scope = NamespaceResourceScope>,
+        K: Resource + Clone + Debug + DeserializeOwned,
+        <K as Resource>::DynamicType: Default,
+        F: FnMut(K) -> Option<T>,
+        P: FnMut(usize, usize),
+    {
+        let mut items = Vec::new();
+        self.for_each_item::<K, _, _>(
+            ListParams::default(),
+            |item| {
+                if let Some(extracted) = extract(item) {
+                    items.push(extracted);
+                }
+            },
+            progress,
+        )
+        .await?;
+
+        Ok(items)
+    }
+
+    /// List the names of the resources of the namespace. Only the metadata are fetched (e.g. the data of the Secrets
+    /// isn't read)
+    pub async fn list_names<K>(&self) -> Result<Vec<String>>
+    where
+        K: Resource<Scope = NamespaceResourceScope>,
+        K: Resource + Clone + Debug + DeserializeOwned,
+        <K as Resource>::DynamicType: Default,
+    {
+        let kind = K::kind(&Default::default()).to_string();
+        let namespace = self.namespace.as_ref();
+
+        let mut names = Vec::new();
+        self.for_each_page(
+            &kind,
+            namespace,
+            |client, lp| {
+                let api = Api::<K>::namespaced(client, namespace);
+                async move { api.list_metadata(&lp).await }
+            },
+            ListParams::default(),
+            |item| names.extend(item.metadata.name),
+            |_, _| {},
+        )
+        .await?;
+
+        Ok(names)
+    }
+
+    /// Fetch the resources by pages using limit/continue and process each item as soon as its page is fetched.
+    /// The progress is reported after each page so that the user knows that the listing is still running
+    ///
+    /// # Arguments
+    ///
+    /// * `lp` - ListParams
+    /// * `handler` - F
+    /// * `progress` - P (called with the number of pages & items fetched so far)
+    async fn for_each_item<K, F, P>(&self, lp: ListParams, handler: F, progress: P) -> Result<()>
     where
         K: Resource<Scope = NamespaceResourceScope>,
         K: Resource + Clone + Debug + DeserializeOwned,
         <K as Resource>::DynamicType: Default,
+        F: FnMut(K),
+        P: FnMut(usize, usize),
+    {
+        let namespace = self.namespace.as_ref();
+        self.for_each_item_in(
+            namespace,
+            |client| Api::namespaced(client, namespace),
+            lp,
+            handler,
+            progress,
+        )
+        .await
+    }
+
+    /// Fetch the resources of the API built from the client by pages, as [`Self::for_each_item`] does for the
+    /// namespace of the handler
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - &str (empty for the cluster-scoped resources)
+    /// * `api` - A
+    /// * `lp` - ListParams
+    /// * `handler` - F
+    /// * `progress` - P (called with the number of pages & items fetched so far)
+    async fn for_each_item_in<K, A, F, P>(
+        &self,
+        namespace: &str,
+        api: A,
+        lp: ListParams,
+        handler: F,
+        progress: P,
+    ) -> Result<()>
+    where
+        K: Resource + Clone + Debug + DeserializeOwned,
+        <K as Resource>::DynamicType: Default,
+        A: Fn(Client) -> Api<K>,
+        F: FnMut(K),
+        P: FnMut(usize, usize),
     {
-        let target_object: Api<K> = Api::namespaced(self.client.clone(), self.namespace.as_ref());
+        let kind = K::kind(&Default::default()).to_string();
+        self.for_each_page(
+            &kind,
+            namespace,
+            |client, lp| {
+                let api = api(client);
+                async move { api.list(&lp).await }
+            },
+            lp,
+            handler,
+            progress,
+        )
+        .await
+    }
+
+    /// Fetch the pages of the list request using limit/continue with the same retry as [`Self::request`]. Used for
+    /// the lists which aren't lists of typed resources (e.g. custom resources or metadata only)
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - &str
+    /// * `namespace` - &str (empty for the cluster-scoped resources)
+    /// * `list` - L (list the page of the params with the client)
+    /// * `lp` - ListParams
+    /// * `handler` - F
+    /// * `progress` - P (called with the number of pages & items fetched so far)
+    async fn for_each_page<T, L, Fut, F, P>(
+        &self,
+        kind: &str,
+        namespace: &str,
+        list: L,
+        mut lp: ListParams,
+        mut handler: F,
+        mut progress: P,
+    ) -> Result<()>
+    where
+        T: Clone,
+        L: Fn(Client, ListParams) -> Fut,
+        Fut: Future<Output = kube::Result<ObjectList<T>>>,
+        F: FnMut(T),
+        P: FnMut(usize, usize),
+    {
+        lp.limit = Some(LIST_PAGE_SIZE);
+        let (mut pages, mut items) = (0, 0);
+
+        loop {
+            let page = self
+                .request_client(|client| list(client, lp.clone()))
+                .await?;
+
+            pages += 1;
+            items += page.items.len();
+            page.items.into_iter().for_each(&mut handler);
+
+            events::emit(Event::ListProgress {
+                kind,
+                namespace,
+                pages,
+                items,
+            });
+            progress(pages, items);
+
+            match page.metadata.continue_ {
+                Some(token) if !token.is_empty() => lp.continue_token = Some(token),
+                _ => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// List the jobs owned by an object (e.g. the jobs scheduled by a cronjob) from the most recent to the oldest
+    ///
+    /// # Arguments
+    ///
+    /// * `owner_uid` - &str
+    pub async fn list_owned_jobs(&self, owner_uid: &str) -> Result<Vec<Job>> {
+        let mut jobs = Vec::new();
+        self.for_each_item::<Job, _, _>(
+            ListParams::default(),
+            |job| {
+                let owned = job
+                    .metadata
+                    .owner_references
+                    .iter()
+                    .flatten()
+                    .any(|owner| owner.uid == owner_uid);
 
-        let lp = ListParams::default();
-        let list = target_object.list(&lp).await?;
+                if owned {
+                    jobs.push(job);
+                }
+            },
+            |_, _| {},
+        )
+        .await?;
+
+        jobs.sort_by(|a, b| {
+            b.metadata
+                .creation_timestamp
+                .cmp(&a.metadata.creation_timestamp)
+        });
+
+        Ok(jobs)
+    }
+
+    /// Delete the job along with its pods. The deletion is foreground, the job is removed once its pods are deleted
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - &str
+    pub async fn delete_job(&self, name: &str) -> Result<()> {
+        self.ensure_writable(tr_args("action-delete-job", &[("name", name.to_string())]))?;
+
+        let delete_params = &DeleteParams::foreground();
+        self.request(|api: Api<Job>| async move { api.delete(name, delete_params).await })
+            .await
+            .map_err(|err| {
+                anyhow!(tr_args(
+                    "delete-job-failed",
+                    &[("name", name.to_string()), ("error", err.to_string())]
+                ))
+            })?;
+
+        Ok(())
+    }
 
-        let list = list
-            .items
+    /// Wait until the job is deleted
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - &str
+    /// * `uid` - &str
+    pub async fn wait_for_deletion(&self, name: &str, uid: &str) -> Result<()> {
+        await_condition(self.api::<Job>(), name, is_deleted(uid))
+            .await
+            .map_err(|err| {
+                anyhow!(tr_args(
+                    "wait-deletion-failed",
+                    &[("name", name.to_string()), ("error", err.to_string())]
+                ))
+            })?;
+
+        Ok(())
+    }
+
+    /// List the manual jobs dispatched from the source object, or from every source when none is given
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Option<&str>
+    pub async fn list_manual_jobs(&self, source: Option<&str>) -> Result<Vec<Job>> {
+        let mut jobs = Vec::new();
+        let selector = match source {
+            Some(source) => format!("{MANAGED_BY_LABEL}={MANAGED_BY},{SOURCE_LABEL}={source}"),
+            None => format!("{MANAGED_BY_LABEL}={MANAGED_BY}"),
+        };
+        let lp = ListParams::default().labels(&selector);
+        self.for_each_item::<Job, _, _>(lp, |job| jobs.push(job), |_, _| {})
+            .await?;
+
+        Ok(jobs)
+    }
+
+    /// Watch the job and its pods and print the transitions of their status until the job is finished
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - &str
+    pub async fn watch_job(&self, name: &str) -> Result<JobStatus> {
+        // The watch would wait forever for a job which doesn't exist
+        self.get_object::<Job, _>(name).await?;
+
+        let jobs = watcher(
+            self.api::<Job>(),
+            watcher::Config::default().fields(&format!("metadata.name={name}")),
+        )
+        .default_backoff()
+        .applied_objects()
+        .map(|res| res.map(|job| Watched::Job(Box::new(job))));
+        let pods = watcher(
+            self.api::<Pod>(),
+            watcher::Config::default().labels(&format!("{JOB_NAME_LABEL}={name}")),
+        )
+        .default_backoff()
+        .applied_objects()
+        .map(|res| res.map(|pod| Watched::Pod(Box::new(pod))));
+
+        let mut stream = pin!(stream::select(jobs, pods));
+        let mut transitions = job::Transitions::default();
+        while let Some(res) = stream.next().await {
+            let watched = match res {
+                // The watcher reconnects by itself, the error is only reported to the user
+                Err(err) => {
+                    eprintln!(
+                        "{}",
+                        tr_args(
+                            "watch-interrupted",
+                            &[("name", name.to_string()), ("error", err.to_string())]
+                        )
+                    );
+                    continue;
+                }
+                res => res?,
+            };
+
+            let job = match watched {
+                Watched::Job(job) => job,
+                Watched::Pod(pod) => {
+                    let phase = pod
+                        .status
+                        .and_then(|status| status.phase)
+                        .unwrap_or_else(|| "Pending".to_string());
+                    let object = format!("pod/{}", pod.metadata.name.unwrap_or_default());
+                    print_transition(&mut transitions, &object, &phase);
+                    continue;
+                }
+            };
+
+            let status = job::job_status(&job);
+            print_transition(
+                &mut transitions,
+                &format!("job/{name}"),
+                &status.to_string(),
+            );
+            if matches!(status, JobStatus::Succeeded | JobStatus::Failed(_)) {
+                return Ok(status);
+            }
+        }
+
+        Err(anyhow!(tr_args(
+            "watch-ended",
+            &[("name", name.to_string())]
+        )))
+    }
+
+    /// Print the logs of the containers of the pods created by the job. Each line is prefixed with the pod and the
+    /// container, the logs of the containers are interleaved as they're received
+    ///
+    /// # Arguments
+    ///
+    /// * `job_name` - &str
+    /// * `follow` - bool (stream the logs until the containers exit)
+    pub async fn stream_job_logs(&self, job_name: &str, follow: bool) -> Result<()> {
+        let pods = self.get_job_pods(job_name).await?;
+        if pods.is_empty() {
+            return Err(anyhow!(tr_args(
+                "no-job-pod",
+                &[("name", job_name.to_string())]
+            )));
+        }
+
+        let api: Api<Pod> = self.api();
+        let mut streams = Vec::new();
+        for pod in &pods {
+            let pod_name = pod.metadata.name.as_deref().unwrap_or_default();
+            let containers = pod.spec.iter().flat_map(|spec| {
+                spec.init_containers
+                    .iter()
+                    .flatten()
+                    .chain(&spec.containers)
+            });
+
+            for container in containers {
+                let lp = LogParams {
+                    follow,
+                    container: Some(container.name.clone()),
+                    ..Default::default()
+                };
+
+                // Containers which haven't started yet have no logs
+                let reader = match api.log_stream(pod_name, &lp).await {
+                    Err(err) => {
+                        eprintln!(
+                            "{}",
+                            tr_args(
+                                "logs-unavailable",
+                                &[
+                                    ("container", format!("{pod_name}/{}", container.name)),
+                                    ("error", err.to_string())
+                                ]
+                            )
+                        );
+                        continue;
+                    }
+                    res => res?,
+                };
+
+                let prefix = format!("[{pod_name}/{}]", container.name)
+                    .truecolor(COLOR.0, COLOR.1, COLOR.2)
+                    .to_string();
+                streams.push(
+                    reader
+                        .lines()
+                        .map(move |line| line.map(|line| format!("{prefix} {line}")))
+                        .boxed(),
+                );
+            }
+        }
+
+        let mut lines = stream::select_all(streams);
+        while let Some(line) = lines.next().await {
+            println!("{}", line?);
+        }
+
+        Ok(())
+    }
+
+    /// Get the pods created by the job
+    ///
+    /// # Arguments
+    ///
+    /// * `job_name` - &str
+    pub async fn get_job_pods(&self, job_name: &str) -> Result<Vec<Pod>> {
+        let mut pods = Vec::new();
+        let lp = ListParams::default().labels(&format!("{JOB_NAME_LABEL}={job_name}"));
+        self.for_each_item::<Pod, _, _>(lp, |pod| pods.push(pod), |_, _| {})
+            .await?;
+
+        Ok(pods)
+    }
+
+    /// Get the events of the objects (e.g. the job and its pods)
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - &[&str]
+    pub async fn get_events(&self, names: &[&str]) -> Result<Vec<K8sEvent>> {
+        let mut events = Vec::new();
+        for name in names {
+            let lp = ListParams::default().fields(&format!("involvedObject.name={name}"));
+            self.for_each_item::<K8sEvent, _, _>(lp, |event| events.push(event), |_, _| {})
+                .await?;
+        }
+
+        Ok(events)
+    }
+
+    /// Get the data of the ConfigMaps referenced by the environment variables. Each ConfigMap is fetched
+    /// only once and the ConfigMaps are fetched concurrently. ConfigMaps which do not exist are skipped
+    ///
+    /// # Arguments
+    ///
+    /// * `envs` - &[ContainerEnv]
+    pub async fn get_referenced_config_maps(
+        &self,
+        envs: &[ContainerEnv],
+    ) -> Result<BTreeMap<String, BTreeMap<String, String>>> {
+        let names = referenced_names(envs, |source| {
+            source
+                .config_map_key_ref
+                .as_ref()
+                .map(|selector| selector.name.to_owned())
+        });
+
+        let config_maps = self
+            .get_all::<ConfigMap>(names)
+            .await?
             .into_iter()
-            .filter_map(|item| item.meta().name.clone())
-            .collect::<Vec<_>>();
+            .map(|(name, cm)| (name, cm.data.unwrap_or_default()))
+            .collect();
+
+        Ok(config_maps)
+    }
+
+    /// Get the data of the Secrets referenced by the environment variables. This is the only place where the
+    /// data of a Secret is read, it must only be called when the user explicitly asked to show the secrets
+    ///
+    /// # Arguments
+    ///
+    /// * `envs` - &[ContainerEnv]
+    pub async fn get_referenced_secrets(
+        &self,
+        envs: &[ContainerEnv],
+    ) -> Result<BTreeMap<String, BTreeMap<String, String>>> {
+        let names = referenced_names(envs, |source| {
+            source
+                .secret_key_ref
+                .as_ref()
+                .map(|selector| selector.name.to_owned())
+        });
+
+        let secrets = self
+            .get_all::<Secret>(names)
+            .await?
+            .into_iter()
+            .map(|(name, secret)| {
+                let data = secret
+                    .data
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(key, value)| (key, String::from_utf8_lossy(&value.0).into_owned()))
+                    .collect();
+
+                (name, data)
+            })
+            .collect();
+
+        Ok(secrets)
+    }
+
+    /// Fetch the objects concurrently. Objects which do not exist are skipped
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - BTreeSet<String>
+    async fn get_all<K>(&self, names: BTreeSet<String>) -> Result<Vec<(String, K)>>
+    where
+        K: Resource<Scope = NamespaceResourceScope>,
+        K: Resource + Clone + Debug + DeserializeOwned,
+        <K as Resource>::DynamicType: Default,
+    {
+        let requests = names.into_iter().map(|name| async move {
+            let object = self
+                .request(|api: Api<K>| {
+                    let name = &name;
+                    async move { api.get_opt(name).await }
+                })
+                .await?;
+
+            Ok(object.map(|object| (name, object)))
+        });
+
+        let objects = future::try_join_all(requests).await?;
 
-        Ok(list)
+        Ok(objects.into_iter().flatten().collect())
     }
 
     /// Build a manual job from the cronjob job spec
@@ -135,13 +1059,19 @@ where
     /// # Arguments
     ///
     /// * `name` - N
+    /// * `source` - Name of the object used as the source of the job
     /// * `job_spec` - JobSpec
     /// * `backoff_limit` - BackoffLimit for the job
+    /// * `labels` - Labels added to the job
+    /// * `annotations` - Annotations added to the job
     pub fn build_manual_job<N: AsRef<str>>(
         &mut self,
         name: N,
+        source: &str,
         mut job_spec: JobSpec,
         backoff_limit: i32,
+        mut labels: BTreeMap<String, String>,
+        mut annotations: BTreeMap<String, String>,
     ) -> Result<&Self> {
         let mut job: Job = serde_json::from_value(json!({
             "apiVersion": "batch/v1",
@@ -153,16 +1083,37 @@ where
         }))?;
 
         job_spec.backoff_limit = Some(backoff_limit);
+
+        // The hash allows to verify later on that the job which ran matches an exported manifest
+        annotations.insert(SPEC_HASH_ANNOTATION.to_string(), hash::hash(&job_spec)?);
         job.spec = Some(job_spec);
 
+        labels.insert(MANAGED_BY_LABEL.to_string(), MANAGED_BY.to_string());
+        // Names of deployments may exceed the length allowed for a label value
+        if source.len() <= MAX_LABEL_VALUE_LENGTH {
+            labels.insert(SOURCE_LABEL.to_string(), source.to_string());
+        }
+        job.metadata.labels = Some(labels);
+
+        job.metadata.annotations = Some(annotations);
+
+        bundle::record_spec(&job);
         self.job = Some(job);
 
         Ok(self)
     }
 
+    /// Get the spec hash of the job built by [`Self::build_manual_job`], which is the one annotated on the job
+    pub fn spec_hash(&self) -> Option<String> {
+        self.job
+            .as_ref()
+            .and_then(|job| job.metadata.annotations.as_ref())
+            .and_then(|annotations| annotations.get(SPEC_HASH_ANNOTATION))
+            .cloned()
+    }
+
     /// Apply the manual job in K8S
     pub async fn apply_manual_job(&self) -> Result<Job> {
-        let job_api: Api<Job> = Api::namespaced(self.client.clone(), self.namespace.as_ref());
         let mut pp = PostParams::default();
 
         if self.dry_run {
@@ -170,15 +1121,117 @@ where
         }
 
         let Some(job) = &self.job else {
-            return Err(anyhow!("Unable to create the job as building spec failed"));
+            return Err(anyhow!(tr("job-spec-missing")));
+        };
+
+        // Report every invalid field at once instead of one server rejection at a time
+        validate::validate_job(job)?;
+
+        // A dry run doesn't persist anything and is allowed in read-only mode
+        if !self.dry_run {
+            self.ensure_writable(tr("action-create-job"))?;
+        }
+
+        let pp = &pp;
+        self.request(|api: Api<Job>| async move { api.create(pp, job).await })
+            .await
+    }
+
+    /// Create again a job which was deleted. The fields set by the API server are removed so that a new
+    /// selector is generated
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - Job
+    pub async fn recreate_job(&self, mut job: Job) -> Result<Job> {
+        let name = job
+            .metadata
+            .name
+            .clone()
+            .or_else(|| job.metadata.generate_name.clone())
+            .unwrap_or_default();
+        self.ensure_writable(tr_args("action-recreate-job", &[("name", name.clone())]))?;
+
+        job.metadata = ObjectMeta {
+            name: job.metadata.name,
+            generate_name: job.metadata.generate_name,
+            labels: job.metadata.labels,
+            annotations: job.metadata.annotations,
+            ..Default::default()
         };
+        job.status = None;
+        remove_controller_uid(&mut job);
+        if let Some(spec) = job.spec.as_mut() {
+            spec.selector = None;
+        }
+
+        let (pp, job) = (&PostParams::default(), &job);
+        self.request(|api: Api<Job>| async move { api.create(pp, job).await })
+            .await
+            .map_err(|err| {
+                anyhow!(tr_args(
+                    "recreate-job-failed",
+                    &[("name", name.clone()), ("error", err.to_string())]
+                ))
+            })
+    }
 
-        let job = job_api.create(&pp, job).await?;
+    /// Suspend or resume a job
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - &str
+    /// * `suspend` - bool
+    pub async fn set_job_suspend(&self, name: &str, suspend: bool) -> Result<Job> {
+        self.ensure_writable(tr_args("action-suspend-job", &[("name", name.to_string())]))?;
+
+        let patch = &Patch::Merge(json!({ "spec": { "suspend": suspend } }));
+        let pp = &PatchParams::default();
+
+        let job = self
+            .request(|api: Api<Job>| async move { api.patch(name, pp, patch).await })
+            .await
+            .map_err(|err| {
+                anyhow!(tr_args(
+                    "suspend-job-failed",
+                    &[("name", name.to_string()), ("error", err.to_string())]
+                ))
+            })?;
 
         Ok(job)
     }
 
-    /// Wait for the job to complete by polling the pod associated with the job.
+    /// Suspend or resume the scheduling of a cronjob
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - &str
+    /// * `suspend` - bool
+    pub async fn set_cron_job_suspend(&self, name: &str, suspend: bool) -> Result<CronJob> {
+        self.ensure_writable(tr_args(
+            "action-suspend-cronjob",
+            &[("name", name.to_string())],
+        ))?;
+
+        let patch = &Patch::Merge(json!({ "spec": { "suspend": suspend } }));
+        let pp = &PatchParams::default();
+
+        let cron_job = self
+            .request(|api: Api<CronJob>| async move { api.patch(name, pp, patch).await })
+            .await
+            .map_err(|err| {
+                anyhow!(tr_args(
+                    "suspend-cronjob-failed",
+                    &[("name", name.to_string()), ("error", err.to_string())]
+                ))
+            })?;
+
+        Ok(cron_job)
+    }
+
+    /// Wait for the job to complete by watching the job. The watch requests bookmarks and is resumed
+    /// from the last known resourceVersion with a backoff when the connection drops (e.g. API server rollout).
+    /// The reason of the failure is returned as soon as the job fails
     ///
     /// # Arguments
     ///
@@ -195,13 +1248,54 @@ where
             None => return Ok(job),
         };
 
-        // Create a pod_api in order to retrieve the list of pod associated with the job.
-        let job_api: Api<Job> = Api::namespaced(self.client.clone(), self.namespace.as_ref());
+        let job_api: Api<Job> = self.api();
+        let config = watcher::Config {
+            bookmarks: true,
+            ..Default::default()
+        }
+        .fields(&format!("metadata.name={name}"));
+
+        let completed = async {
+            let mut stream = pin!(watcher(job_api, config).default_backoff().applied_objects());
+            while let Some(res) = stream.next().await {
+                // The watcher reconnects by itself, the error is only reported to the user
+                let Some(job) = res
+                    .inspect_err(|err| {
+                        eprintln!(
+                            "{}",
+                            tr_args(
+                                "watch-interrupted",
+                                &[("name", name.clone()), ("error", err.to_string())]
+                            )
+                        )
+                    })
+                    .ok()
+                else {
+                    continue;
+                };
 
-        let conds = await_condition(job_api, name, is_job_completed());
-        let _ = tokio::time::timeout(duration, conds).await.map_err(|_| {
-            anyhow!("Job with name {name} may take more time than the maximum wait duration")
-        })?;
+                // A failed job won't complete, waiting until the deadline would hide the reason of the failure
+                match job::job_status(&job) {
+                    JobStatus::Succeeded => return Ok(()),
+                    JobStatus::Failed(reason) => {
+                        return Err(anyhow!(tr_args(
+                            "job-failed-reason",
+                            &[
+                                ("name", name.clone()),
+                                ("reason", reason.unwrap_or_else(|| tr("unknown-reason")))
+                            ]
+                        )));
+                    }
+                    _ => {}
+                }
+            }
+
+            Err(anyhow!(tr_args("watch-ended", &[("name", name.clone())])))
+        };
+
+        tokio::time::timeout(duration, completed)
+            .await
+            .map_err(|_| anyhow!(tr_args("wait-timeout", &[("name", name.clone())])))??;
 
         Ok(job)
     }
@@ -211,37 +1305,22 @@ where
     /// # Arguments
     ///
     /// * `job` - Job
-    pub fn display_spec(&self, mut job: Job) -> Result<Option<String>> {
+    pub fn display_spec(&self, mut job: Job, redact: bool) -> Result<Option<String>> {
         if !self.dry_run {
-            println!(
-                "Job {} created",
-                job.metadata
-                    .name
-                    .unwrap_or_default()
-                    .truecolor(COLOR.0, COLOR.1, COLOR.2)
-                    .bold()
-            );
+            let name = job
+                .metadata
+                .name
+                .unwrap_or_default()
+                .truecolor(COLOR.0, COLOR.1, COLOR.2)
+                .bold();
+            println!("{}", tr_args("job-created", &[("name", name.to_string())]));
 
             return Ok(None);
         }
 
         // Remove presence of managed fields from the job
         job.metadata.managed_fields = None;
-        // Remove presence of labels containing "controler-uid" in the metadata & template
-        if let Some(fields) = job.metadata.labels.as_mut() {
-            fields.remove(BATCH_UID_REMOVE);
-            fields.remove(UID_REMOVE);
-        }
-
-        if let Some(labels) = job
-            .spec
-            .as_mut()
-            .and_then(|spec| spec.template.metadata.as_mut())
-            .and_then(|tmpl| tmpl.labels.as_mut())
-        {
-            labels.remove(UID_REMOVE);
-            labels.remove(BATCH_UID_REMOVE);
-        }
+        remove_controller_uid(&mut job);
 
         job.spec
             .as_mut()
@@ -249,17 +1328,243 @@ where
             .and_then(|selector| selector.match_labels.as_mut())
             .map(|selector| selector.remove(BATCH_UID_REMOVE));
 
-        let yaml = serde_yml::to_string(&job)?;
+        // The file keeps the job as sent to the API server, before its defaults, so that the hash of its spec matches
+        // the spec hash annotation
+        let yaml = serde_yml::to_string(self.job.as_ref().unwrap_or(&job))?;
 
         if !self.dry_run_output_path {
+            let name = job
+                .metadata
+                .name
+                .clone()
+                .unwrap_or_default()
+                .bright_purple()
+                .bold();
             println!(
-                "\nDry run result for job {}",
-                job.metadata.name.unwrap_or_default().bright_purple().bold()
+                "\n{}",
+                tr_args("dry-run-result", &[("name", name.to_string())])
             );
 
-            println!("\n{yaml}");
+            // The printed spec is often shared, the sensitive values are masked. The file keeps them to be applied
+            match redact {
+                true => {
+                    let mut redacted = job;
+                    redact::redact_job(&mut redacted);
+                    println!("\n{}", serde_yml::to_string(&redacted)?);
+                }
+                false => println!("\n{}", serde_yml::to_string(&job)?),
+            }
         }
 
         Ok(Some(yaml))
     }
 }
+
+/// Object received from the watch of a job
+enum Watched {
+    Job(Box<Job>),
+    Pod(Box<Pod>),
+}
+
+/// Print the transition of the status of the object if it changed, colored according to the new status
+///
+/// # Arguments
+///
+/// * `transitions` - &mut job::Transitions
+/// * `object` - &str
+/// * `status` - &str
+fn print_transition(transitions: &mut job::Transitions, object: &str, status: &str) {
+    let Some(transition) = transitions.update(object, status) else {
+        return;
+    };
+
+    let transition = match status {
+        status if status.starts_with("Succeeded") => transition.bright_green(),
+        status if status.starts_with("Failed") => transition.bright_red(),
+        _ => transition.bright_yellow(),
+    };
+    println!(
+        "{} {transition}",
+        object.truecolor(COLOR.0, COLOR.1, COLOR.2)
+    );
+}
+
+/// Check whether the job was dispatched with bakkutteh
+///
+/// # Arguments
+///
+/// * `job` - &Job
+pub fn is_manual_job(job: &Job) -> bool {
+    job.metadata
+        .labels
+        .as_ref()
+        .and_then(|labels| labels.get(MANAGED_BY_LABEL))
+        .is_some_and(|managed_by| managed_by == MANAGED_BY)
+}
+
+/// Get the name of the object from which the manual job was dispatched
+///
+/// # Arguments
+///
+/// * `job` - &Job
+pub fn manual_job_source(job: &Job) -> Option<&str> {
+    job.metadata
+        .labels
+        .as_ref()
+        .and_then(|labels| labels.get(SOURCE_LABEL))
+        .map(String::as_str)
+}
+
+/// Rename the job dispatched previously so that it can be created again. The name is generated by the API server
+/// from the source of the job when no name is given (e.g. momo-manual-x7k2p)
+///
+/// # Arguments
+///
+/// * `job` - &mut Job
+/// * `name` - Option<&str>
+pub fn rename_job(job: &mut Job, name: Option<&str>) {
+    match name {
+        Some(name) => job.metadata.name = Some(name.to_string()),
+        None => {
+            let source = manual_job_source(job)
+                .or(job.metadata.name.as_deref())
+                .unwrap_or_default();
+            job.metadata.generate_name = Some(format!("{source}-manual-"));
+            job.metadata.name = None;
+        }
+    }
+
+    remove_job_name(job);
+}
+
+/// Get the spec of the job as it was rendered by bakkutteh, without the selector and the labels set by the API
+/// server
+///
+/// # Arguments
+///
+/// * `job` - &Job
+pub fn rendered_spec(job: &Job) -> Option<JobSpec> {
+    let mut job = job.clone();
+    remove_controller_uid(&mut job);
+    remove_job_name(&mut job);
+
+    job.spec.map(|spec| JobSpec {
+        selector: None,
+        ..spec
+    })
+}
+
+/// Remove the labels containing the name of the job in the metadata & template. They're set by the API server
+///
+/// # Arguments
+///
+/// * `job` - &mut Job
+fn remove_job_name(job: &mut Job) {
+    for labels in [
+        job.metadata.labels.as_mut(),
+        job.spec
+            .as_mut()
+            .and_then(|spec| spec.template.metadata.as_mut())
+            .and_then(|tmpl| tmpl.labels.as_mut()),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        labels.remove(JOB_NAME_LABEL);
+        labels.remove(BATCH_JOB_NAME_LABEL);
+    }
+}
+
+/// Remove the labels containing the "controller-uid" of the job in the metadata & template
+///
+/// # Arguments
+///
+/// * `job` - &mut Job
+fn remove_controller_uid(job: &mut Job) {
+    if let Some(fields) = job.metadata.labels.as_mut() {
+        fields.remove(BATCH_UID_REMOVE);
+        fields.remove(UID_REMOVE);
+    }
+
+    if let Some(labels) = job
+        .spec
+        .as_mut()
+        .and_then(|spec| spec.template.metadata.as_mut())
+        .and_then(|tmpl| tmpl.labels.as_mut())
+    {
+        labels.remove(UID_REMOVE);
+        labels.remove(BATCH_UID_REMOVE);
+    }
+}
+
+/// Build the client from the kubeconfig or the in-cluster config. The requests are logged when the level is above 0
+///
+/// # Arguments
+///
+/// * `api_log` - u8
+async fn build_client(api_log: u8) -> Result<Client> {
+    let config = Config::infer().await?;
+    if api_log == 0 {
+        return Ok(Client::try_from(config)?);
+    }
+
+    let client = ClientBuilder::try_from(config)?
+        .with_layer(&api_log::ApiLogLayer(api_log))
+        .build();
+
+    Ok(client)
+}
+
+/// Convert the error of the API. Denied permissions are explained with the RBAC objects which would grant them
+///
+/// # Arguments
+///
+/// * `err` - kube::Error
+fn api_error(err: kube::Error) -> anyhow::Error {
+    bundle::record_api_error(&err);
+
+    match &err {
+        kube::Error::Api(resp) if resp.code == FORBIDDEN => {
+            match rbac::Denied::parse(&resp.message) {
+                // The response is kept as the source so that the denial can be told apart
+                Some(denied) => {
+                    let explanation = denied.explain();
+                    anyhow::Error::from(err).context(explanation)
+                }
+                None => err.into(),
+            }
+        }
+        _ => err.into(),
+    }
+}
+
+/// Check whether the request was denied by the API server (e.g. the user can't list the pods of kube-system)
+///
+/// # Arguments
+///
+/// * `err` - &anyhow::Error
+pub fn is_forbidden(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<kube::Error>(),
+        Some(kube::Error::Api(resp)) if resp.code == FORBIDDEN
+    )
+}
+
+/// Get the names of the objects referenced by the env of the containers
+///
+/// # Arguments
+///
+/// * `envs` - &[ContainerEnv]
+/// * `name` - F
+fn referenced_names<F>(envs: &[ContainerEnv], name: F) -> BTreeSet<String>
+where
+    F: Fn(&EnvVarSource) -> Option<String>,
+{
+    envs.iter()
+        .flat_map(|container| container.envs.values())
+        .filter_map(|kind| match kind {
+            EnvKind::ConfigMap(source) | EnvKind::Secret(source) => name(source),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,421 @@
+use anyhow::{Result, anyhow};
+use k8s_openapi::api::core::v1::{
+    HostAlias, PersistentVolumeClaimVolumeSource, Pod, PodDNSConfig, PodDNSConfigOption, PodSpec,
+    ProjectedVolumeSource, ServiceAccountTokenProjection, Volume, VolumeMount, VolumeProjection,
+};
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+
+// Constant
+const HOSTNAMES_SEPARATOR: char = ',';
+// The pod only uses the dnsConfig with this policy, which requires at least a nameserver
+const DNS_POLICY_NONE: &str = "None";
+pub const DNS_POLICIES: [&str; 4] = ["ClusterFirst", "ClusterFirstWithHostNet", "Default", "None"];
+// Scheduler used when the pod spec doesn't define any
+pub const DEFAULT_SCHEDULER: &str = "default-scheduler";
+const SCHEDULER_NAME_ARG: &str = "--scheduler-name";
+const PVC_VOLUME_PREFIX: &str = "bakkutteh-pvc";
+// Projected service account tokens. The API server refuses an expiration below 10 minutes
+const TOKEN_FILE: &str = "token";
+const TOKEN_VOLUME_PREFIX: &str = "bakkutteh-token";
+const DEFAULT_TOKEN_EXPIRATION: i64 = 3600;
+const MIN_TOKEN_EXPIRATION: i64 = 600;
+
+/// Parse a host alias written with the format IP=HOSTNAME[,HOSTNAME...]
+///
+/// # Arguments
+///
+/// * `input` - &str
+pub fn parse_host_alias(input: &str) -> Result<HostAlias> {
+    let format_err = || anyhow!("Host alias should respect the format: IP=HOSTNAME[,HOSTNAME...]");
+
+    let (ip, hostnames) = input.split_once('=').ok_or_else(format_err)?;
+    let ip = ip
+        .trim()
+        .parse::<IpAddr>()
+        .map_err(|err| anyhow!("Invalid IP {ip} for the host alias: {err}"))?;
+    let hostnames = hostnames
+        .split(HOSTNAMES_SEPARATOR)
+        .map(str::trim)
+        .filter(|hostname| !hostname.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    if hostnames.is_empty() {
+        return Err(format_err());
+    }
+
+    Ok(HostAlias {
+        ip: ip.to_string(),
+        hostnames: Some(hostnames),
+    })
+}
+
+/// Append the host aliases to the ones defined by the pod spec
+///
+/// # Arguments
+///
+/// * `spec` - &mut PodSpec
+/// * `aliases` - &[HostAlias]
+pub fn add_host_aliases(spec: &mut PodSpec, aliases: &[HostAlias]) {
+    if aliases.is_empty() {
+        return;
+    }
+
+    spec.host_aliases
+        .get_or_insert_default()
+        .extend(aliases.iter().cloned());
+}
+
+/// Parse a resolver option written with the format NAME[=VALUE] (e.g. ndots=2)
+///
+/// # Arguments
+///
+/// * `input` - &str
+pub fn parse_dns_option(input: &str) -> Result<PodDNSConfigOption> {
+    let (name, value) = match input.split_once('=') {
+        Some((name, value)) => (name.trim(), Some(value.trim().to_string())),
+        None => (input.trim(), None),
+    };
+
+    if name.is_empty() {
+        return Err(anyhow!(
+            "DNS option should respect the format: NAME[=VALUE]"
+        ));
+    }
+
+    Ok(PodDNSConfigOption {
+        name: Some(name.to_string()),
+        value,
+    })
+}
+
+/// Override the dnsPolicy and append the nameservers, searches & options to the dnsConfig of the pod spec
+///
+/// # Arguments
+///
+/// * `spec` - &mut PodSpec
+/// * `policy` - Option<&str>
+/// * `config` - PodDNSConfig
+pub fn set_dns(spec: &mut PodSpec, policy: Option<&str>, config: PodDNSConfig) -> Result<()> {
+    if let Some(policy) = policy {
+        spec.dns_policy = Some(policy.to_string());
+    }
+
+    let PodDNSConfig {
+        nameservers,
+        searches,
+        options,
+    } = config;
+    if nameservers.is_some() || searches.is_some() || options.is_some() {
+        let dns_config = spec.dns_config.get_or_insert_default();
+        for (current, added) in [
+            (&mut dns_config.nameservers, nameservers),
+            (&mut dns_config.searches, searches),
+        ] {
+            current
+                .get_or_insert_default()
+                .extend(added.into_iter().flatten());
+        }
+        dns_config
+            .options
+            .get_or_insert_default()
+            .extend(options.into_iter().flatten());
+    }
+
+    let has_nameserver = spec
+        .dns_config
+        .as_ref()
+        .and_then(|config| config.nameservers.as_ref())
+        .is_some_and(|nameservers| !nameservers.is_empty());
+    if spec.dns_policy.as_deref() == Some(DNS_POLICY_NONE) && !has_nameserver {
+        return Err(anyhow!(
+            "The dnsPolicy {DNS_POLICY_NONE} requires at least a nameserver (--dns-nameserver)"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Service account token bound to an audience, projected in a volume mounted in the containers
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectedToken {
+    pub audience: String,
+    pub expiration_seconds: i64,
+    pub mount_path: String,
+}
+
+/// Parse a projected token written with the format audience=AUDIENCE,path=MOUNT_PATH[,expiration=SECONDS]
+///
+/// # Arguments
+///
+/// * `input` - &str
+pub fn parse_projected_token(input: &str) -> Result<ProjectedToken> {
+    let format_err = || {
+        anyhow!(
+            "Projected token should respect the format: audience=AUDIENCE,path=MOUNT_PATH[,expiration=SECONDS]"
+        )
+    };
+
+    let (mut audience, mut mount_path, mut expiration_seconds) =
+        (None, None, DEFAULT_TOKEN_EXPIRATION);
+    for field in input.split(',') {
+        match field.split_once('=').ok_or_else(format_err)? {
+            ("audience", value) => audience = Some(value.trim().to_string()),
+            ("path", value) => mount_path = Some(value.trim().to_string()),
+            ("expiration", value) => {
+                expiration_seconds = value
+                    .trim()
+                    .parse()
+                    .map_err(|err| anyhow!("Invalid expiration {value}: {err}"))?
+            }
+            _ => return Err(format_err()),
+        }
+    }
+
+    if expiration_seconds < MIN_TOKEN_EXPIRATION {
+        return Err(anyhow!(
+            "The expiration of a projected token should be at least {MIN_TOKEN_EXPIRATION} seconds"
+        ));
+    }
+
+    match (audience, mount_path) {
+        (Some(audience), Some(mount_path)) if !audience.is_empty() && !mount_path.is_empty() => {
+            Ok(ProjectedToken {
+                audience,
+                expiration_seconds,
+                mount_path,
+            })
+        }
+        _ => Err(format_err()),
+    }
+}
+
+/// Add a volume for each projected token and mount it in every container. The token is written in the `token` file
+///
+/// # Arguments
+///
+/// * `spec` - &mut PodSpec
+/// * `tokens` - &[ProjectedToken]
+pub fn add_projected_tokens(spec: &mut PodSpec, tokens: &[ProjectedToken]) {
+    for (idx, token) in tokens.iter().enumerate() {
+        let name = format!("{TOKEN_VOLUME_PREFIX}-{idx}");
+
+        spec.volumes.get_or_insert_default().push(Volume {
+            name: name.clone(),
+            projected: Some(ProjectedVolumeSource {
+                sources: Some(vec![VolumeProjection {
+                    service_account_token: Some(ServiceAccountTokenProjection {
+                        audience: Some(token.audience.clone()),
+                        expiration_seconds: Some(token.expiration_seconds),
+                        path: TOKEN_FILE.to_string(),
+                    }),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        for container in spec.containers.iter_mut() {
+            container
+                .volume_mounts
+                .get_or_insert_default()
+                .push(VolumeMount {
+                    name: name.clone(),
+                    mount_path: token.mount_path.clone(),
+                    read_only: Some(true),
+                    ..Default::default()
+                });
+        }
+    }
+}
+
+/// Mount the PersistentVolumeClaim at the path in the container. The volume of the pod referencing the claim is
+/// reused if any, otherwise a volume is added
+///
+/// # Arguments
+///
+/// * `spec` - &mut PodSpec
+/// * `claim` - &str
+/// * `container` - &str
+/// * `mount_path` - &str
+pub fn mount_pvc(spec: &mut PodSpec, claim: &str, container: &str, mount_path: &str) -> Result<()> {
+    if !mount_path.starts_with('/') {
+        return Err(anyhow!("The mount path {mount_path} should be absolute"));
+    }
+
+    let volumes = spec.volumes.get_or_insert_default();
+    let existing = volumes.iter().find(|volume| {
+        volume
+            .persistent_volume_claim
+            .as_ref()
+            .is_some_and(|source| source.claim_name == claim)
+    });
+    let name = match existing {
+        Some(volume) => volume.name.clone(),
+        None => {
+            let name = format!("{PVC_VOLUME_PREFIX}-{}", volumes.len());
+            volumes.push(Volume {
+                name: name.clone(),
+                persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                    claim_name: claim.to_string(),
+                    read_only: None,
+                }),
+                ..Default::default()
+            });
+
+            name
+        }
+    };
+
+    let container = spec
+        .containers
+        .iter_mut()
+        .find(|ct| ct.name == container)
+        .ok_or_else(|| anyhow!("Unable to find the container {container}"))?;
+    let mounts = container.volume_mounts.get_or_insert_default();
+    if mounts.iter().any(|mount| mount.mount_path == mount_path) {
+        return Err(anyhow!(
+            "The path {mount_path} is already mounted in the container {}",
+            container.name
+        ));
+    }
+
+    mounts.push(VolumeMount {
+        name,
+        mount_path: mount_path.to_string(),
+        ..Default::default()
+    });
+
+    Ok(())
+}
+
+/// Get the names of the schedulers run by the pods (e.g. from the `--scheduler-name` argument of volcano).
+/// The default scheduler is always part of the names
+///
+/// # Arguments
+///
+/// * `pods` - &[Pod]
+pub fn scheduler_names(pods: &[Pod]) -> BTreeSet<String> {
+    let mut names = BTreeSet::from([DEFAULT_SCHEDULER.to_string()]);
+
+    let args = pods
+        .iter()
+        .filter_map(|pod| pod.spec.as_ref())
+        .flat_map(|spec| spec.containers.iter())
+        .flat_map(|container| {
+            container
+                .command
+                .iter()
+                .flatten()
+                .chain(container.args.iter().flatten())
+        })
+        .collect::<Vec<_>>();
+
+    for (idx, arg) in args.iter().enumerate() {
+        let name = match arg.split_once('=') {
+            Some((flag, name)) if flag == SCHEDULER_NAME_ARG => Some(name),
+            None if arg.as_str() == SCHEDULER_NAME_ARG => {
+                args.get(idx + 1).map(|name| name.as_str())
+            }
+            _ => None,
+        };
+
+        if let Some(name) = name.filter(|name| !name.is_empty()) {
+            names.insert(name.to_string());
+        }
+    }
+
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mount_pvc, parse_host_alias, parse_projected_token, scheduler_names, set_dns};
+    use k8s_openapi::api::core::v1::{Pod, PodDNSConfig, PodSpec};
+
+    #[test]
+    fn expect_to_parse_host_alias() {
+        let alias = parse_host_alias("10.0.0.12=api.staging.local, db.staging.local").unwrap();
+        assert_eq!(alias.ip, "10.0.0.12");
+        assert_eq!(
+            alias.hostnames.unwrap(),
+            vec!["api.staging.local", "db.staging.local"]
+        );
+
+        assert!(parse_host_alias("staging=api.staging.local").is_err());
+        assert!(parse_host_alias("10.0.0.12=").is_err());
+    }
+
+    #[test]
+    fn expect_none_policy_to_require_nameserver() {
+        let mut spec = PodSpec::default();
+        assert!(set_dns(&mut spec, Some("None"), PodDNSConfig::default()).is_err());
+
+        let config = PodDNSConfig {
+            nameservers: Some(vec!["10.0.0.53".to_string()]),
+            searches: Some(vec!["staging.local".to_string()]),
+            ..Default::default()
+        };
+        set_dns(&mut spec, Some("None"), config).unwrap();
+
+        let dns_config = spec.dns_config.unwrap();
+        assert_eq!(dns_config.nameservers.unwrap(), vec!["10.0.0.53"]);
+        assert_eq!(dns_config.searches.unwrap(), vec!["staging.local"]);
+    }
+
+    #[test]
+    fn expect_to_discover_schedulers() {
+        let pod: Pod = serde_json::from_value(serde_json::json!({
+            "spec": {
+                "containers": [{
+                    "name": "volcano-scheduler",
+                    "args": ["--logtostderr", "--scheduler-name=volcano"]
+                }]
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            scheduler_names(&[pod]).into_iter().collect::<Vec<_>>(),
+            vec!["default-scheduler", "volcano"]
+        );
+    }
+
+    #[test]
+    fn expect_to_parse_projected_token() {
+        let token = parse_projected_token("audience=vault,path=/var/run/secrets/vault").unwrap();
+        assert_eq!(token.audience, "vault");
+        assert_eq!(token.mount_path, "/var/run/secrets/vault");
+        assert_eq!(token.expiration_seconds, 3600);
+
+        assert!(parse_projected_token("audience=vault").is_err());
+        assert!(parse_projected_token("audience=vault,path=/token,expiration=60").is_err());
+    }
+
+    #[test]
+    fn expect_to_mount_pvc() {
+        let mut spec: PodSpec = serde_json::from_value(serde_json::json!({
+            "containers": [{ "name": "main" }, { "name": "proxy" }]
+        }))
+        .unwrap();
+
+        mount_pvc(&mut spec, "repair-data", "main", "/data").unwrap();
+        // The volume of the claim is reused
+        mount_pvc(&mut spec, "repair-data", "proxy", "/data").unwrap();
+
+        let volumes = spec.volumes.as_ref().unwrap();
+        assert_eq!(volumes.len(), 1);
+        for container in &spec.containers {
+            let mount = &container.volume_mounts.as_ref().unwrap()[0];
+            assert_eq!(
+                (mount.name.as_str(), mount.mount_path.as_str()),
+                ("bakkutteh-pvc-0", "/data")
+            );
+        }
+
+        assert!(mount_pvc(&mut spec, "repair-data", "main", "/data").is_err());
+        assert!(mount_pvc(&mut spec, "repair-data", "main", "data").is_err());
+    }
+}
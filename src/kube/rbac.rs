@@ -0,0 +1,133 @@
+// Constant
+const SERVICE_ACCOUNT_PREFIX: &str = "system:serviceaccount:";
+const ROLE_NAME: &str = "bakkutteh";
+
+/// Permission denied by the API server, parsed from the message of a 403 response e.g:
+/// jobs.batch is forbidden: User "alice" cannot create resource "jobs" in API group "batch" in the namespace "default"
+#[derive(Debug, PartialEq)]
+pub struct Denied {
+    user: String,
+    verb: String,
+    resource: String,
+    group: String,
+    namespace: Option<String>,
+}
+
+impl Denied {
+    /// Parse the message of a 403 response
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - &str
+    pub fn parse(message: &str) -> Option<Self> {
+        let user = between(message, "User \"", "\"")?;
+        let verb = between(message, "cannot ", " resource")?;
+        let resource = between(message, "resource \"", "\"")?;
+        let group = between(message, "in API group \"", "\"").unwrap_or_default();
+        let namespace = between(message, "in the namespace \"", "\"");
+
+        Some(Self {
+            user: user.to_string(),
+            verb: verb.to_string(),
+            resource: resource.to_string(),
+            group: group.to_string(),
+            namespace: namespace.map(str::to_string),
+        })
+    }
+
+    /// Explain which permission is missing along with a Role & RoleBinding granting it
+    pub fn explain(&self) -> String {
+        let scope = match &self.namespace {
+            Some(ns) => format!("in the namespace {ns}"),
+            None => "at the cluster scope".to_string(),
+        };
+
+        format!(
+            "{} is not allowed to {} {} (API group {:?}) {scope}. A cluster admin can grant it with:\n\n{}",
+            self.user,
+            self.verb,
+            self.resource,
+            self.group,
+            self.snippet()
+        )
+    }
+
+    fn snippet(&self) -> String {
+        let (role_kind, binding_kind, namespace) = match &self.namespace {
+            Some(ns) => ("Role", "RoleBinding", format!("\n  namespace: {ns}")),
+            None => ("ClusterRole", "ClusterRoleBinding", String::new()),
+        };
+
+        let subject = match self
+            .user
+            .strip_prefix(SERVICE_ACCOUNT_PREFIX)
+            .and_then(|sa| sa.split_once(':'))
+        {
+            Some((ns, name)) => {
+                format!("  - kind: ServiceAccount\n    name: {name}\n    namespace: {ns}")
+            }
+            None => format!(
+                "  - kind: User\n    name: {}\n    apiGroup: rbac.authorization.k8s.io",
+                self.user
+            ),
+        };
+
+        format!(
+            "apiVersion: rbac.authorization.k8s.io/v1
+kind: {role_kind}
+metadata:
+  name: {ROLE_NAME}{namespace}
+rules:
+  - apiGroups: [\"{}\"]
+    resources: [\"{}\"]
+    verbs: [\"{}\"]
+---
+apiVersion: rbac.authorization.k8s.io/v1
+kind: {binding_kind}
+metadata:
+  name: {ROLE_NAME}{namespace}
+subjects:
+{subject}
+roleRef:
+  kind: {role_kind}
+  name: {ROLE_NAME}
+  apiGroup: rbac.authorization.k8s.io",
+            self.group, self.resource, self.verb
+        )
+    }
+}
+
+fn between<'a>(value: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = value.split_once(start)?;
+    rest.split_once(end).map(|(found, _)| found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Denied;
+
+    #[test]
+    fn expect_to_parse_denied_permission() {
+        let denied = Denied::parse(
+            "jobs.batch is forbidden: User \"system:serviceaccount:ci:deployer\" cannot create resource \"jobs\" in API group \"batch\" in the namespace \"default\"",
+        )
+        .unwrap();
+
+        assert_eq!(
+            denied,
+            Denied {
+                user: "system:serviceaccount:ci:deployer".to_string(),
+                verb: "create".to_string(),
+                resource: "jobs".to_string(),
+                group: "batch".to_string(),
+                namespace: Some("default".to_string()),
+            }
+        );
+
+        let explanation = denied.explain();
+        assert!(explanation.contains("kind: RoleBinding"));
+        assert!(
+            explanation.contains("kind: ServiceAccount\n    name: deployer\n    namespace: ci")
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+// Constant
+// Labels & annotation set on the workloads of a release by the charts or by Helm itself
+const INSTANCE_LABEL: &str = "app.kubernetes.io/instance";
+const HELM2_RELEASE_LABEL: &str = "release";
+const HELM_RELEASE_ANNOTATION: &str = "meta.helm.sh/release-name";
+
+/// Check whether the object belongs to the Helm release. The labels can't be matched with a single label
+/// selector, hence the objects are filtered once listed
+///
+/// # Arguments
+///
+/// * `meta` - &ObjectMeta
+/// * `release` - &str
+pub fn belongs_to(meta: &ObjectMeta, release: &str) -> bool {
+    let labeled = [INSTANCE_LABEL, HELM2_RELEASE_LABEL].iter().any(|key| {
+        meta.labels
+            .as_ref()
+            .and_then(|l| l.get(*key))
+            .map(String::as_str)
+            == Some(release)
+    });
+
+    labeled
+        || meta
+            .annotations
+            .as_ref()
+            .and_then(|a| a.get(HELM_RELEASE_ANNOTATION))
+            .is_some_and(|name| name == release)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::belongs_to;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn expect_to_match_release() {
+        let meta = |labels: &[(&str, &str)], annotations: &[(&str, &str)]| ObjectMeta {
+            labels: Some(BTreeMap::from_iter(
+                labels.iter().map(|(k, v)| (k.to_string(), v.to_string())),
+            )),
+            annotations: Some(BTreeMap::from_iter(
+                annotations
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string())),
+            )),
+            ..Default::default()
+        };
+
+        assert!(belongs_to(
+            &meta(&[("app.kubernetes.io/instance", "billing")], &[]),
+            "billing"
+        ));
+        assert!(belongs_to(&meta(&[("release", "billing")], &[]), "billing"));
+        assert!(belongs_to(
+            &meta(&[], &[("meta.helm.sh/release-name", "billing")]),
+            "billing"
+        ));
+        assert!(!belongs_to(
+            &meta(&[("app.kubernetes.io/instance", "payments")], &[]),
+            "billing"
+        ));
+        assert!(!belongs_to(&ObjectMeta::default(), "billing"));
+    }
+}
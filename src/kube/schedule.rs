@@ -0,0 +1,387 @@
+use anyhow::{Result, anyhow};
+use jiff::{
+    Timestamp, ToSpan, Zoned,
+    civil::{Date, DateTime, Time},
+    tz::TimeZone,
+};
+
+// Constant
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+// Prefixes which can be used to set the time zone in the schedule itself
+const TZ_PREFIXES: [&str; 2] = ["CRON_TZ=", "TZ="];
+// Format used to display a run
+const RUN_FORMAT: &str = "%Y-%m-%d %H:%M %Z";
+// Maximum number of years looked ahead when searching the next run
+const MAX_LOOKAHEAD_YEARS: i16 = 5;
+
+/// Schedule is a parsed cron expression of a CronJob in the time zone of the CronJob
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+    time_zone: TimeZone,
+}
+
+impl Schedule {
+    /// Parse the schedule of a CronJob. The time zone defaults to UTC when the CronJob doesn't specify it
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - &str
+    /// * `time_zone` - Option<&str>
+    pub fn parse(expr: &str, time_zone: Option<&str>) -> Result<Self> {
+        let mut expr = expr.trim();
+        let mut time_zone = time_zone.map(str::to_string);
+
+        // The schedule may embed the time zone e.g: CRON_TZ=Asia/Singapore 0 * * * *
+        if let Some((prefix, rest)) = expr.split_once(char::is_whitespace)
+            && let Some(tz) = TZ_PREFIXES.iter().find_map(|p| prefix.strip_prefix(p))
+        {
+            time_zone = Some(tz.to_string());
+            expr = rest.trim();
+        }
+
+        let expr = match expr {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expr => expr,
+        };
+
+        let fields = expr.split_whitespace().collect::<Vec<_>>();
+        let [minute, hour, day, month, weekday] = fields.as_slice() else {
+            return Err(anyhow!(
+                "The schedule {expr:?} should contains 5 fields (minute hour day month weekday)"
+            ));
+        };
+
+        let time_zone = match time_zone {
+            Some(tz) => {
+                TimeZone::get(&tz).map_err(|err| anyhow!("Unknown time zone {tz:?}: {err}"))?
+            }
+            None => TimeZone::UTC,
+        };
+
+        // Sunday can be written as 7
+        let mut weekdays = parse_field(weekday, 0, 7, &WEEKDAYS, 0)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[], 0)?,
+            hours: parse_field(hour, 0, 23, &[], 0)?,
+            days: parse_field(day, 1, 31, &[], 0)?,
+            months: parse_field(month, 1, 12, &MONTHS, 1)?,
+            weekdays,
+            any_day: day.starts_with(['*', '?']),
+            any_weekday: weekday.starts_with(['*', '?']),
+            time_zone,
+        })
+    }
+
+    /// Get the next run strictly after the given timestamp
+    ///
+    /// # Arguments
+    ///
+    /// * `after` - Timestamp
+    pub fn next_after(&self, after: Timestamp) -> Option<Zoned> {
+        let start = self.time_zone.to_datetime(after);
+        let mut dt = start
+            .date()
+            .to_datetime(Time::new(start.hour(), start.minute(), 0, 0).ok()?)
+            .checked_add(1.minute())
+            .ok()?;
+        let limit = start.year() + MAX_LOOKAHEAD_YEARS;
+
+        while dt.year() <= limit {
+            if !has(self.months, dt.month()) {
+                dt = dt
+                    .date()
+                    .first_of_month()
+                    .checked_add(1.month())
+                    .ok()?
+                    .to_datetime(Time::midnight());
+                continue;
+            }
+
+            if !self.matches_day(dt.date()) {
+                dt = dt.date().tomorrow().ok()?.to_datetime(Time::midnight());
+                continue;
+            }
+
+            if !has(self.hours, dt.hour()) {
+                dt = start_of_hour(dt)?.checked_add(1.hour()).ok()?;
+                continue;
+            }
+
+            if has(self.minutes, dt.minute()) {
+                // Datetimes in a DST gap are moved after the gap
+                let zoned = self.time_zone.to_ambiguous_zoned(dt).compatible().ok()?;
+                if zoned.timestamp() > after {
+                    return Some(zoned);
+                }
+            }
+
+            dt = dt.checked_add(1.minute()).ok()?;
+        }
+
+        None
+    }
+
+    /// Get the next runs after the given timestamp
+    ///
+    /// # Arguments
+    ///
+    /// * `after` - Timestamp
+    /// * `count` - usize
+    pub fn upcoming(&self, after: Timestamp, count: usize) -> Vec<Zoned> {
+        let mut runs: Vec<Zoned> = Vec::with_capacity(count);
+        while runs.len() < count {
+            let from = runs.last().map_or(after, |run| run.timestamp());
+            match self.next_after(from) {
+                Some(run) => runs.push(run),
+                None => break,
+            }
+        }
+
+        runs
+    }
+
+    /// Get the number of seconds between the next two runs after the given timestamp
+    ///
+    /// # Arguments
+    ///
+    /// * `after` - Timestamp
+    pub fn interval_seconds(&self, after: Timestamp) -> Option<i64> {
+        match self.upcoming(after, 2).as_slice() {
+            [first, second] => Some(second.timestamp().as_second() - first.timestamp().as_second()),
+            _ => None,
+        }
+    }
+
+    // Like cron, when both the day of month and the day of week are restricted, either of them can match
+    fn matches_day(&self, date: Date) -> bool {
+        let day = has(self.days, date.day());
+        let weekday = has(self.weekdays, date.weekday().to_sunday_zero_offset());
+
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        }
+    }
+}
+
+/// Render the run in the time zone of the schedule and in the local time zone when the offsets differ
+///
+/// # Arguments
+///
+/// * `run` - &Zoned
+/// * `local` - &TimeZone
+pub fn format_run(run: &Zoned, local: &TimeZone) -> String {
+    let scheduled = run.strftime(RUN_FORMAT).to_string();
+    let local_run = run.with_time_zone(local.clone());
+
+    match local_run.offset() == run.offset() {
+        true => scheduled,
+        false => format!("{scheduled} (local: {})", local_run.strftime(RUN_FORMAT)),
+    }
+}
+
+fn has(bits: u64, value: i8) -> bool {
+    bits & (1 << value) != 0
+}
+
+fn start_of_hour(dt: DateTime) -> Option<DateTime> {
+    Some(dt.date().to_datetime(Time::new(dt.hour(), 0, 0, 0).ok()?))
+}
+
+/// Parse a field of the cron expression into a bitset of the allowed values.
+/// Supports `*`, `?`, lists (`1,2`), ranges (`1-5`), steps (`*/5`, `1-10/2`, `3/5`) and names (`MON`, `JAN`)
+///
+/// # Arguments
+///
+/// * `field` - &str
+/// * `min` - i8
+/// * `max` - i8
+/// * `names` - &[&str]
+/// * `offset` - i8 (value of the first name)
+fn parse_field(field: &str, min: i8, max: i8, names: &[&str], offset: i8) -> Result<u64> {
+    let parse_value = |value: &str| -> Result<i8> {
+        let parsed = match value.parse::<i8>() {
+            Ok(v) => v,
+            Err(_) => names
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(value))
+                .and_then(|idx| i8::try_from(idx).ok())
+                .map(|idx| idx + offset)
+                .ok_or_else(|| anyhow!("Invalid value {value:?} in the cron field {field:?}"))?,
+        };
+
+        match parsed >= min && parsed <= max {
+            true => Ok(parsed),
+            false => Err(anyhow!(
+                "Value {parsed} of the cron field {field:?} should be between {min} and {max}"
+            )),
+        }
+    };
+
+    let mut bits = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (
+                range,
+                Some(
+                    step.parse::<i8>()
+                        .ok()
+                        .filter(|step| *step > 0)
+                        .ok_or_else(|| anyhow!("Invalid step in the cron field {field:?}"))?,
+                ),
+            ),
+            None => (part, None),
+        };
+
+        let (start, end) = match range {
+            "*" | "?" => (min, max),
+            range => match range.split_once('-') {
+                Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+                // A single value with a step runs until the maximum e.g: 5/15
+                None => {
+                    let value = parse_value(range)?;
+                    (value, step.map_or(value, |_| max))
+                }
+            },
+        };
+
+        if start > end {
+            return Err(anyhow!("Invalid range in the cron field {field:?}"));
+        }
+
+        let step = step.unwrap_or(1);
+        let mut value = start;
+        while value <= end {
+            bits |= 1 << value;
+            value = match value.checked_add(step) {
+                Some(v) => v,
+                None => break,
+            };
+        }
+    }
+
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Schedule, format_run};
+    use jiff::{Timestamp, tz::TimeZone};
+
+    fn upcoming(expr: &str, tz: Option<&str>, from: &str, count: usize) -> Vec<String> {
+        let schedule = Schedule::parse(expr, tz).unwrap();
+        schedule
+            .upcoming(from.parse::<Timestamp>().unwrap(), count)
+            .into_iter()
+            .map(|run| run.timestamp().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn expect_to_compute_upcoming_runs() {
+        assert_eq!(
+            upcoming("*/15 * * * *", None, "2025-01-01T10:07:00Z", 3),
+            vec![
+                "2025-01-01T10:15:00Z",
+                "2025-01-01T10:30:00Z",
+                "2025-01-01T10:45:00Z"
+            ]
+        );
+
+        assert_eq!(
+            upcoming("0 2 * * MON-FRI", None, "2025-01-03T03:00:00Z", 2),
+            vec!["2025-01-06T02:00:00Z", "2025-01-07T02:00:00Z"]
+        );
+
+        assert_eq!(
+            upcoming("@monthly", None, "2025-01-15T00:00:00Z", 1),
+            vec!["2025-02-01T00:00:00Z"]
+        );
+    }
+
+    #[test]
+    fn expect_to_use_time_zone() {
+        assert_eq!(
+            upcoming(
+                "0 9 * * *",
+                Some("Asia/Singapore"),
+                "2025-01-01T00:00:00Z",
+                1
+            ),
+            vec!["2025-01-01T01:00:00Z"]
+        );
+
+        assert_eq!(
+            upcoming(
+                "CRON_TZ=Asia/Singapore 0 9 * * *",
+                None,
+                "2025-01-01T00:00:00Z",
+                1
+            ),
+            vec!["2025-01-01T01:00:00Z"]
+        );
+    }
+
+    #[test]
+    fn expect_to_compute_interval() {
+        let after = "2025-01-01T10:07:00Z".parse::<Timestamp>().unwrap();
+
+        assert_eq!(
+            Schedule::parse("@hourly", None)
+                .unwrap()
+                .interval_seconds(after),
+            Some(3600)
+        );
+        assert_eq!(
+            Schedule::parse("*/15 * * * *", None)
+                .unwrap()
+                .interval_seconds(after),
+            Some(900)
+        );
+    }
+
+    #[test]
+    fn expect_invalid_schedule_to_fail() {
+        assert!(Schedule::parse("* * * *", None).is_err());
+        assert!(Schedule::parse("60 * * * *", None).is_err());
+        assert!(Schedule::parse("* * * * FOO", None).is_err());
+        assert!(Schedule::parse("* * * * *", Some("Mars/Olympus")).is_err());
+    }
+
+    #[test]
+    fn expect_to_format_run_in_both_zones() {
+        let schedule = Schedule::parse("0 9 * * *", Some("Asia/Singapore")).unwrap();
+        let run = schedule
+            .next_after("2025-01-01T00:00:00Z".parse::<Timestamp>().unwrap())
+            .unwrap();
+
+        assert_eq!(
+            format_run(&run, &TimeZone::UTC),
+            "2025-01-01 09:00 +08 (local: 2025-01-01 01:00 UTC)"
+        );
+        assert_eq!(
+            format_run(&run, &TimeZone::get("Asia/Singapore").unwrap()),
+            "2025-01-01 09:00 +08"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use k8s_openapi::api::core::v1::{PodSpec, SeccompProfile};
+
+// Constant
+// Label of the namespace holding the enforced Pod Security profile
+pub const ENFORCE_LABEL: &str = "pod-security.kubernetes.io/enforce";
+pub const RESTRICTED: &str = "restricted";
+const SECCOMP_RUNTIME_DEFAULT: &str = "RuntimeDefault";
+const SECCOMP_LOCALHOST: &str = "Localhost";
+const DROP_ALL: &str = "ALL";
+
+/// Set the securityContext fields required by the restricted Pod Security profile. Missing fields are added and the
+/// values refused by the profile are overridden, compliant values set by the template are kept as is.
+/// Returns the list of the fields which were changed
+///
+/// # Arguments
+///
+/// * `spec` - &mut PodSpec
+pub fn restrict(spec: &mut PodSpec) -> Vec<String> {
+    let mut changes = Vec::new();
+    let pod_ctx = spec.security_context.get_or_insert_default();
+
+    if pod_ctx.run_as_non_root != Some(true) {
+        pod_ctx.run_as_non_root = Some(true);
+        changes.push("securityContext.runAsNonRoot: true".to_string());
+    }
+
+    let seccomp_allowed = pod_ctx
+        .seccomp_profile
+        .as_ref()
+        .is_some_and(|p| p.type_ == SECCOMP_RUNTIME_DEFAULT || p.type_ == SECCOMP_LOCALHOST);
+    if !seccomp_allowed {
+        pod_ctx.seccomp_profile = Some(SeccompProfile {
+            type_: SECCOMP_RUNTIME_DEFAULT.to_string(),
+            ..Default::default()
+        });
+        changes.push(format!(
+            "securityContext.seccompProfile.type: {SECCOMP_RUNTIME_DEFAULT}"
+        ));
+    }
+
+    let containers = spec
+        .init_containers
+        .iter_mut()
+        .flatten()
+        .chain(spec.containers.iter_mut());
+
+    for container in containers {
+        let ctx = container.security_context.get_or_insert_default();
+
+        // A container can't override the runAsNonRoot of the pod with false
+        if ctx.run_as_non_root == Some(false) {
+            ctx.run_as_non_root = Some(true);
+            changes.push(format!(
+                "{}: securityContext.runAsNonRoot: true",
+                container.name
+            ));
+        }
+
+        if ctx.allow_privilege_escalation != Some(false) {
+            ctx.allow_privilege_escalation = Some(false);
+            changes.push(format!(
+                "{}: securityContext.allowPrivilegeEscalation: false",
+                container.name
+            ));
+        }
+
+        let capabilities = ctx.capabilities.get_or_insert_default();
+        let drop = capabilities.drop.get_or_insert_default();
+        if !drop.iter().any(|c| c == DROP_ALL) {
+            drop.push(DROP_ALL.to_string());
+            changes.push(format!(
+                "{}: securityContext.capabilities.drop: [{}]",
+                container.name,
+                drop.join(", ")
+            ));
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::restrict;
+    use k8s_openapi::api::core::v1::PodSpec;
+
+    #[test]
+    fn expect_to_override_refused_values() {
+        let mut spec: PodSpec = serde_json::from_value(serde_json::json!({
+            "securityContext": { "runAsNonRoot": false },
+            "containers": [{
+                "name": "main",
+                "securityContext": {
+                    "allowPrivilegeEscalation": true,
+                    "capabilities": { "drop": ["NET_RAW"] }
+                }
+            }]
+        }))
+        .unwrap();
+
+        let changes = restrict(&mut spec);
+        assert_eq!(changes.len(), 4);
+
+        let pod_ctx = spec.security_context.unwrap();
+        assert_eq!(pod_ctx.run_as_non_root, Some(true));
+        assert!(pod_ctx.seccomp_profile.is_some());
+
+        let ctx = spec.containers[0].security_context.clone().unwrap();
+        assert_eq!(ctx.allow_privilege_escalation, Some(false));
+        assert_eq!(
+            ctx.capabilities.unwrap().drop,
+            Some(vec!["NET_RAW".to_string(), "ALL".to_string()])
+        );
+    }
+
+    #[test]
+    fn expect_compliant_spec_to_be_kept() {
+        let mut spec: PodSpec = serde_json::from_value(serde_json::json!({
+            "securityContext": {
+                "runAsNonRoot": true,
+                "seccompProfile": { "type": "Localhost", "localhostProfile": "job.json" }
+            },
+            "containers": [{
+                "name": "main",
+                "securityContext": {
+                    "allowPrivilegeEscalation": false,
+                    "capabilities": { "drop": ["ALL"] }
+                }
+            }]
+        }))
+        .unwrap();
+
+        let changes = restrict(&mut spec);
+        assert!(changes.is_empty());
+        assert_eq!(
+            spec.security_context
+                .unwrap()
+                .seccomp_profile
+                .unwrap()
+                .type_,
+            "Localhost"
+        );
+    }
+}
@@ -2,22 +2,232 @@ use anyhow::{Result, anyhow};
 use k8s_openapi::{
     api::{
         batch::v1::JobSpec,
-        core::v1::{EnvVar, EnvVarSource, ResourceRequirements},
+        core::v1::{
+            ConfigMapEnvSource, Container, EnvFromSource, EnvVar, EnvVarSource,
+            ObjectFieldSelector, PodSpec, ResourceFieldSelector, SecretEnvSource,
+            SecretKeySelector,
+        },
     },
     apimachinery::pkg::api::resource::Quantity,
 };
-use std::{collections::BTreeMap, ops::Deref};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    ops::Deref,
+};
+
+// Restart policy of the init containers running as native sidecars (Kubernetes 1.28+)
+const SIDECAR_RESTART_POLICY: &str = "Always";
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum EnvKind {
     Literal(String),
+    /// Reference to a ConfigMap key
     ConfigMap(Box<EnvVarSource>),
+    /// Reference to a Secret key. The value is sensitive
+    Secret(Box<EnvVarSource>),
+    /// Field of the pod exposed with the downward API (e.g. metadata.name)
+    FieldRef(Box<EnvVarSource>),
+    /// Resource of a container exposed with the downward API (e.g. limits.memory)
+    ResourceFieldRef(Box<EnvVarSource>),
+}
+
+impl EnvKind {
+    /// Create the env referencing the key of a Secret
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - String (name of the Secret)
+    /// * `key` - String
+    pub fn secret_key(name: String, key: String) -> Self {
+        EnvKind::Secret(Box::new(EnvVarSource {
+            secret_key_ref: Some(SecretKeySelector {
+                name,
+                key,
+                optional: None,
+            }),
+            ..Default::default()
+        }))
+    }
+
+    /// Create the env exposing a field of the pod with the downward API
+    ///
+    /// # Arguments
+    ///
+    /// * `field_path` - String (e.g. metadata.name)
+    pub fn field_ref(field_path: String) -> Self {
+        EnvKind::FieldRef(Box::new(EnvVarSource {
+            field_ref: Some(ObjectFieldSelector {
+                field_path,
+                api_version: None,
+            }),
+            ..Default::default()
+        }))
+    }
+
+    /// Create the env exposing a resource of the container with the downward API
+    ///
+    /// # Arguments
+    ///
+    /// * `resource` - String (e.g. limits.memory)
+    pub fn resource_field_ref(resource: String) -> Self {
+        EnvKind::ResourceFieldRef(Box::new(EnvVarSource {
+            resource_field_ref: Some(ResourceFieldSelector {
+                resource,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+    }
+}
+
+impl fmt::Display for EnvKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvKind::Literal(value) => write!(f, "{value}"),
+            EnvKind::ConfigMap(source) => match &source.config_map_key_ref {
+                Some(cm) => write!(f, "configmap {}/{}", cm.name, cm.key),
+                None => write!(f, "valueFrom"),
+            },
+            EnvKind::Secret(source) => match &source.secret_key_ref {
+                Some(secret) => write!(f, "secret {}/{}", secret.name, secret.key),
+                None => write!(f, "valueFrom"),
+            },
+            EnvKind::FieldRef(source) => match &source.field_ref {
+                Some(field) => write!(f, "fieldRef {}", field.field_path),
+                None => write!(f, "valueFrom"),
+            },
+            EnvKind::ResourceFieldRef(source) => match &source.resource_field_ref {
+                Some(ResourceFieldSelector {
+                    container_name: Some(container),
+                    resource,
+                    ..
+                }) => write!(f, "resourceFieldRef {resource} ({container})"),
+                Some(selector) => write!(f, "resourceFieldRef {}", selector.resource),
+                None => write!(f, "valueFrom"),
+            },
+        }
+    }
+}
+
+/// Kind of the object whose keys are loaded as env with envFrom
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EnvFromKind {
+    ConfigMap,
+    Secret,
+}
+
+impl fmt::Display for EnvFromKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvFromKind::ConfigMap => write!(f, "configmap"),
+            EnvFromKind::Secret => write!(f, "secret"),
+        }
+    }
+}
+
+/// Source of the envFrom of a container: every key of the ConfigMap or of the Secret is loaded as an env
+#[derive(Debug, PartialEq, Clone)]
+pub struct EnvFrom {
+    pub kind: EnvFromKind,
+    pub name: String,
+    /// Prefix prepended to the keys
+    pub prefix: Option<String>,
+    pub optional: Option<bool>,
+}
+
+impl EnvFrom {
+    /// Create the source loading the keys of the ConfigMap or of the Secret
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - EnvFromKind
+    /// * `name` - S
+    pub fn new<S: Into<String>>(kind: EnvFromKind, name: S) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            prefix: None,
+            optional: None,
+        }
+    }
+}
+
+impl fmt::Display for EnvFrom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.kind, self.name)?;
+        if let Some(prefix) = &self.prefix {
+            write!(f, " (prefix {prefix})")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<&EnvFromSource> for EnvFrom {
+    type Error = anyhow::Error;
+
+    fn try_from(source: &EnvFromSource) -> Result<Self> {
+        let (kind, name, optional) = match (&source.config_map_ref, &source.secret_ref) {
+            (Some(cm), _) => (EnvFromKind::ConfigMap, cm.name.clone(), cm.optional),
+            (_, Some(secret)) => (EnvFromKind::Secret, secret.name.clone(), secret.optional),
+            _ => return Err(anyhow!("Unsupported envFrom source {source:?}")),
+        };
+
+        Ok(Self {
+            kind,
+            name,
+            prefix: source.prefix.clone(),
+            optional,
+        })
+    }
+}
+
+impl From<&EnvFrom> for EnvFromSource {
+    fn from(env_from: &EnvFrom) -> Self {
+        let mut source = EnvFromSource {
+            prefix: env_from.prefix.clone(),
+            ..Default::default()
+        };
+
+        let (name, optional) = (env_from.name.clone(), env_from.optional);
+        match env_from.kind {
+            EnvFromKind::ConfigMap => {
+                source.config_map_ref = Some(ConfigMapEnvSource { name, optional })
+            }
+            EnvFromKind::Secret => source.secret_ref = Some(SecretEnvSource { name, optional }),
+        }
+
+        source
+    }
 }
 
 #[derive(Default, Debug)]
 pub struct ContainerEnv {
     pub name: String,
+    /// Whether the container is a native sidecar (init container restarted during the whole life of the pod)
+    pub sidecar: bool,
+    /// Whether the container is an init container running before the containers (e.g. schema migrations)
+    pub init: bool,
     pub envs: BTreeMap<String, EnvKind>,
+    /// Values of the env names defined multiple times in the container (in the order of the spec).
+    /// The value kept in `envs` is the last one as done by Kubernetes
+    pub duplicates: BTreeMap<String, Vec<EnvKind>>,
+    /// Sources of the envFrom of the container
+    pub env_from: Vec<EnvFrom>,
+    /// Names of the env removed from the container (e.g. DRY_RUN=true which must be absent for a manual run)
+    pub removed: BTreeSet<String>,
+}
+
+impl ContainerEnv {
+    /// Name of the container displayed in the prompts. The init containers and the sidecars are marked
+    pub fn label(&self) -> String {
+        match (self.init, self.sidecar) {
+            (true, _) => format!("{} (init)", self.name),
+            (_, true) => format!("{} (sidecar)", self.name),
+            _ => self.name.clone(),
+        }
+    }
 }
 
 #[derive(Default, Debug, Clone)]
@@ -54,33 +264,58 @@ impl SpecHandler for JobSpec {
 
         let mut containers_env = Vec::new();
 
-        for container in pod_spec.containers.iter() {
+        // The env of the init containers (native sidecars included) is edited after the one of the containers
+        let containers = pod_spec
+            .containers
+            .iter()
+            .map(|container| (container, false))
+            .chain(
+                pod_spec
+                    .init_containers
+                    .iter()
+                    .flatten()
+                    .map(|container| (container, true)),
+            );
+
+        for (container, init) in containers {
             let mut cont_env = ContainerEnv {
                 name: container.name.to_owned(),
+                sidecar: is_sidecar(container),
+                init: init && !is_sidecar(container),
                 ..Default::default()
             };
 
-            if let Some(env) = &container.env {
-                let envs: BTreeMap<String, EnvKind> = env
-                    .iter()
-                    .filter_map(|e| {
-                        let name = e.name.to_owned();
-                        if let Some(literal) = e.value.to_owned() {
-                            return Some((name, EnvKind::Literal(literal)));
-                        }
-
-                        if let Some(c) = e.value_from.to_owned() {
-                            return Some((name, EnvKind::ConfigMap(Box::new(c))));
-                        }
+            // Containers without env are kept in order to be able to add env to them
+            for e in container.env.iter().flatten() {
+                let kind = match (e.value.to_owned(), e.value_from.to_owned()) {
+                    (Some(literal), _) => EnvKind::Literal(literal),
+                    (None, Some(c)) if c.secret_key_ref.is_some() => EnvKind::Secret(Box::new(c)),
+                    (None, Some(c)) if c.field_ref.is_some() => EnvKind::FieldRef(Box::new(c)),
+                    (None, Some(c)) if c.resource_field_ref.is_some() => {
+                        EnvKind::ResourceFieldRef(Box::new(c))
+                    }
+                    (None, Some(c)) => EnvKind::ConfigMap(Box::new(c)),
+                    (None, None) => continue,
+                };
 
-                        None
-                    })
-                    .collect();
+                // Kubernetes tolerates duplicated env names, keep track of every value
+                if let Some(previous) = cont_env.envs.insert(e.name.to_owned(), kind.clone()) {
+                    cont_env
+                        .duplicates
+                        .entry(e.name.to_owned())
+                        .or_insert_with(|| vec![previous])
+                        .push(kind);
+                }
+            }
 
-                cont_env.envs = envs;
+            cont_env.env_from = container
+                .env_from
+                .iter()
+                .flatten()
+                .map(EnvFrom::try_from)
+                .collect::<Result<_>>()?;
 
-                containers_env.push(cont_env);
-            }
+            containers_env.push(cont_env);
         }
 
         Ok(containers_env)
@@ -98,51 +333,99 @@ impl SpecHandler for JobSpec {
             .as_mut()
             .ok_or_else(|| anyhow!("Unable to found pod spec on job"))?;
 
-        for (idx, container) in pod_spec.containers.iter_mut().enumerate() {
-            let updated_env =
-                match envs
-                    .get_mut(idx)
-                    .and_then(|cont| match cont.name == container.name {
-                        true => Some(cont),
-                        false => None,
-                    }) {
-                    Some(updated_env) => updated_env,
-                    None => {
-                        return Err(anyhow!(
-                            "Unable to get the environment variable for the container {:?}",
-                            container.name
-                        ));
-                    }
-                };
+        let containers = pod_spec
+            .containers
+            .iter_mut()
+            .chain(pod_spec.init_containers.iter_mut().flatten());
 
-            if let Some(container_envs) = container.env.as_mut() {
-                for container_env in container_envs.iter_mut() {
-                    if let Some(value) = updated_env.envs.get(&container_env.name) {
-                        match value {
-                            EnvKind::Literal(value) => container_env.value = Some(value.clone()),
-                            EnvKind::ConfigMap(value) => {
-                                container_env.value_from = Some(value.deref().clone())
-                            }
-                        }
+        for container in containers {
+            // Containers without env to rebuild (e.g. added by another step) are kept as is.
+            // Env of containers which are no longer in the spec are ignored
+            let Some(updated_env) = envs.iter_mut().find(|cont| cont.name == container.name) else {
+                continue;
+            };
 
-                        // Drain the key from the map
-                        updated_env.envs.remove(&container_env.name);
-                    }
-                }
+            container.env_from = (!updated_env.env_from.is_empty()).then(|| {
+                updated_env
+                    .env_from
+                    .iter()
+                    .map(EnvFromSource::from)
+                    .collect()
+            });
+
+            // The env is created on demand for the containers which don't have any
+            if container.env.is_none() && updated_env.envs.is_empty() {
+                continue;
+            }
+
+            let container_envs = container.env.get_or_insert_with(Vec::new);
 
-                // Add additional environment variables to the container if there are still some existing keys
-                if !updated_env.envs.is_empty() {
-                    for (key, value) in &updated_env.envs {
-                        if let EnvKind::Literal(value) = value {
-                            container_envs.push(EnvVar {
-                                name: key.to_owned(),
-                                value: Some(value.to_owned()),
-                                value_from: None,
-                            });
+            // Only keep a single entry of the duplicated env names. The value is the one selected in the envs.
+            // The removed env are dropped whatever their value
+            let mut seen = BTreeSet::new();
+            container_envs.retain(|env| {
+                !updated_env.removed.contains(&env.name)
+                    && (!updated_env.duplicates.contains_key(&env.name)
+                        || seen.insert(env.name.clone()))
+            });
+            for name in &updated_env.removed {
+                updated_env.envs.remove(name);
+            }
+
+            for container_env in container_envs.iter_mut() {
+                if let Some(value) = updated_env.envs.get(&container_env.name) {
+                    match value {
+                        // An env is either a literal or a reference
+                        EnvKind::Literal(value) => {
+                            container_env.value = Some(value.clone());
+                            container_env.value_from = None;
+                        }
+                        EnvKind::ConfigMap(value)
+                        | EnvKind::Secret(value)
+                        | EnvKind::FieldRef(value)
+                        | EnvKind::ResourceFieldRef(value) => {
+                            container_env.value = None;
+                            container_env.value_from = Some(value.deref().clone())
                         }
                     }
+
+                    // Drain the key from the map
+                    updated_env.envs.remove(&container_env.name);
+                }
+            }
+
+            // Add additional environment variables to the container if there are still some existing keys
+            if !updated_env.envs.is_empty() {
+                for (key, value) in &updated_env.envs {
+                    let (value, value_from) = match value {
+                        EnvKind::Literal(value) => (Some(value.to_owned()), None),
+                        EnvKind::ConfigMap(source)
+                        | EnvKind::Secret(source)
+                        | EnvKind::FieldRef(source)
+                        | EnvKind::ResourceFieldRef(source) => (None, Some(source.deref().clone())),
+                    };
+
+                    container_envs.push(EnvVar {
+                        name: key.to_owned(),
+                        value,
+                        value_from,
+                    });
                 }
             }
+
+            // Kubernetes tolerates duplicated env names but only the last one is used, which would hide a value
+            let mut names = BTreeSet::new();
+            if let Some(name) = container_envs.iter().find(|env| !names.insert(&env.name)) {
+                return Err(anyhow!(
+                    "The env {} is defined multiple times in the container {}",
+                    name.name,
+                    container.name
+                ));
+            }
+
+            if container_envs.is_empty() {
+                container.env = None;
+            }
         }
 
         Ok(())
@@ -156,47 +439,52 @@ impl SpecHandler for JobSpec {
         let Some(container) = tmpl
             .containers
             .iter_mut()
+            .chain(tmpl.init_containers.iter_mut().flatten())
             .rfind(|ct| ct.name == resources.container_name)
         else {
             return Err(anyhow!("Unable to get the targeted container"));
         };
 
-        match container.resources.as_mut() {
-            Some(pds) => {
-                let lim = pds.limits.as_mut().map_or(
-                    BTreeMap::from([
-                        ("cpu".to_string(), resources.cpu.clone()),
-                        ("memory".to_string(), resources.memory.clone()),
-                    ]),
-                    |lim| {
-                        lim.insert("cpu".to_string(), resources.cpu);
-                        lim.insert("memory".to_string(), resources.memory);
-
-                        lim.clone()
-                    },
-                );
-
-                pds.limits = Some(lim);
-            }
-            None => {
-                container.resources = Some(ResourceRequirements {
-                    limits: None,
-                    requests: None,
-                    ..Default::default()
-                })
-            }
-        };
+        // The containers without resources get their limits as well
+        let limits = container
+            .resources
+            .get_or_insert_default()
+            .limits
+            .get_or_insert_default();
+        limits.insert("cpu".to_string(), resources.cpu);
+        limits.insert("memory".to_string(), resources.memory);
 
         Ok(())
     }
 }
 
+/// Check whether the init container is a native sidecar
+///
+/// # Arguments
+///
+/// * `container` - &Container
+pub fn is_sidecar(container: &Container) -> bool {
+    container.restart_policy.as_deref() == Some(SIDECAR_RESTART_POLICY)
+}
+
+/// Get the native sidecars of the pod spec
+///
+/// # Arguments
+///
+/// * `spec` - &PodSpec
+pub fn sidecars(spec: &PodSpec) -> impl Iterator<Item = &Container> {
+    spec.init_containers
+        .iter()
+        .flatten()
+        .filter(|container| is_sidecar(container))
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
     use super::SpecHandler;
-    use crate::kube::spec::{EnvKind, SpecResources};
+    use crate::kube::spec::{EnvFrom, EnvFromKind, EnvKind, SpecResources};
     use k8s_openapi::{
         api::{
             batch::v1::JobSpec,
@@ -328,4 +616,414 @@ mod tests {
             Quantity("0.01".to_string())
         );
     }
+
+    #[test]
+    fn expect_to_set_resources_of_container_without_resources() {
+        let mut job_spec: JobSpec = serde_json::from_value(serde_json::json!({
+            "template": { "spec": { "containers": [{ "name": "main" }] } }
+        }))
+        .unwrap();
+
+        job_spec
+            .update_resources(SpecResources {
+                memory: Quantity("512Mi".to_string()),
+                cpu: Quantity("500m".to_string()),
+                container_name: "main".to_string(),
+            })
+            .unwrap();
+
+        let pod = job_spec.template.spec.unwrap();
+        let limits = pod.containers[0]
+            .resources
+            .as_ref()
+            .and_then(|resources| resources.limits.clone())
+            .unwrap();
+        assert_eq!(limits.get("memory"), Some(&Quantity("512Mi".to_string())));
+        assert_eq!(limits.get("cpu"), Some(&Quantity("500m".to_string())));
+    }
+
+    #[test]
+    fn expect_to_detect_duplicated_env() {
+        let mut job_spec = JobSpec {
+            template: PodTemplateSpec {
+                metadata: None,
+                spec: Some(PodSpec {
+                    containers: vec![Container {
+                        env: Some(vec![
+                            EnvVar {
+                                name: "key".to_string(),
+                                value: Some("first".to_string()),
+                                ..Default::default()
+                            },
+                            EnvVar {
+                                name: "key".to_string(),
+                                value: Some("second".to_string()),
+                                ..Default::default()
+                            },
+                        ]),
+                        name: "main".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+            },
+            ..Default::default()
+        };
+
+        let mut envs = job_spec.get_env().unwrap();
+        let container = envs.first_mut().unwrap();
+        assert_eq!(
+            container.duplicates.get("key").unwrap(),
+            &vec![
+                EnvKind::Literal("first".to_string()),
+                EnvKind::Literal("second".to_string())
+            ]
+        );
+
+        // Keep the first value
+        container
+            .envs
+            .insert("key".to_string(), EnvKind::Literal("first".to_string()));
+
+        // The duplicated env which weren't resolved are reported
+        let mut unresolved = job_spec.clone();
+        let mut unresolved_envs = unresolved.get_env().unwrap();
+        unresolved_envs[0].duplicates.clear();
+        let err = unresolved.rebuild_env(&mut unresolved_envs).unwrap_err();
+        assert!(err.to_string().contains("defined multiple times"));
+
+        job_spec.rebuild_env(&mut envs).unwrap();
+
+        let spec = job_spec.template.spec.unwrap();
+        let env = spec.containers.first().unwrap().env.as_ref().unwrap();
+        assert_eq!(env.len(), 1);
+        assert_eq!(env.first().unwrap().value.as_deref(), Some("first"));
+    }
+
+    #[test]
+    fn expect_to_rebuild_env_by_container_name() {
+        let container = |name: &str| Container {
+            env: Some(vec![EnvVar {
+                name: "key".to_string(),
+                value: Some("value".to_string()),
+                ..Default::default()
+            }]),
+            name: name.to_string(),
+            ..Default::default()
+        };
+
+        let mut job_spec = JobSpec {
+            template: PodTemplateSpec {
+                metadata: None,
+                spec: Some(PodSpec {
+                    containers: vec![container("main"), container("sidecar")],
+                    ..Default::default()
+                }),
+            },
+            ..Default::default()
+        };
+
+        let mut envs = job_spec.get_env().unwrap();
+        // Only keep the env of the sidecar which is no longer at the same position
+        envs.retain(|cont| cont.name == "sidecar");
+        envs.first_mut()
+            .unwrap()
+            .envs
+            .insert("key".to_string(), EnvKind::Literal("dodo".to_string()));
+
+        assert!(job_spec.rebuild_env(&mut envs).is_ok());
+
+        let spec = job_spec.template.spec.unwrap();
+        let values = spec
+            .containers
+            .iter()
+            .map(|c| {
+                c.env
+                    .as_ref()
+                    .unwrap()
+                    .first()
+                    .unwrap()
+                    .value
+                    .clone()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(values, vec!["value".to_string(), "dodo".to_string()]);
+    }
+
+    #[test]
+    fn expect_to_add_env_to_container_without_env() {
+        let mut job_spec = JobSpec {
+            template: PodTemplateSpec {
+                metadata: None,
+                spec: Some(PodSpec {
+                    containers: vec![Container {
+                        name: "main".to_string(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+            },
+            ..Default::default()
+        };
+
+        let mut envs = job_spec.get_env().unwrap();
+        let container = envs
+            .first_mut()
+            .expect("Expect to get the container without env");
+        container
+            .envs
+            .insert("key".to_string(), EnvKind::Literal("value".to_string()));
+
+        assert!(job_spec.rebuild_env(&mut envs).is_ok());
+
+        let spec = job_spec.template.spec.unwrap();
+        let env = spec.containers.first().unwrap().env.as_ref().unwrap();
+        assert_eq!(env.first().unwrap().value.as_deref(), Some("value"));
+    }
+
+    #[test]
+    fn expect_literal_to_replace_reference() {
+        let mut job_spec: JobSpec = serde_json::from_value(serde_json::json!({
+            "template": {
+                "spec": {
+                    "containers": [{
+                        "name": "main",
+                        "env": [{
+                            "name": "LOG_LEVEL",
+                            "valueFrom": { "configMapKeyRef": { "name": "momo-config", "key": "level" } }
+                        }, {
+                            "name": "PASSWORD",
+                            "valueFrom": { "secretKeyRef": { "name": "momo-secret", "key": "password" } }
+                        }]
+                    }]
+                }
+            }
+        }))
+        .unwrap();
+
+        let mut envs = job_spec.get_env().unwrap();
+        let secret = envs[0].envs.get("PASSWORD").unwrap();
+        assert!(matches!(secret, EnvKind::Secret(_)));
+        assert_eq!(secret.to_string(), "secret momo-secret/password");
+
+        envs[0].envs.insert(
+            "LOG_LEVEL".to_string(),
+            EnvKind::Literal("debug".to_string()),
+        );
+        job_spec.rebuild_env(&mut envs).unwrap();
+
+        let spec = job_spec.template.spec.unwrap();
+        let env = &spec.containers[0].env.as_ref().unwrap()[0];
+        assert_eq!(env.value.as_deref(), Some("debug"));
+        assert!(env.value_from.is_none());
+        let secret = &spec.containers[0].env.as_ref().unwrap()[1];
+        assert!(secret.value_from.as_ref().unwrap().secret_key_ref.is_some());
+    }
+
+    #[test]
+    fn expect_secret_key_to_replace_literal() {
+        let mut job_spec: JobSpec = serde_json::from_value(serde_json::json!({
+            "template": {
+                "spec": {
+                    "containers": [{
+                        "name": "main",
+                        "env": [{ "name": "PASSWORD", "value": "hunter2" }]
+                    }]
+                }
+            }
+        }))
+        .unwrap();
+
+        let mut envs = job_spec.get_env().unwrap();
+        envs[0].envs.insert(
+            "PASSWORD".to_string(),
+            EnvKind::secret_key("momo-secret".to_string(), "password".to_string()),
+        );
+        job_spec.rebuild_env(&mut envs).unwrap();
+
+        let spec = job_spec.template.spec.unwrap();
+        let env = &spec.containers[0].env.as_ref().unwrap()[0];
+        assert!(env.value.is_none());
+        let selector = env
+            .value_from
+            .as_ref()
+            .unwrap()
+            .secret_key_ref
+            .as_ref()
+            .unwrap();
+        assert_eq!(
+            (selector.name.as_str(), selector.key.as_str()),
+            ("momo-secret", "password")
+        );
+    }
+
+    #[test]
+    fn expect_to_keep_and_add_downward_env() {
+        let mut job_spec: JobSpec = serde_json::from_value(serde_json::json!({
+            "template": {
+                "spec": {
+                    "containers": [{
+                        "name": "main",
+                        "env": [{
+                            "name": "MEMORY_LIMIT",
+                            "valueFrom": { "resourceFieldRef": { "containerName": "main", "resource": "limits.memory" } }
+                        }]
+                    }]
+                }
+            }
+        }))
+        .unwrap();
+
+        let mut envs = job_spec.get_env().unwrap();
+        let memory = envs[0].envs.get("MEMORY_LIMIT").unwrap();
+        assert_eq!(memory.to_string(), "resourceFieldRef limits.memory (main)");
+
+        envs[0].envs.insert(
+            "POD_NAME".to_string(),
+            EnvKind::field_ref("metadata.name".to_string()),
+        );
+        job_spec.rebuild_env(&mut envs).unwrap();
+
+        let spec = job_spec.template.spec.unwrap();
+        let env = spec.containers[0].env.as_ref().unwrap();
+        let resource = env[0]
+            .value_from
+            .as_ref()
+            .unwrap()
+            .resource_field_ref
+            .as_ref();
+        assert_eq!(resource.unwrap().resource, "limits.memory");
+        assert_eq!(env[1].name, "POD_NAME");
+        let field = env[1].value_from.as_ref().unwrap().field_ref.as_ref();
+        assert_eq!(field.unwrap().field_path, "metadata.name");
+    }
+
+    #[test]
+    fn expect_to_remove_env() {
+        let mut job_spec: JobSpec = serde_json::from_value(serde_json::json!({
+            "template": {
+                "spec": {
+                    "containers": [{
+                        "name": "main",
+                        "env": [
+                            { "name": "DRY_RUN", "value": "true" },
+                            { "name": "LOG_LEVEL", "value": "info" },
+                            { "name": "DRY_RUN", "value": "1" }
+                        ]
+                    }, {
+                        "name": "proxy",
+                        "env": [{ "name": "DRY_RUN", "value": "true" }]
+                    }]
+                }
+            }
+        }))
+        .unwrap();
+
+        let mut envs = job_spec.get_env().unwrap();
+        for container in envs.iter_mut() {
+            container.removed.insert("DRY_RUN".to_string());
+        }
+        job_spec.rebuild_env(&mut envs).unwrap();
+
+        let spec = job_spec.template.spec.unwrap();
+        let names = spec.containers[0]
+            .env
+            .iter()
+            .flatten()
+            .map(|env| env.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["LOG_LEVEL"]);
+        assert!(spec.containers[1].env.is_none());
+    }
+
+    #[test]
+    fn expect_to_rebuild_env_from() {
+        let mut job_spec: JobSpec = serde_json::from_value(serde_json::json!({
+            "template": {
+                "spec": {
+                    "containers": [{
+                        "name": "main",
+                        "envFrom": [
+                            { "configMapRef": { "name": "momo-config" } },
+                            { "secretRef": { "name": "momo-secret" }, "prefix": "DB_" }
+                        ]
+                    }]
+                }
+            }
+        }))
+        .unwrap();
+
+        let mut envs = job_spec.get_env().unwrap();
+        let env_from = &mut envs[0].env_from;
+        assert_eq!(
+            env_from.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["configmap momo-config", "secret momo-secret (prefix DB_)"]
+        );
+
+        env_from.remove(0);
+        env_from.push(EnvFrom::new(EnvFromKind::ConfigMap, "dodo-config"));
+        job_spec.rebuild_env(&mut envs).unwrap();
+
+        let spec = job_spec.template.spec.unwrap();
+        let env_from = spec.containers[0].env_from.as_ref().unwrap();
+        assert_eq!(env_from.len(), 2);
+        assert_eq!(env_from[0].secret_ref.as_ref().unwrap().name, "momo-secret");
+        assert_eq!(env_from[0].prefix.as_deref(), Some("DB_"));
+        assert_eq!(
+            env_from[1].config_map_ref.as_ref().unwrap().name,
+            "dodo-config"
+        );
+    }
+
+    #[test]
+    fn expect_to_rebuild_env_of_init_containers() {
+        let container = |name: &str, restart_policy: Option<&str>| Container {
+            env: Some(vec![EnvVar {
+                name: "key".to_string(),
+                value: Some("value".to_string()),
+                ..Default::default()
+            }]),
+            name: name.to_string(),
+            restart_policy: restart_policy.map(String::from),
+            ..Default::default()
+        };
+
+        let mut job_spec = JobSpec {
+            template: PodTemplateSpec {
+                metadata: None,
+                spec: Some(PodSpec {
+                    containers: vec![container("main", None)],
+                    init_containers: Some(vec![
+                        container("migrate", None),
+                        container("proxy", Some("Always")),
+                    ]),
+                    ..Default::default()
+                }),
+            },
+            ..Default::default()
+        };
+
+        let mut envs = job_spec.get_env().unwrap();
+        assert_eq!(
+            envs.iter().map(|c| c.label()).collect::<Vec<_>>(),
+            vec!["main", "migrate (init)", "proxy (sidecar)"]
+        );
+
+        envs[1]
+            .envs
+            .insert("key".to_string(), EnvKind::Literal("momo".to_string()));
+        envs[2]
+            .envs
+            .insert("key".to_string(), EnvKind::Literal("dodo".to_string()));
+        job_spec.rebuild_env(&mut envs).unwrap();
+
+        let init_containers = job_spec.template.spec.unwrap().init_containers.unwrap();
+        let values = init_containers
+            .iter()
+            .map(|c| c.env.as_ref().unwrap()[0].value.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec!["momo", "dodo"]);
+    }
 }
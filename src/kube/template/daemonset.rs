@@ -0,0 +1,22 @@
+use super::TemplateSpecOps;
+use k8s_openapi::api::batch::v1::JobTemplateSpec;
+use k8s_openapi::api::{apps::v1::DaemonSet, batch::v1::JobSpec};
+
+impl TemplateSpecOps for DaemonSet {
+    fn get_template_spec(&self) -> Option<JobTemplateSpec> {
+        self.spec.clone().as_mut().map(|ds| {
+            // Pods of a daemonset are always restarted, the job runs once
+            if let Some(spec) = ds.template.spec.as_mut() {
+                spec.restart_policy = Some("Never".to_string());
+            }
+
+            JobTemplateSpec {
+                metadata: ds.template.metadata.clone(),
+                spec: Some(JobSpec {
+                    template: ds.template.clone(),
+                    ..Default::default()
+                }),
+            }
+        })
+    }
+}
@@ -1,7 +1,9 @@
 use k8s_openapi::api::batch::v1::JobTemplateSpec;
 
 pub mod cronjob;
+pub mod daemonset;
 pub mod deployment;
+pub mod pod;
 
 pub trait TemplateSpecOps {
     /// Get the template spec for a targeted Kubernetes object
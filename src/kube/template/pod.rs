@@ -0,0 +1,82 @@
+use super::TemplateSpecOps;
+use k8s_openapi::api::batch::v1::{JobSpec, JobTemplateSpec};
+use k8s_openapi::api::core::v1::{Pod, PodTemplateSpec};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+// Constant
+// Labels set by the controllers of the pod. The labels of a job controller would not match the selector of the new job
+const CONTROLLER_LABELS: [&str; 5] = [
+    "controller-uid",
+    "batch.kubernetes.io/controller-uid",
+    "job-name",
+    "batch.kubernetes.io/job-name",
+    "pod-template-hash",
+];
+
+impl TemplateSpecOps for Pod {
+    fn get_template_spec(&self) -> Option<JobTemplateSpec> {
+        let mut spec = self.spec.clone()?;
+        // Fields bound to the running pod, the pod of the job is scheduled & debugged on its own
+        spec.node_name = None;
+        spec.ephemeral_containers = None;
+        spec.restart_policy = Some("Never".to_string());
+
+        // Only the labels & annotations are kept, the owner references, uid, status... belong to the running pod
+        let labels = self.metadata.labels.clone().map(|mut labels| {
+            labels.retain(|key, _| !CONTROLLER_LABELS.contains(&key.as_str()));
+            labels
+        });
+        let metadata = ObjectMeta {
+            labels,
+            annotations: self.metadata.annotations.clone(),
+            ..Default::default()
+        };
+
+        Some(JobTemplateSpec {
+            metadata: Some(metadata.clone()),
+            spec: Some(JobSpec {
+                template: PodTemplateSpec {
+                    metadata: Some(metadata),
+                    spec: Some(spec),
+                },
+                ..Default::default()
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::kube::template::TemplateSpecOps;
+    use k8s_openapi::api::core::v1::Pod;
+    use serde_json::json;
+
+    #[test]
+    fn expect_to_strip_pod_only_fields() {
+        let pod: Pod = serde_json::from_value(json!({
+            "metadata": {
+                "name": "api-7d9f-x2k",
+                "labels": { "app": "api", "pod-template-hash": "7d9f" },
+                "ownerReferences": [{
+                    "apiVersion": "apps/v1", "kind": "ReplicaSet", "name": "api-7d9f", "uid": "1"
+                }]
+            },
+            "spec": {
+                "nodeName": "node-1",
+                "restartPolicy": "Always",
+                "containers": [{ "name": "main", "image": "api:1.0" }]
+            },
+            "status": { "phase": "Running" }
+        }))
+        .unwrap();
+
+        let tmpl = pod.get_template_spec().unwrap();
+        let meta = tmpl.metadata.unwrap();
+        assert!(meta.owner_references.is_none());
+        assert_eq!(meta.labels.unwrap().keys().collect::<Vec<_>>(), ["app"]);
+
+        let spec = tmpl.spec.unwrap().template.spec.unwrap();
+        assert!(spec.node_name.is_none());
+        assert_eq!(spec.restart_policy.as_deref(), Some("Never"));
+    }
+}
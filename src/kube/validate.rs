@@ -0,0 +1,286 @@
+use anyhow::{Result, anyhow};
+use k8s_openapi::{
+    api::{batch::v1::Job, core::v1::Container},
+    apimachinery::pkg::api::resource::Quantity,
+};
+use std::collections::BTreeMap;
+
+// Constant
+const MAX_LABEL_LENGTH: usize = 63;
+const MAX_SUBDOMAIN_LENGTH: usize = 253;
+const JOB_RESTART_POLICIES: [&str; 2] = ["Never", "OnFailure"];
+// Suffixes supported by the Quantity format
+// @link https://kubernetes.io/docs/reference/kubernetes-api/common-definitions/quantity/
+const QUANTITY_SUFFIXES: [&str; 15] = [
+    "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "n", "u", "m", "k", "M", "G", "T", "P", "E",
+];
+
+/// Validate the job against the rules enforced by the Kubernetes API server. All the violations are reported at once
+///
+/// # Arguments
+///
+/// * `job` - &Job
+pub fn validate_job(job: &Job) -> Result<()> {
+    let mut violations = Vec::new();
+
+    match job.metadata.name.as_deref() {
+        Some(name) => {
+            if let Some(err) = check_dns_label(name) {
+                violations.push(format!("metadata.name {name:?}: {err}"));
+            }
+        }
+        None => violations.push("metadata.name: the name of the job is required".to_string()),
+    }
+
+    check_labels(
+        "metadata.labels",
+        job.metadata.labels.as_ref(),
+        &mut violations,
+    );
+
+    let Some(spec) = job.spec.as_ref() else {
+        violations.push("spec: the spec of the job is required".to_string());
+        return report(violations);
+    };
+
+    if let Some(metadata) = spec.template.metadata.as_ref() {
+        check_labels(
+            "spec.template.metadata.labels",
+            metadata.labels.as_ref(),
+            &mut violations,
+        );
+    }
+
+    let Some(pod_spec) = spec.template.spec.as_ref() else {
+        violations.push("spec.template.spec: the pod spec is required".to_string());
+        return report(violations);
+    };
+
+    if let Some(policy) = pod_spec.restart_policy.as_deref()
+        && !JOB_RESTART_POLICIES.contains(&policy)
+    {
+        violations.push(format!(
+            "spec.template.spec.restartPolicy {policy:?}: should be one of {JOB_RESTART_POLICIES:?}"
+        ));
+    }
+
+    if pod_spec.containers.is_empty() {
+        violations
+            .push("spec.template.spec.containers: at least one container is required".to_string());
+    }
+
+    for (idx, container) in pod_spec.containers.iter().enumerate() {
+        check_container(
+            &format!("spec.template.spec.containers[{idx}]"),
+            container,
+            &mut violations,
+        );
+    }
+
+    for (idx, container) in pod_spec.init_containers.iter().flatten().enumerate() {
+        check_container(
+            &format!("spec.template.spec.initContainers[{idx}]"),
+            container,
+            &mut violations,
+        );
+    }
+
+    report(violations)
+}
+
+fn report(violations: Vec<String>) -> Result<()> {
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "The job is invalid:\n  - {}",
+        violations.join("\n  - ")
+    ))
+}
+
+fn check_container(path: &str, container: &Container, violations: &mut Vec<String>) {
+    if let Some(err) = check_dns_label(&container.name) {
+        violations.push(format!("{path}.name {:?}: {err}", container.name));
+    }
+
+    if container.image.as_deref().is_none_or(str::is_empty) {
+        violations.push(format!("{path}.image: the image is required"));
+    }
+
+    for env in container.env.iter().flatten() {
+        if env.name.is_empty() {
+            violations.push(format!("{path}.env: the name of the env is required"));
+        }
+    }
+
+    let Some(resources) = container.resources.as_ref() else {
+        return;
+    };
+
+    for (kind, quantities) in [
+        ("limits", resources.limits.as_ref()),
+        ("requests", resources.requests.as_ref()),
+    ] {
+        for (name, quantity) in quantities.into_iter().flatten() {
+            if !is_valid_quantity(quantity) {
+                violations.push(format!(
+                    "{path}.resources.{kind}.{name} {:?}: invalid quantity",
+                    quantity.0
+                ));
+            }
+        }
+    }
+}
+
+fn check_labels(
+    path: &str,
+    labels: Option<&BTreeMap<String, String>>,
+    violations: &mut Vec<String>,
+) {
+    for (key, value) in labels.into_iter().flatten() {
+        let (prefix, name) = match key.split_once('/') {
+            Some((prefix, name)) => (Some(prefix), name),
+            None => (None, key.as_str()),
+        };
+
+        if let Some(prefix) = prefix
+            && (prefix.len() > MAX_SUBDOMAIN_LENGTH
+                || prefix
+                    .split('.')
+                    .any(|part| check_dns_label(part).is_some()))
+        {
+            violations.push(format!("{path} {key:?}: invalid prefix of the key"));
+        }
+
+        if name.is_empty() || !is_label_value(name) {
+            violations.push(format!("{path} {key:?}: invalid name of the key"));
+        }
+
+        if !is_label_value(value) {
+            violations.push(format!("{path}.{key} {value:?}: invalid label value"));
+        }
+    }
+}
+
+/// Check that the value is a DNS-1123 label (used for the job & container names)
+///
+/// # Arguments
+///
+/// * `value` - &str
+fn check_dns_label(value: &str) -> Option<&'static str> {
+    if value.is_empty() || value.len() > MAX_LABEL_LENGTH {
+        return Some("should contains between 1 and 63 characters");
+    }
+
+    let valid_chars = value
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    let valid_bounds = !value.starts_with('-') && !value.ends_with('-');
+
+    match valid_chars && valid_bounds {
+        true => None,
+        false => Some(
+            "should contains only lowercase alphanumeric characters or '-' and start and end with an alphanumeric character",
+        ),
+    }
+}
+
+/// Check that the value is a valid label value. An empty value is valid
+///
+/// # Arguments
+///
+/// * `value` - &str
+fn is_label_value(value: &str) -> bool {
+    if value.is_empty() {
+        return true;
+    }
+
+    let starts_and_ends_alnum = value.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && value.ends_with(|c: char| c.is_ascii_alphanumeric());
+
+    value.len() <= MAX_LABEL_LENGTH
+        && starts_and_ends_alnum
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Check that the quantity respects the format <sign><number><suffix>
+///
+/// # Arguments
+///
+/// * `quantity` - &Quantity
+pub fn is_valid_quantity(quantity: &Quantity) -> bool {
+    let value = quantity.0.trim_start_matches(['+', '-']);
+    let number_end = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, suffix) = value.split_at(number_end);
+
+    let valid_number =
+        !number.is_empty() && number != "." && number.chars().filter(|c| *c == '.').count() <= 1;
+
+    let valid_suffix = suffix.is_empty()
+        || QUANTITY_SUFFIXES.contains(&suffix)
+        || suffix
+            .strip_prefix(['e', 'E'])
+            .map(|exp| exp.trim_start_matches(['+', '-']))
+            .is_some_and(|exp| !exp.is_empty() && exp.chars().all(|c| c.is_ascii_digit()));
+
+    valid_number && valid_suffix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_valid_quantity, validate_job};
+    use k8s_openapi::{
+        api::{
+            batch::v1::{Job, JobSpec},
+            core::v1::{Container, PodSpec, PodTemplateSpec},
+        },
+        apimachinery::pkg::{api::resource::Quantity, apis::meta::v1::ObjectMeta},
+    };
+
+    #[test]
+    fn expect_to_validate_quantities() {
+        for valid in ["1", "0.5", "100m", "1.5Gi", "1e3", "+2Ki", ".5"] {
+            assert!(is_valid_quantity(&Quantity(valid.to_string())), "{valid}");
+        }
+
+        for invalid in ["", "Gi", "1.2.3", "10Mb", "1e", "abc"] {
+            assert!(
+                !is_valid_quantity(&Quantity(invalid.to_string())),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn expect_to_report_all_violations() {
+        let job = Job {
+            metadata: ObjectMeta {
+                name: Some("Invalid_Name".to_string()),
+                ..Default::default()
+            },
+            spec: Some(JobSpec {
+                template: PodTemplateSpec {
+                    metadata: None,
+                    spec: Some(PodSpec {
+                        containers: vec![Container {
+                            name: "main".to_string(),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }),
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let err = validate_job(&job).unwrap_err().to_string();
+        assert!(err.contains("metadata.name"));
+        assert!(err.contains("containers[0].image"));
+    }
+}
@@ -0,0 +1,8 @@
+//! Dispatch a kubernetes job from a cronjob, a deployment or a daemonset. The dispatch can be customized with the
+//! stages of [`cli::pipeline`] passed to [`cli::Cli::run_with`]
+pub mod cli;
+pub mod config;
+pub mod kube;
+
+#[cfg(feature = "testing")]
+pub mod testing;
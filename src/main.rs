@@ -1,32 +1,196 @@
-use clap::Parser;
-use cli::ui;
+use bakkutteh::cli::{
+    self, bundle, events, i18n, man, panic, pipeline::Stage, plugin, session, timings::Timings, ui,
+    update,
+};
+use bakkutteh::{config, kube};
+use clap::{CommandFactory, Parser};
 use colored::{self, Colorize};
-
-mod cli;
-mod kube;
+use std::io;
+use tokio::signal;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Set the theme of the CLI for inquire interactions.
-    ui::init_clack_purple_theme();
-
     let cli = cli::Cli::parse();
 
+    i18n::init(cli.lang.as_deref());
+
+    // Unexpected panics (e.g. from the prompts on unusual terminals) shouldn't leave the terminal in raw mode
+    panic::install_hook();
+
+    // Set the theme of the CLI for inquire interactions or fallback to plain prompts.
+    match cli.plain_prompts {
+        true => ui::enable_plain_prompts(),
+        false => ui::init_clack_purple_theme(),
+    }
+
+    // The prompts are answered with the options when no one can answer them (e.g. CI pipelines)
+    if cli.is_non_interactive() {
+        ui::enable_non_interactive();
+    }
+
+    events::set_format(cli.log_format);
+
+    if let Some(path) = &cli.replay {
+        session::load_replay(path)?;
+    }
+
+    if let Some(path) = &cli.record {
+        session::start_record(path);
+    }
+
+    if cli.debug_bundle.is_some() {
+        bundle::enable();
+    }
+
+    // Man pages are rendered from the definition of the CLI only
+    if let Some(cli::Command::Man { out_dir }) = &cli.command {
+        man::write(cli::Cli::command(), out_dir.as_deref())?;
+        return Ok(());
+    }
+
+    if let Some(cli::Command::Completions { shell }) = &cli.command {
+        let mut cmd = cli::Cli::command();
+        let name = cmd.get_name().to_string();
+        clap_complete::generate(*shell, &mut cmd, name, &mut io::stdout());
+        return Ok(());
+    }
+
+    if let Some(cli::Command::SelfUpdate { force }) = &cli.command {
+        update::self_update(*force)?;
+        return Ok(());
+    }
+
+    // The doctor diagnoses the environment and doesn't need a working client
+    if let Some(cli::Command::Doctor) = &cli.command {
+        cli.doctor().await?;
+        return Ok(());
+    }
+
+    // The history is read from the local audit log
+    if let Some(cli::Command::History {
+        all_namespaces,
+        source,
+        since,
+        failed,
+        limit,
+    }) = &cli.command
+    {
+        cli.history(*all_namespaces, source.as_deref(), *since, *failed, *limit)?;
+        return Ok(());
+    }
+
+    if let Some(cli::Command::Hash { file }) = &cli.command {
+        cli.hash_manifest(file)?;
+        return Ok(());
+    }
+
+    let config = config::load(cli.config.as_deref())?;
+
     // Initialize the kube handler
     let mut kube_handler = kube::KubeHandler::new(
         &cli.namespace,
         cli.dry_run,
         cli.dry_run_output_path.is_some(),
+        cli.read_only || config.read_only,
+        cli.verbose_api,
     )
     .await?;
 
+    // Subcommands managing the jobs previously dispatched
+    match &cli.command {
+        Some(cli::Command::ListManual) => return Ok(cli.list_manual(&kube_handler).await?),
+        Some(cli::Command::Logs { job, follow }) => {
+            return Ok(kube_handler.stream_job_logs(job, *follow).await?);
+        }
+        Some(cli::Command::Status { job, watch }) => {
+            return Ok(cli.status(&kube_handler, job, *watch).await?);
+        }
+        Some(cli::Command::Describe { job }) => {
+            return Ok(cli.describe(&kube_handler, job).await?);
+        }
+        Some(cli::Command::Cancel { job, wait }) => {
+            return Ok(cli.cancel(&kube_handler, job, *wait).await?);
+        }
+        Some(cli::Command::Rerun { job, name }) => {
+            return Ok(cli.rerun(&kube_handler, job, name.as_deref()).await?);
+        }
+        Some(cli::Command::Diff { job: Some(job), .. }) => {
+            return Ok(cli.diff_job(&kube_handler, job).await?);
+        }
+        Some(cli::Command::Suspend { cronjob }) => {
+            return Ok(cli.set_suspend(&kube_handler, cronjob, true).await?);
+        }
+        Some(cli::Command::Resume { cronjob }) => {
+            return Ok(cli.set_suspend(&kube_handler, cronjob, false).await?);
+        }
+        Some(cli::Command::Prune { older_than }) => {
+            return Ok(cli.prune(&kube_handler, *older_than).await?);
+        }
+        _ => {}
+    }
+
+    // Plugins transform the job after its edition, in the order of their names. They aren't sandboxed, hence they
+    // only run when enabled
+    let mut stages: Vec<Box<dyn Stage>> = Vec::new();
+    let plugins = cli.plugins || config.plugins;
+    if let Some(dir) = config.plugins_dir().filter(|_| plugins) {
+        for plugin in plugin::discover(&dir, &cli.namespace)? {
+            stages.push(Box::new(plugin));
+        }
+    }
+
     // Run the command
-    if let Err(err) = cli.run(&mut kube_handler).await {
-        println!(
-            "Unable to create job due to error: {}",
-            err.to_string().bright_red().bold()
-        );
+    let mut timings = Timings::new();
+    // Interrupting the tool stops the run, the prompts catch Ctrl-C themselves and return an error. The plain prompts
+    // block the task of the run while they read the stdin, they're interrupted from another task
+    tokio::spawn(async {
+        while signal::ctrl_c().await.is_ok() {
+            ui::interrupt();
+        }
+    });
+    let result = tokio::select! {
+        result = cli.run_with(&mut kube_handler, &config, &mut timings, &stages) => result,
+        _ = signal::ctrl_c() => {
+            panic::restore_terminal();
+            Err(anyhow::anyhow!(i18n::tr("interrupted")))
+        }
     };
 
+    if let Err(err) = &result {
+        let error = err.to_string().bright_red().bold().to_string();
+        println!("{}", i18n::tr_args("job-failed", &[("error", error)]));
+
+        if let Err(err) = cli.recover(&kube_handler).await {
+            let error = err.to_string();
+            eprintln!("{}", i18n::tr_args("recover-failed", &[("error", error)]));
+        }
+
+        if let Some(path) = &cli.debug_bundle {
+            match bundle::write(path, err, kube_handler.discovery().await) {
+                Ok(()) => eprintln!(
+                    "{}",
+                    i18n::tr_args("debug-bundle-written", &[("path", path.clone())])
+                ),
+                Err(err) => eprintln!(
+                    "{}",
+                    i18n::tr_args("debug-bundle-failed", &[("error", err.to_string())])
+                ),
+            }
+        }
+    };
+
+    // Timings are printed even if the run failed as the slowness may be the cause of the failure
+    if cli.timings {
+        eprintln!("{}", timings.summary());
+    }
+
+    // Save the answers even if the run failed so that the session can be replayed up to the failure
+    session::save()?;
+
+    // The failure was already printed, the exit code tells the scripts (e.g. CI pipelines) that the run failed
+    if result.is_err() {
+        std::process::exit(1);
+    }
+
     Ok(())
 }
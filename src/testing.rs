@@ -0,0 +1,309 @@
+//! Harness of the integration tests. A kind or k3d cluster is created for the tests unless an existing cluster is
+//! selected with `BAKKUTTEH_TEST_CLUSTER=existing`, in which case the current context of the kubeconfig is used.
+//! Each harness works in its own namespace where the sample cronjobs / deployments are seeded
+use crate::cli::{Cli, i18n, session, timings::Timings, ui};
+use crate::config::Config;
+use crate::kube::KubeHandler;
+use ::kube::{
+    Client,
+    api::{Api, DeleteParams, PostParams},
+};
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use k8s_openapi::api::{
+    apps::v1::Deployment, batch::v1::CronJob, batch::v1::Job, core::v1::Namespace,
+};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Constant
+const CLUSTER_ENV: &str = "BAKKUTTEH_TEST_CLUSTER";
+const CLUSTER_NAME: &str = "bakkutteh-test";
+const SAMPLE_IMAGE: &str = "busybox:1.36";
+
+static NAMESPACES: AtomicUsize = AtomicUsize::new(0);
+
+/// Cluster on which the integration tests run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    Kind,
+    K3d,
+    /// Cluster of the current context of the kubeconfig
+    Existing,
+}
+
+impl Provider {
+    /// Get the provider from the `BAKKUTTEH_TEST_CLUSTER` env. Defaults to kind
+    pub fn from_env() -> Result<Self> {
+        match env::var(CLUSTER_ENV).as_deref() {
+            Err(_) | Ok("kind") => Ok(Self::Kind),
+            Ok("k3d") => Ok(Self::K3d),
+            Ok("existing") => Ok(Self::Existing),
+            Ok(other) => Err(anyhow!(
+                "Unsupported {CLUSTER_ENV} {other}, expected kind, k3d or existing"
+            )),
+        }
+    }
+
+    fn exists(&self) -> Result<bool> {
+        let output = match self {
+            Self::Kind => run("kind", &["get", "clusters"])?,
+            Self::K3d => run("k3d", &["cluster", "list", "--no-headers"])?,
+            Self::Existing => return Ok(true),
+        };
+
+        Ok(output
+            .lines()
+            .any(|line| line.split_whitespace().next() == Some(CLUSTER_NAME)))
+    }
+
+    /// Create the cluster and switch the current context of the kubeconfig to it
+    fn create(&self) -> Result<()> {
+        match self {
+            Self::Kind => run(
+                "kind",
+                &[
+                    "create",
+                    "cluster",
+                    "--name",
+                    CLUSTER_NAME,
+                    "--wait",
+                    "120s",
+                ],
+            ),
+            Self::K3d => run("k3d", &["cluster", "create", CLUSTER_NAME, "--wait"]),
+            Self::Existing => Ok(String::new()),
+        }
+        .map(|_| ())
+    }
+
+    fn delete(&self) -> Result<()> {
+        match self {
+            Self::Kind => run("kind", &["delete", "cluster", "--name", CLUSTER_NAME]),
+            Self::K3d => run("k3d", &["cluster", "delete", CLUSTER_NAME]),
+            Self::Existing => Ok(String::new()),
+        }
+        .map(|_| ())
+    }
+}
+
+/// Run the command of the provider and get its output
+///
+/// # Arguments
+///
+/// * `program` - &str
+/// * `args` - &[&str]
+fn run(program: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|err| anyhow!("Unable to run {program}, make sure that it's installed: {err}"))?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{program} {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Cluster and namespace of an integration test. The cluster is deleted once the harness is dropped if it was
+/// created by the harness
+pub struct Harness {
+    provider: Provider,
+    created: bool,
+    client: Client,
+    pub namespace: String,
+}
+
+impl Harness {
+    /// Create the cluster if it doesn't exist yet then create the namespace of the test
+    pub async fn start() -> Result<Self> {
+        let provider = Provider::from_env()?;
+        let created = !provider.exists()?;
+        if created {
+            provider.create()?;
+        }
+
+        let client = Client::try_default().await?;
+        let namespace = format!(
+            "bakkutteh-test-{}-{}",
+            std::process::id(),
+            NAMESPACES.fetch_add(1, Ordering::Relaxed)
+        );
+
+        let ns: Namespace = serde_json::from_value(json!({
+            "metadata": { "name": namespace }
+        }))?;
+        Api::<Namespace>::all(client.clone())
+            .create(&PostParams::default(), &ns)
+            .await?;
+
+        Ok(Self {
+            provider,
+            created,
+            client,
+            namespace,
+        })
+    }
+
+    /// Seed a cronjob running the sample image with the given env
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - &str
+    /// * `env` - &[(&str, &str)]
+    pub async fn seed_cron_job(&self, name: &str, env: &[(&str, &str)]) -> Result<CronJob> {
+        let cron_job: CronJob = serde_json::from_value(json!({
+            "metadata": { "name": name },
+            "spec": {
+                // Once a year so that the scheduled runs don't interfere with the tests
+                "schedule": "0 0 1 1 *",
+                "jobTemplate": {
+                    "spec": { "template": sample_pod_template(name, env, Some("Never")) }
+                }
+            }
+        }))?;
+
+        Ok(self
+            .api::<CronJob>()
+            .create(&PostParams::default(), &cron_job)
+            .await?)
+    }
+
+    /// Seed a deployment without replicas running the sample image with the given env
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - &str
+    /// * `env` - &[(&str, &str)]
+    pub async fn seed_deployment(&self, name: &str, env: &[(&str, &str)]) -> Result<Deployment> {
+        let deployment: Deployment = serde_json::from_value(json!({
+            "metadata": { "name": name },
+            "spec": {
+                "replicas": 0,
+                "selector": { "matchLabels": { "app": name } },
+                "template": sample_pod_template(name, env, None)
+            }
+        }))?;
+
+        Ok(self
+            .api::<Deployment>()
+            .create(&PostParams::default(), &deployment)
+            .await?)
+    }
+
+    /// Run the whole dispatch of the source with the other arguments of the CLI and get the created job
+    /// ({source}-manual). The prompts are answered with the answers keyed by their question (e.g. "env-title name=FOO") as
+    /// in a replayed session. A prompt without answer fails the dispatch as the prompts are plain and the stdin of
+    /// the tests is empty
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - &str
+    /// * `args` - &[&str] (without the namespace, source and target)
+    /// * `answers` - &[(&str, &str)]
+    pub async fn dispatch(
+        &self,
+        source: &str,
+        args: &[&str],
+        answers: &[(&str, &str)],
+    ) -> Result<Job> {
+        let cli = Cli::try_parse_from(
+            [
+                "bakkutteh",
+                "--lang",
+                "en",
+                "--plain-prompts",
+                "-n",
+                &self.namespace,
+                "-j",
+                source,
+            ]
+            .into_iter()
+            .chain(args.iter().copied()),
+        )?;
+
+        let mut replay: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (question, answer) in answers {
+            replay.entry(*question).or_default().push(*answer);
+        }
+        let path = env::temp_dir().join(format!("{}-answers.yaml", self.namespace));
+        fs::write(&path, serde_yml::to_string(&replay)?)?;
+
+        i18n::init(cli.lang.as_deref());
+        ui::enable_plain_prompts();
+        session::load_replay(path.to_string_lossy())?;
+
+        let mut kube_handler =
+            KubeHandler::new(self.namespace.as_str(), false, false, false, 0).await?;
+        let result = cli
+            .run(&mut kube_handler, &Config::default(), &mut Timings::new())
+            .await;
+        let _ = fs::remove_file(&path);
+        result?;
+
+        Ok(self.api::<Job>().get(&format!("{source}-manual")).await?)
+    }
+
+    /// Delete the namespace of the test. The cluster is deleted when the harness is dropped
+    pub async fn cleanup(self) -> Result<()> {
+        Api::<Namespace>::all(self.client.clone())
+            .delete(&self.namespace, &DeleteParams::background())
+            .await?;
+
+        Ok(())
+    }
+
+    fn api<K>(&self) -> Api<K>
+    where
+        K: ::kube::Resource<Scope = k8s_openapi::NamespaceResourceScope>,
+        <K as ::kube::Resource>::DynamicType: Default,
+    {
+        Api::namespaced(self.client.clone(), &self.namespace)
+    }
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        if self.created
+            && let Err(err) = self.provider.delete()
+        {
+            eprintln!("Unable to delete the test cluster due to: {err}");
+        }
+    }
+}
+
+/// Pod template of the sample workloads, which prints its env and exits
+///
+/// # Arguments
+///
+/// * `name` - &str
+/// * `env` - &[(&str, &str)]
+/// * `restart_policy` - Option<&str> (deployments only accept the default one)
+fn sample_pod_template(name: &str, env: &[(&str, &str)], restart_policy: Option<&str>) -> Value {
+    let env = env
+        .iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect::<Vec<_>>();
+
+    json!({
+        "metadata": { "labels": { "app": name } },
+        "spec": {
+            "restartPolicy": restart_policy,
+            "containers": [{
+                "name": "main",
+                "image": SAMPLE_IMAGE,
+                "command": ["env"],
+                "env": env
+            }]
+        }
+    })
+}
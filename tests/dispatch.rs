@@ -0,0 +1,78 @@
+//! End-to-end dispatch against a kind / k3d cluster. Run with `cargo test --features testing`
+use bakkutteh::testing::Harness;
+use k8s_openapi::api::batch::v1::Job;
+use std::process::Command;
+
+// Answers of the prompts following the review of the env
+const SKIP_EDITION: [(&str, &str); 4] = [
+    ("confirm-additional-env", "false"),
+    ("confirm-resources", "false"),
+    ("confirm-metadata target=job", "false"),
+    ("confirm-metadata target=pods", "false"),
+];
+
+fn env_value(job: &Job, name: &str) -> Option<String> {
+    job.spec
+        .as_ref()?
+        .template
+        .spec
+        .as_ref()?
+        .containers
+        .iter()
+        .flat_map(|container| container.env.iter().flatten())
+        .find(|env| env.name == name)
+        .and_then(|env| env.value.clone())
+}
+
+// The prompts share the replayed session of the process, the dispatches are run one after the other
+#[tokio::test]
+async fn expect_to_dispatch_job_from_sources() {
+    let harness = Harness::start().await.unwrap();
+
+    // The env of the cronjob is edited and the job is waited for
+    harness
+        .seed_cron_job("report", &[("GREETING", "hello")])
+        .await
+        .unwrap();
+    let job = harness
+        .dispatch(
+            "report",
+            &["--scheduler-name", "default-scheduler", "--wait", "3m"],
+            &[&[("env-title name=GREETING", "bonjour")], &SKIP_EDITION[..]].concat(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(env_value(&job, "GREETING").as_deref(), Some("bonjour"));
+    assert_eq!(job.status.and_then(|status| status.succeeded), Some(1));
+
+    // The spec of the deployment is converted to a job which never restarts
+    harness
+        .seed_deployment("api", &[("PORT", "8080")])
+        .await
+        .unwrap();
+    let job = harness
+        .dispatch(
+            "api",
+            &["--deployment", "--scheduler-name", "default-scheduler"],
+            &[&[("env-title name=PORT", "8080")], &SKIP_EDITION[..]].concat(),
+        )
+        .await
+        .unwrap();
+
+    let restart_policy = job
+        .spec
+        .and_then(|spec| spec.template.spec)
+        .and_then(|spec| spec.restart_policy);
+    assert_eq!(restart_policy.as_deref(), Some("Never"));
+
+    // A failed dispatch exits with a non-zero code so that the CI pipelines fail as well
+    let status = Command::new(env!("CARGO_BIN_EXE_bakkutteh"))
+        .args(["--non-interactive", "-n", &harness.namespace])
+        .args(["-j", "missing", "--scheduler-name", "default-scheduler"])
+        .status()
+        .unwrap();
+    assert_eq!(status.code(), Some(1));
+
+    harness.cleanup().await.unwrap();
+}